@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+/// How a `Clip` continues after its last frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PlayMode {
+    /// Starts over from the first frame.
+    #[default]
+    Loop,
+    /// Plays backwards to the first frame, then forwards again, without repeating the ends.
+    PingPong,
+    /// Stops on the last frame.
+    Once,
+}
+
+/// One step of a `Clip`: the value shown, for how long, and the events fired when it starts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame<T> {
+    /// What the frame shows, like a `TextureRegion`, a `Color` or a whole `Shape`.
+    pub value: T,
+    pub duration: Duration,
+    /// Names of the events returned by `Animation::update` when the frame is entered.
+    pub events: Vec<String>,
+}
+
+/// A named sequence of frames, like the walk cycle of a character.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clip<T> {
+    pub name: String,
+    pub frames: Vec<Frame<T>>,
+    pub mode: PlayMode,
+}
+
+impl<T> Frame<T> {
+    pub fn new(value: T, duration: Duration) -> Self {
+        Frame {
+            value,
+            duration,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn with_event(mut self, name: impl Into<String>) -> Self {
+        self.events.push(name.into());
+        self
+    }
+}
+
+impl<T> Clip<T> {
+    /// Creates a looping clip without frames.
+    pub fn new(name: impl Into<String>) -> Self {
+        Clip {
+            name: name.into(),
+            frames: Vec::new(),
+            mode: PlayMode::default(),
+        }
+    }
+
+    /// Creates a looping clip showing each value for the same `duration`.
+    pub fn uniform(
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = T>,
+        duration: Duration,
+    ) -> Self {
+        values.into_iter().fold(Self::new(name), |clip, value| {
+            clip.with_frame(value, duration)
+        })
+    }
+
+    /// Appends a frame showing `value` for `duration`.
+    pub fn with_frame(self, value: T, duration: Duration) -> Self {
+        self.with(Frame::new(value, duration))
+    }
+
+    /// Appends `frame`.
+    pub fn with(mut self, frame: Frame<T>) -> Self {
+        self.frames.push(frame);
+        self
+    }
+
+    pub fn with_mode(self, mode: PlayMode) -> Self {
+        Clip { mode, ..self }
+    }
+
+    /// Fires the event `name` whenever frame `index` is entered.
+    ///
+    /// # Panics
+    /// Panics if there is no frame `index`.
+    pub fn with_event(mut self, index: usize, name: impl Into<String>) -> Self {
+        self.frames[index].events.push(name.into());
+        self
+    }
+
+    /// Plays the frames in the opposite order.
+    pub fn reversed(mut self) -> Self {
+        self.frames.reverse();
+        self
+    }
+
+    /// Time it takes to play every frame once.
+    pub fn duration(&self) -> Duration {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+}
@@ -0,0 +1,5 @@
+mod clip;
+mod playback;
+
+pub use clip::{Clip, Frame, PlayMode};
+pub use playback::Animation;
@@ -0,0 +1,209 @@
+use std::time::Duration;
+
+use crate::animation::clip::{Clip, PlayMode};
+
+/// Named clips of frames and the position of the one playing.
+///
+/// `T` is whatever changes from frame to frame, like the `TextureRegion` of a sprite, the index
+/// of a `SheetFrame` or a `Color`. Call `update` once per tick with the time since the last one.
+#[derive(Debug, Clone)]
+pub struct Animation<T> {
+    clips: Vec<Clip<T>>,
+    playing: Option<Playback>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Playback {
+    clip: usize,
+    frame: usize,
+    /// Time spent on the current frame.
+    elapsed: Duration,
+    /// Whether a `PingPong` clip is playing towards its last frame.
+    forward: bool,
+    finished: bool,
+    /// Whether the events of the first frame were returned yet.
+    entered: bool,
+}
+
+impl Playback {
+    fn new(clip: usize) -> Self {
+        Self {
+            clip,
+            frame: 0,
+            elapsed: Duration::ZERO,
+            forward: true,
+            finished: false,
+            entered: false,
+        }
+    }
+}
+
+impl<T> Default for Animation<T> {
+    fn default() -> Self {
+        Self {
+            clips: Vec::new(),
+            playing: None,
+        }
+    }
+}
+
+impl<T> Animation<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `clip`, replacing any clip with the same name.
+    pub fn with_clip(mut self, clip: Clip<T>) -> Self {
+        self.add_clip(clip);
+        self
+    }
+
+    /// Adds `clip`, replacing any clip with the same name. Replacing the playing clip stops it.
+    pub fn add_clip(&mut self, clip: Clip<T>) -> &mut Self {
+        match self.clips.iter().position(|other| other.name == clip.name) {
+            Some(index) => {
+                self.clips[index] = clip;
+                if self.playing.is_some_and(|playing| playing.clip == index) {
+                    self.playing = None;
+                }
+            }
+            None => self.clips.push(clip),
+        }
+        self
+    }
+
+    pub fn clip(&self, name: &str) -> Option<&Clip<T>> {
+        self.clips.iter().find(|clip| clip.name == name)
+    }
+
+    pub fn clips(&self) -> &[Clip<T>] {
+        &self.clips
+    }
+
+    /// Starts playing the clip `name` from its first frame, unless it is already playing.
+    /// Returns `false` if there is no clip `name`.
+    pub fn play(&mut self, name: &str) -> bool {
+        let Some(index) = self.clips.iter().position(|clip| clip.name == name) else {
+            return false;
+        };
+        if self.playing.is_none_or(|playing| playing.clip != index) {
+            self.playing = Some(Playback::new(index));
+        }
+        true
+    }
+
+    /// Plays the current clip again from its first frame.
+    pub fn restart(&mut self) {
+        if let Some(playing) = &mut self.playing {
+            *playing = Playback::new(playing.clip);
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.playing = None;
+    }
+
+    /// Name of the clip playing.
+    pub fn playing(&self) -> Option<&str> {
+        self.playing
+            .map(|playing| self.clips[playing.clip].name.as_str())
+    }
+
+    /// Index of the current frame in the playing clip.
+    pub fn frame_index(&self) -> Option<usize> {
+        self.playing.map(|playing| playing.frame)
+    }
+
+    /// Value of the current frame, `None` if nothing is playing or the clip has no frames.
+    pub fn frame(&self) -> Option<&T> {
+        let playing = self.playing?;
+        self.clips[playing.clip]
+            .frames
+            .get(playing.frame)
+            .map(|frame| &frame.value)
+    }
+
+    /// Whether a `PlayMode::Once` clip has reached the end of its last frame.
+    pub fn is_finished(&self) -> bool {
+        self.playing.is_some_and(|playing| playing.finished)
+    }
+
+    /// Advances the playing clip by `delta` and returns the events of the frames entered,
+    /// in order. The events of the first frame are returned by the first update after `play`.
+    ///
+    /// A `delta` spanning several laps of a `Loop` or `PingPong` clip, like the first one after
+    /// a pause, only plays one of them: the events of the other whole laps are not returned,
+    /// and the frame shown is the same as if every lap had been played.
+    pub fn update(&mut self, delta: Duration) -> Vec<String> {
+        let mut events = Vec::new();
+        let Some(playing) = &mut self.playing else {
+            return events;
+        };
+        let clip = &self.clips[playing.clip];
+        if clip.frames.is_empty() {
+            return events;
+        }
+        if !playing.entered {
+            playing.entered = true;
+            events.extend_from_slice(&clip.frames[0].events);
+        }
+        // A clip without duration would never leave its frames
+        if playing.finished || clip.duration().is_zero() {
+            return events;
+        }
+
+        playing.elapsed += delta;
+        // Playing a whole lap ends on the same frame, so all but one are skipped
+        if let Some(lap) = lap_duration(clip)
+            && playing.elapsed >= lap * 2
+        {
+            let into_lap = playing.elapsed.as_nanos() % lap.as_nanos();
+            playing.elapsed = lap + Duration::from_nanos(into_lap as u64);
+        }
+
+        let last = clip.frames.len() - 1;
+        loop {
+            let duration = clip.frames[playing.frame].duration;
+            if playing.elapsed < duration {
+                break;
+            }
+            if clip.mode == PlayMode::Once && playing.frame == last {
+                playing.elapsed = duration;
+                playing.finished = true;
+                break;
+            }
+
+            playing.elapsed -= duration;
+            playing.frame = match clip.mode {
+                PlayMode::Loop if playing.frame == last => 0,
+                PlayMode::PingPong if last == 0 => 0,
+                PlayMode::PingPong if playing.forward && playing.frame == last => {
+                    playing.forward = false;
+                    last - 1
+                }
+                PlayMode::PingPong if !playing.forward && playing.frame == 0 => {
+                    playing.forward = true;
+                    1
+                }
+                PlayMode::PingPong if !playing.forward => playing.frame - 1,
+                _ => playing.frame + 1,
+            };
+            events.extend_from_slice(&clip.frames[playing.frame].events);
+        }
+        events
+    }
+}
+
+/// Time after which a repeating clip is back on the same frame, going the same direction.
+fn lap_duration<T>(clip: &Clip<T>) -> Option<Duration> {
+    let (first, last) = (clip.frames.first()?, clip.frames.last()?);
+    match clip.mode {
+        PlayMode::Loop => Some(clip.duration()),
+        // The frames at both ends are shown once per lap, the others twice
+        PlayMode::PingPong if clip.frames.len() > 1 => {
+            Some(clip.duration() * 2 - first.duration - last.duration)
+        }
+        PlayMode::PingPong => Some(clip.duration()),
+        PlayMode::Once => None,
+    }
+}
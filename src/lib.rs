@@ -1,2 +1,3 @@
+pub mod animation;
 pub mod math;
 pub mod rendering;
@@ -1,9 +1,9 @@
-use std::sync::Arc;
+use std::{f32::consts::TAU, sync::Arc};
 
 use stellar_engine::{
     math::Vector2,
     rendering::{
-        RendererState,
+        Color, RendererState,
         shapes::{Quad, Shape, Triangle},
     },
 };
@@ -55,21 +55,17 @@ impl ApplicationHandler<RendererState> for App {
             }
             WindowEvent::CursorMoved { position, .. } => {
                 if let Some(state) = &self.state {
-                    let (width, height) = state.window_size();
-                    let (width, height) = (width as f32, height as f32);
-                    let x = position.x as f32 - (width * 0.5);
-                    let y = (height - position.y as f32) - (height * 0.5);
-
-                    self.mouse_position = Vector2::new(x, y);
+                    let screen_position = Vector2::new(position.x as f32, position.y as f32);
+                    self.mouse_position = state.camera.screen_to_world(screen_position);
                 }
             }
-            WindowEvent::MouseInput { state, .. } => {
-                if state.is_pressed() {
-                    println!("{:?}", self.mouse_position);
-                    let shape = Shape::Quad(Quad::square(self.mouse_position, 50.0));
-                    if let Some(state) = &mut self.state {
-                        state.render_queue.add(shape);
-                    }
+            WindowEvent::MouseInput { state, .. } if state.is_pressed() => {
+                println!("{:?}", self.mouse_position);
+                let shape = Shape::Quad(
+                    Quad::square(self.mouse_position, 50.0).rotated(fastrand::f32() * TAU),
+                );
+                if let Some(state) = &mut self.state {
+                    state.render_queue.add(shape);
                 }
             }
             WindowEvent::KeyboardInput {
@@ -92,9 +88,11 @@ impl ApplicationHandler<RendererState> for App {
                             Vector2::new(x, y)
                         };
 
-                        let shape = Shape::Triangle(Triangle {
-                            points: [rand_vector2(), rand_vector2(), rand_vector2()],
-                        });
+                        let color = Color::from_hsv(fastrand::f32() * 360.0, 0.8, 1.0);
+                        let shape = Shape::Triangle(
+                            Triangle::new([rand_vector2(), rand_vector2(), rand_vector2()])
+                                .with_color(color),
+                        );
 
                         state.render_queue.add(shape);
                     }
@@ -0,0 +1,147 @@
+use num_traits::Float;
+use std::ops::{Mul, MulAssign};
+
+use crate::math::{
+    matrix::{Matrix2, Matrix3},
+    vector::{Vector2, VectorItem},
+};
+
+/// A 2D transform that keeps parallel lines parallel: a linear `matrix`, like a rotation,
+/// scale or shear, followed by a `translation`.
+///
+/// Cheaper than a `Matrix3` for the same transforms, as the bottom row is known.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine2<T>
+where
+    T: VectorItem,
+{
+    pub matrix: Matrix2<T>,
+    pub translation: Vector2<T>,
+}
+
+impl<T> Default for Affine2<T>
+where
+    T: VectorItem,
+{
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T> Affine2<T>
+where
+    T: VectorItem,
+{
+    pub fn new(matrix: Matrix2<T>, translation: Vector2<T>) -> Self {
+        Self {
+            matrix,
+            translation,
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::from_matrix(Matrix2::identity())
+    }
+
+    pub fn from_matrix(matrix: Matrix2<T>) -> Self {
+        Self::new(matrix, Vector2::default())
+    }
+
+    pub fn from_translation(translation: Vector2<T>) -> Self {
+        Self::new(Matrix2::identity(), translation)
+    }
+
+    /// Scales x by `scale.x` and y by `scale.y`.
+    pub fn from_scale(scale: Vector2<T>) -> Self {
+        Self::from_matrix(Matrix2::from_scale(scale))
+    }
+
+    /// Rotates by `angle` radians counter-clockwise around the origin.
+    pub fn from_angle(angle: T) -> Self
+    where
+        T: Float,
+    {
+        Self::from_matrix(Matrix2::from_angle(angle))
+    }
+
+    /// Scales, then rotates by `angle` radians counter-clockwise, then translates.
+    pub fn from_scale_angle_translation(
+        scale: Vector2<T>,
+        angle: T,
+        translation: Vector2<T>,
+    ) -> Self
+    where
+        T: Float,
+    {
+        Self::new(
+            Matrix2::from_angle(angle) * Matrix2::from_scale(scale),
+            translation,
+        )
+    }
+
+    /// Factor areas are scaled by, negative if the transform mirrors.
+    pub fn determinant(&self) -> T {
+        self.matrix.determinant()
+    }
+
+    /// The transform undoing this one, `None` if it collapses the plane onto a line or point.
+    pub fn inverse(&self) -> Option<Self>
+    where
+        T: Float,
+    {
+        let matrix = self.matrix.inverse()?;
+        Some(Self::new(matrix, -(matrix * self.translation)))
+    }
+
+    pub fn transform_point(&self, point: Vector2<T>) -> Vector2<T> {
+        self.matrix * point + self.translation
+    }
+
+    /// Transforms a direction, which is not moved by the translation.
+    pub fn transform_vector(&self, vector: Vector2<T>) -> Vector2<T> {
+        self.matrix * vector
+    }
+}
+
+impl<T> Mul for Affine2<T>
+where
+    T: VectorItem,
+{
+    type Output = Self;
+
+    /// Applies `rhs` first, then `self`.
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.matrix * rhs.matrix,
+            self.transform_point(rhs.translation),
+        )
+    }
+}
+
+impl<T> MulAssign for Affine2<T>
+where
+    T: VectorItem,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> Mul<Vector2<T>> for Affine2<T>
+where
+    T: VectorItem,
+{
+    type Output = Vector2<T>;
+
+    /// Same as `transform_point`.
+    fn mul(self, rhs: Vector2<T>) -> Self::Output {
+        self.transform_point(rhs)
+    }
+}
+
+/// Column-major 4x4 matrix for uniform buffers, leaving z unchanged.
+impl From<Affine2<f32>> for [[f32; 4]; 4] {
+    fn from(affine: Affine2<f32>) -> Self {
+        Matrix3::from(affine).into()
+    }
+}
@@ -0,0 +1,130 @@
+use num_traits::Float;
+use std::ops::{Mul, MulAssign};
+
+use crate::math::vector::{Vector2, VectorItem};
+
+/// A 2x2 matrix stored as its columns, the vectors the x and y axes are mapped to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix2<T>
+where
+    T: VectorItem,
+{
+    pub x_axis: Vector2<T>,
+    pub y_axis: Vector2<T>,
+}
+
+impl<T> Default for Matrix2<T>
+where
+    T: VectorItem,
+{
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T> Matrix2<T>
+where
+    T: VectorItem,
+{
+    pub fn from_columns(x_axis: Vector2<T>, y_axis: Vector2<T>) -> Self {
+        Self { x_axis, y_axis }
+    }
+
+    pub fn from_rows(first: Vector2<T>, second: Vector2<T>) -> Self {
+        Self::from_columns(first, second).transpose()
+    }
+
+    pub fn identity() -> Self {
+        Self::from_scale(Vector2::new(T::one(), T::one()))
+    }
+
+    /// Scales x by `scale.x` and y by `scale.y`.
+    pub fn from_scale(scale: Vector2<T>) -> Self {
+        Self::from_columns(
+            Vector2::new(scale.x, T::zero()),
+            Vector2::new(T::zero(), scale.y),
+        )
+    }
+
+    /// Rotates by `angle` radians counter-clockwise.
+    pub fn from_angle(angle: T) -> Self
+    where
+        T: Float,
+    {
+        let (sin, cos) = angle.sin_cos();
+        Self::from_columns(Vector2::new(cos, sin), Vector2::new(-sin, cos))
+    }
+
+    /// Factor areas are scaled by, negative if the matrix mirrors.
+    pub fn determinant(&self) -> T {
+        self.x_axis.perp_dot(self.y_axis)
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_columns(
+            Vector2::new(self.x_axis.x, self.y_axis.x),
+            Vector2::new(self.x_axis.y, self.y_axis.y),
+        )
+    }
+
+    /// The matrix undoing this one, `None` if it collapses the plane onto a line or point.
+    pub fn inverse(&self) -> Option<Self>
+    where
+        T: Float,
+    {
+        let determinant = self.determinant();
+        if determinant == T::zero() || !determinant.is_finite() {
+            return None;
+        }
+        let inverse = T::one() / determinant;
+        Some(Self::from_columns(
+            Vector2::new(self.y_axis.y, -self.x_axis.y) * inverse,
+            Vector2::new(-self.y_axis.x, self.x_axis.x) * inverse,
+        ))
+    }
+}
+
+impl<T> Mul for Matrix2<T>
+where
+    T: VectorItem,
+{
+    type Output = Self;
+
+    /// Applies `rhs` first, then `self`.
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::from_columns(self * rhs.x_axis, self * rhs.y_axis)
+    }
+}
+
+impl<T> MulAssign for Matrix2<T>
+where
+    T: VectorItem,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> Mul<Vector2<T>> for Matrix2<T>
+where
+    T: VectorItem,
+{
+    type Output = Vector2<T>;
+
+    fn mul(self, rhs: Vector2<T>) -> Self::Output {
+        self.x_axis * rhs.x + self.y_axis * rhs.y
+    }
+}
+
+/// Column-major 4x4 matrix for uniform buffers, leaving z and w unchanged.
+impl From<Matrix2<f32>> for [[f32; 4]; 4] {
+    fn from(matrix: Matrix2<f32>) -> Self {
+        let Matrix2 { x_axis, y_axis } = matrix;
+        [
+            [x_axis.x, x_axis.y, 0.0, 0.0],
+            [y_axis.x, y_axis.y, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]
+    }
+}
@@ -0,0 +1,189 @@
+use num_traits::Float;
+use std::ops::{Mul, MulAssign};
+
+use crate::math::{
+    matrix::Affine2,
+    vector::{Vector2, Vector3, VectorItem},
+};
+
+/// A 3x3 matrix stored as its columns. Used on 2D positions in homogeneous coordinates,
+/// where the third column is the translation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix3<T>
+where
+    T: VectorItem,
+{
+    pub x_axis: Vector3<T>,
+    pub y_axis: Vector3<T>,
+    pub z_axis: Vector3<T>,
+}
+
+impl<T> Default for Matrix3<T>
+where
+    T: VectorItem,
+{
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T> Matrix3<T>
+where
+    T: VectorItem,
+{
+    pub fn from_columns(x_axis: Vector3<T>, y_axis: Vector3<T>, z_axis: Vector3<T>) -> Self {
+        Self {
+            x_axis,
+            y_axis,
+            z_axis,
+        }
+    }
+
+    pub fn from_rows(first: Vector3<T>, second: Vector3<T>, third: Vector3<T>) -> Self {
+        Self::from_columns(first, second, third).transpose()
+    }
+
+    pub fn identity() -> Self {
+        Self::from_scale(Vector2::new(T::one(), T::one()))
+    }
+
+    /// Moves 2D positions by `translation`.
+    pub fn from_translation(translation: Vector2<T>) -> Self {
+        Self {
+            z_axis: translation.extend(T::one()),
+            ..Self::identity()
+        }
+    }
+
+    /// Scales x by `scale.x` and y by `scale.y`.
+    pub fn from_scale(scale: Vector2<T>) -> Self {
+        Self::from_columns(
+            Vector3::new(scale.x, T::zero(), T::zero()),
+            Vector3::new(T::zero(), scale.y, T::zero()),
+            Vector3::new(T::zero(), T::zero(), T::one()),
+        )
+    }
+
+    /// Rotates 2D positions by `angle` radians counter-clockwise around the origin.
+    pub fn from_angle(angle: T) -> Self
+    where
+        T: Float,
+    {
+        Affine2::from_angle(angle).into()
+    }
+
+    pub fn determinant(&self) -> T {
+        self.x_axis.dot(self.y_axis.cross(self.z_axis))
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_columns(
+            Vector3::new(self.x_axis.x, self.y_axis.x, self.z_axis.x),
+            Vector3::new(self.x_axis.y, self.y_axis.y, self.z_axis.y),
+            Vector3::new(self.x_axis.z, self.y_axis.z, self.z_axis.z),
+        )
+    }
+
+    /// The matrix undoing this one, `None` if its determinant is zero.
+    pub fn inverse(&self) -> Option<Self>
+    where
+        T: Float,
+    {
+        let determinant = self.determinant();
+        if determinant == T::zero() || !determinant.is_finite() {
+            return None;
+        }
+        // The rows of the inverse are perpendicular to all columns but one
+        let inverse = T::one() / determinant;
+        Some(Self::from_rows(
+            self.y_axis.cross(self.z_axis) * inverse,
+            self.z_axis.cross(self.x_axis) * inverse,
+            self.x_axis.cross(self.y_axis) * inverse,
+        ))
+    }
+
+    /// Transforms a 2D position, which is moved by the translation.
+    pub fn transform_point(&self, point: Vector2<T>) -> Vector2<T> {
+        let transformed = *self * point.extend(T::one());
+        transformed.xy() / transformed.z
+    }
+
+    /// Transforms a 2D direction, which is not moved by the translation.
+    pub fn transform_vector(&self, vector: Vector2<T>) -> Vector2<T> {
+        (*self * vector.extend(T::zero())).xy()
+    }
+}
+
+impl<T> Mul for Matrix3<T>
+where
+    T: VectorItem,
+{
+    type Output = Self;
+
+    /// Applies `rhs` first, then `self`.
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::from_columns(self * rhs.x_axis, self * rhs.y_axis, self * rhs.z_axis)
+    }
+}
+
+impl<T> MulAssign for Matrix3<T>
+where
+    T: VectorItem,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> Mul<Vector3<T>> for Matrix3<T>
+where
+    T: VectorItem,
+{
+    type Output = Vector3<T>;
+
+    fn mul(self, rhs: Vector3<T>) -> Self::Output {
+        self.x_axis * rhs.x + self.y_axis * rhs.y + self.z_axis * rhs.z
+    }
+}
+
+impl<T> Mul<Vector2<T>> for Matrix3<T>
+where
+    T: VectorItem,
+{
+    type Output = Vector2<T>;
+
+    /// Same as `transform_point`.
+    fn mul(self, rhs: Vector2<T>) -> Self::Output {
+        self.transform_point(rhs)
+    }
+}
+
+impl<T> From<Affine2<T>> for Matrix3<T>
+where
+    T: VectorItem,
+{
+    fn from(affine: Affine2<T>) -> Self {
+        Self::from_columns(
+            affine.matrix.x_axis.extend(T::zero()),
+            affine.matrix.y_axis.extend(T::zero()),
+            affine.translation.extend(T::one()),
+        )
+    }
+}
+
+/// Column-major 4x4 matrix for uniform buffers, acting on x, y and w and leaving z unchanged.
+impl From<Matrix3<f32>> for [[f32; 4]; 4] {
+    fn from(matrix: Matrix3<f32>) -> Self {
+        let Matrix3 {
+            x_axis,
+            y_axis,
+            z_axis,
+        } = matrix;
+        [
+            [x_axis.x, x_axis.y, 0.0, x_axis.z],
+            [y_axis.x, y_axis.y, 0.0, y_axis.z],
+            [0.0, 0.0, 1.0, 0.0],
+            [z_axis.x, z_axis.y, 0.0, z_axis.z],
+        ]
+    }
+}
@@ -0,0 +1,7 @@
+mod affine2;
+mod matrix2;
+mod matrix3;
+
+pub use affine2::Affine2;
+pub use matrix2::Matrix2;
+pub use matrix3::Matrix3;
@@ -1,3 +1,5 @@
+mod matrix;
 mod vector;
 
-pub use vector::{Vector2, VectorItem};
+pub use matrix::{Affine2, Matrix2, Matrix3};
+pub use vector::{Vector2, Vector3, Vector4, VectorItem};
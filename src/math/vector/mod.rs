@@ -1,5 +1,23 @@
+/// Used to generate swizzle methods, which build a vector from some of the components of
+/// another, like `v.yx()` for `Vector2::new(v.y, v.x)`.
+macro_rules! impl_swizzles {
+    ($target:ident: $(($($component:ident),+)),+ $(,)?) => {
+        paste! {
+            $(
+                pub fn [<$($component)+>](&self) -> $target<T> {
+                    $target::new($(self.$component),+)
+                }
+            )+
+        }
+    };
+}
+
 mod item;
 mod vector2;
+mod vector3;
+mod vector4;
 
 pub use item::VectorItem;
 pub use vector2::Vector2;
+pub use vector3::Vector3;
+pub use vector4::Vector4;
@@ -1,11 +1,11 @@
-use num_traits::Float;
+use num_traits::{Float, Signed};
 use paste::paste;
 use std::ops::RemAssign;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
 
-use crate::math::vector::VectorItem;
+use crate::math::vector::{Vector3, VectorItem};
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Vector2<T>
 where
     T: VectorItem,
@@ -32,6 +32,195 @@ where
     {
         self.length_squared().sqrt()
     }
+
+    pub fn dot(&self, other: Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the 3D cross product, also called the 2D cross product.
+    /// Positive when `other` is counter-clockwise from `self`.
+    pub fn perp_dot(&self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Rotated by 90 degrees counter-clockwise.
+    pub fn perpendicular(&self) -> Self
+    where
+        T: Neg<Output = T>,
+    {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Same direction with a length of one. The components of the zero vector become NaN,
+    /// use `try_normalize` if the vector can be zero.
+    pub fn normalize(&self) -> Self
+    where
+        T: Float,
+    {
+        *self / self.length()
+    }
+
+    /// Same direction with a length of one, `None` if the vector is zero or not finite.
+    pub fn try_normalize(&self) -> Option<Self>
+    where
+        T: Float,
+    {
+        let length = self.length();
+        (length > T::zero() && length.is_finite()).then(|| *self / length)
+    }
+
+    pub fn distance_squared(&self, other: Self) -> T {
+        (*self - other).length_squared()
+    }
+
+    pub fn distance(&self, other: Self) -> T
+    where
+        T: Float,
+    {
+        (*self - other).length()
+    }
+
+    /// Vector of length one pointing `angle` radians counter-clockwise from the x axis.
+    pub fn from_angle(angle: T) -> Self
+    where
+        T: Float,
+    {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(cos, sin)
+    }
+
+    /// Radians from the x axis to the vector, counter-clockwise in `-PI..=PI`.
+    pub fn angle(&self) -> T
+    where
+        T: Float,
+    {
+        self.y.atan2(self.x)
+    }
+
+    /// Radians to turn `self` by to point along `other`, counter-clockwise in `-PI..=PI`.
+    pub fn angle_between(&self, other: Self) -> T
+    where
+        T: Float,
+    {
+        self.perp_dot(other).atan2(self.dot(other))
+    }
+
+    /// Rotated by `angle` radians counter-clockwise.
+    pub fn rotate(&self, angle: T) -> Self
+    where
+        T: Float,
+    {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// The part of `self` pointing along `other`. Projecting onto the zero vector gives NaN.
+    pub fn project_onto(&self, other: Self) -> Self
+    where
+        T: Float,
+    {
+        other * (self.dot(other) / other.length_squared())
+    }
+
+    /// Mirrored at a line with the `normal`, which must have a length of one,
+    /// like a ball bouncing off a wall.
+    pub fn reflect(&self, normal: Self) -> Self {
+        let two = T::one() + T::one();
+        *self - normal * (two * self.dot(normal))
+    }
+
+    /// Interpolates linearly from `self` at `t = 0` to `other` at `t = 1`.
+    pub fn lerp(&self, other: Self, t: T) -> Self
+    where
+        T: Float,
+    {
+        *self + (other - *self) * t
+    }
+
+    /// Same direction with the length clamped to `min..=max`. The zero vector stays zero.
+    pub fn clamp_length(&self, min: T, max: T) -> Self
+    where
+        T: Float,
+    {
+        let length = self.length();
+        if length == T::zero() {
+            *self
+        } else {
+            *self * (length.max(min).min(max) / length)
+        }
+    }
+
+    /// The smaller of each component.
+    pub fn min(&self, other: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        Self::new(
+            if other.x < self.x { other.x } else { self.x },
+            if other.y < self.y { other.y } else { self.y },
+        )
+    }
+
+    /// The larger of each component.
+    pub fn max(&self, other: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        Self::new(
+            if other.x > self.x { other.x } else { self.x },
+            if other.y > self.y { other.y } else { self.y },
+        )
+    }
+
+    /// The absolute value of each component.
+    pub fn abs(&self) -> Self
+    where
+        T: Signed,
+    {
+        Self::new(self.x.abs(), self.y.abs())
+    }
+
+    /// Whether each component differs from the one of `other` by at most `epsilon`.
+    pub fn approx_eq(&self, other: Self, epsilon: T) -> bool
+    where
+        T: Float,
+    {
+        (self.x - other.x).abs() <= epsilon && (self.y - other.y).abs() <= epsilon
+    }
+
+    /// Adds `z` as the third component.
+    pub fn extend(self, z: T) -> Vector3<T> {
+        Vector3::new(self.x, self.y, z)
+    }
+
+    impl_swizzles!(Vector2: (x, y), (y, x));
+}
+
+impl<T> From<[T; 2]> for Vector2<T>
+where
+    T: VectorItem,
+{
+    fn from([x, y]: [T; 2]) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Vector2<T>> for [T; 2]
+where
+    T: VectorItem,
+{
+    fn from(vector: Vector2<T>) -> Self {
+        [vector.x, vector.y]
+    }
+}
+
+impl<T> From<(T, T)> for Vector2<T>
+where
+    T: VectorItem,
+{
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
 }
 
 /// Used to generate basic **vector** mathematical operations for vectors.
@@ -0,0 +1,172 @@
+use num_traits::Float;
+use paste::paste;
+use std::ops::RemAssign;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+use crate::math::vector::{Vector2, Vector4, VectorItem};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vector3<T>
+where
+    T: VectorItem,
+{
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vector3<T>
+where
+    T: VectorItem,
+{
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn length_squared(&self) -> T {
+        self.x * self.x + self.y * self.y + self.z * self.z
+    }
+
+    pub fn length(&self) -> T
+    where
+        T: Float,
+    {
+        self.length_squared().sqrt()
+    }
+
+    pub fn dot(&self, other: Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Vector perpendicular to both, following the right-hand rule.
+    pub fn cross(&self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Adds `w` as the fourth component, 1 for a point and 0 for a direction
+    /// in homogeneous coordinates.
+    pub fn extend(self, w: T) -> Vector4<T> {
+        Vector4::new(self.x, self.y, self.z, w)
+    }
+
+    /// Drops the `z` component.
+    pub fn truncate(self) -> Vector2<T> {
+        Vector2::new(self.x, self.y)
+    }
+
+    impl_swizzles!(Vector2: (x, y), (x, z), (y, x), (y, z), (z, x), (z, y));
+    impl_swizzles!(
+        Vector3: (x, y, z),
+        (x, z, y),
+        (y, x, z),
+        (y, z, x),
+        (z, x, y),
+        (z, y, x),
+    );
+}
+
+impl<T> From<[T; 3]> for Vector3<T>
+where
+    T: VectorItem,
+{
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T> From<Vector3<T>> for [T; 3]
+where
+    T: VectorItem,
+{
+    fn from(vector: Vector3<T>) -> Self {
+        [vector.x, vector.y, vector.z]
+    }
+}
+
+impl<T> From<(T, T, T)> for Vector3<T>
+where
+    T: VectorItem,
+{
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+/// Used to generate basic **vector** mathematical operations for vectors.
+macro_rules! impl_operators_vector3 {
+    ($trait:ty, $op:tt, $aop:tt) => {
+        paste! {
+            impl<T> $trait for Vector3<T> where T: VectorItem {
+                type Output = Self;
+
+                fn [<$trait:lower>](self, rhs: Self) -> Self::Output {
+                    Self {
+                        x: self.x $op rhs.x,
+                        y: self.y $op rhs.y,
+                        z: self.z $op rhs.z,
+                    }
+                }
+            }
+
+            impl<T> [<$trait Assign>] for Vector3<T> where T: VectorItem {
+                fn [<$trait:lower _assign>](&mut self, rhs: Self) {
+                    self.x $aop rhs.x;
+                    self.y $aop rhs.y;
+                    self.z $aop rhs.z;
+                }
+            }
+        }
+    };
+}
+
+/// Used to generate basic **scalar** mathematical operations for vectors.
+macro_rules! impl_scalar_operators_vector3 {
+    ($trait:ty, $op:tt, $aop:tt) => {
+        paste! {
+            impl<T> $trait<T> for Vector3<T> where T: VectorItem {
+                type Output = Self;
+
+                fn [<$trait:lower>](self, rhs: T) -> Self::Output {
+                    Self {
+                        x: self.x $op rhs,
+                        y: self.y $op rhs,
+                        z: self.z $op rhs,
+                    }
+                }
+            }
+
+            impl<T> [<$trait Assign>]<T> for Vector3<T> where T: VectorItem {
+                fn [<$trait:lower _assign>](&mut self, rhs: T) {
+                    self.x $aop rhs;
+                    self.y $aop rhs;
+                    self.z $aop rhs;
+                }
+            }
+        }
+    };
+}
+
+impl_operators_vector3!(Add, +, +=);
+impl_operators_vector3!(Sub, -, -=);
+impl_scalar_operators_vector3!(Mul, *, *=);
+impl_scalar_operators_vector3!(Div, /, /=);
+impl_scalar_operators_vector3!(Rem, %, %=);
+
+impl<T> Neg for Vector3<T>
+where
+    T: VectorItem + Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
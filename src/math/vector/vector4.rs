@@ -0,0 +1,190 @@
+use num_traits::Float;
+use paste::paste;
+use std::ops::RemAssign;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+use crate::math::vector::{Vector2, Vector3, VectorItem};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vector4<T>
+where
+    T: VectorItem,
+{
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+impl<T> Vector4<T>
+where
+    T: VectorItem,
+{
+    pub fn new(x: T, y: T, z: T, w: T) -> Self {
+        Self { x, y, z, w }
+    }
+
+    pub fn length_squared(&self) -> T {
+        self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w
+    }
+
+    pub fn length(&self) -> T
+    where
+        T: Float,
+    {
+        self.length_squared().sqrt()
+    }
+
+    /// Drops the `w` component.
+    pub fn truncate(self) -> Vector3<T> {
+        Vector3::new(self.x, self.y, self.z)
+    }
+
+    impl_swizzles!(
+        Vector2: (x, y),
+        (x, z),
+        (x, w),
+        (y, x),
+        (y, z),
+        (y, w),
+        (z, x),
+        (z, y),
+        (z, w),
+        (w, x),
+        (w, y),
+        (w, z),
+    );
+    impl_swizzles!(
+        Vector3: (x, y, z),
+        (x, y, w),
+        (x, z, y),
+        (x, z, w),
+        (x, w, y),
+        (x, w, z),
+        (y, x, z),
+        (y, x, w),
+        (y, z, x),
+        (y, z, w),
+        (y, w, x),
+        (y, w, z),
+        (z, x, y),
+        (z, x, w),
+        (z, y, x),
+        (z, y, w),
+        (z, w, x),
+        (z, w, y),
+        (w, x, y),
+        (w, x, z),
+        (w, y, x),
+        (w, y, z),
+        (w, z, x),
+        (w, z, y),
+    );
+}
+
+impl<T> From<[T; 4]> for Vector4<T>
+where
+    T: VectorItem,
+{
+    fn from([x, y, z, w]: [T; 4]) -> Self {
+        Self::new(x, y, z, w)
+    }
+}
+
+impl<T> From<Vector4<T>> for [T; 4]
+where
+    T: VectorItem,
+{
+    fn from(vector: Vector4<T>) -> Self {
+        [vector.x, vector.y, vector.z, vector.w]
+    }
+}
+
+impl<T> From<(T, T, T, T)> for Vector4<T>
+where
+    T: VectorItem,
+{
+    fn from((x, y, z, w): (T, T, T, T)) -> Self {
+        Self::new(x, y, z, w)
+    }
+}
+
+/// Used to generate basic **vector** mathematical operations for vectors.
+macro_rules! impl_operators_vector4 {
+    ($trait:ty, $op:tt, $aop:tt) => {
+        paste! {
+            impl<T> $trait for Vector4<T> where T: VectorItem {
+                type Output = Self;
+
+                fn [<$trait:lower>](self, rhs: Self) -> Self::Output {
+                    Self {
+                        x: self.x $op rhs.x,
+                        y: self.y $op rhs.y,
+                        z: self.z $op rhs.z,
+                        w: self.w $op rhs.w,
+                    }
+                }
+            }
+
+            impl<T> [<$trait Assign>] for Vector4<T> where T: VectorItem {
+                fn [<$trait:lower _assign>](&mut self, rhs: Self) {
+                    self.x $aop rhs.x;
+                    self.y $aop rhs.y;
+                    self.z $aop rhs.z;
+                    self.w $aop rhs.w;
+                }
+            }
+        }
+    };
+}
+
+/// Used to generate basic **scalar** mathematical operations for vectors.
+macro_rules! impl_scalar_operators_vector4 {
+    ($trait:ty, $op:tt, $aop:tt) => {
+        paste! {
+            impl<T> $trait<T> for Vector4<T> where T: VectorItem {
+                type Output = Self;
+
+                fn [<$trait:lower>](self, rhs: T) -> Self::Output {
+                    Self {
+                        x: self.x $op rhs,
+                        y: self.y $op rhs,
+                        z: self.z $op rhs,
+                        w: self.w $op rhs,
+                    }
+                }
+            }
+
+            impl<T> [<$trait Assign>]<T> for Vector4<T> where T: VectorItem {
+                fn [<$trait:lower _assign>](&mut self, rhs: T) {
+                    self.x $aop rhs;
+                    self.y $aop rhs;
+                    self.z $aop rhs;
+                    self.w $aop rhs;
+                }
+            }
+        }
+    };
+}
+
+impl_operators_vector4!(Add, +, +=);
+impl_operators_vector4!(Sub, -, -=);
+impl_scalar_operators_vector4!(Mul, *, *=);
+impl_scalar_operators_vector4!(Div, /, /=);
+impl_scalar_operators_vector4!(Rem, %, %=);
+
+impl<T> Neg for Vector4<T>
+where
+    T: VectorItem + Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: -self.w,
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+};
+
+use crate::{
+    math::Vector2,
+    rendering::{
+        shapes::Sprite,
+        texture::{Texture, TextureError, TextureFilter, TextureRegion},
+    },
+};
+
+/// Many images packed into one texture, so sprites showing any of them share one draw batch.
+#[derive(Debug, Clone)]
+pub struct TextureAtlas {
+    texture: Texture,
+    regions: HashMap<String, TextureRegion>,
+}
+
+/// Packs named images into a `TextureAtlas`.
+///
+/// The images are separated by `padding` transparent pixels, so neighbours never show up at the
+/// edges of a sprite. Each image is also surrounded by `bleed` copies of its edge pixels, which
+/// keeps linear filtering from blending its edges with the padding.
+#[derive(Debug, Clone)]
+pub struct AtlasBuilder {
+    images: Vec<(String, Texture)>,
+    padding: u32,
+    bleed: u32,
+    max_size: u32,
+    filter: TextureFilter,
+}
+
+/// Error returned when the images can't be packed into an atlas.
+#[derive(Debug)]
+pub enum AtlasError {
+    /// Two images were added with the same name.
+    DuplicateName(String),
+    /// The image, with its padding and bleed, is larger than the maximum size of the atlas.
+    ImageTooLarge(String),
+    /// All images together do not fit into an atlas of the maximum size.
+    OutOfSpace {
+        max_size: u32,
+    },
+    Texture(TextureError),
+}
+
+impl TextureAtlas {
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Where the image added as `name` is in the texture, without its bleed.
+    pub fn region(&self, name: &str) -> Option<TextureRegion> {
+        self.regions.get(name).copied()
+    }
+
+    /// Iterates over the names and regions of all images, in no particular order.
+    pub fn regions(&self) -> impl Iterator<Item = (&str, TextureRegion)> {
+        self.regions
+            .iter()
+            .map(|(name, region)| (name.as_str(), *region))
+    }
+
+    /// A sprite showing the image added as `name`, with one world unit per pixel.
+    pub fn sprite(&self, name: &str, center: Vector2<f32>) -> Option<Sprite> {
+        let region = self.region(name)?;
+        Some(Sprite::new(self.texture.clone(), center).with_region(region))
+    }
+}
+
+impl Default for AtlasBuilder {
+    fn default() -> Self {
+        Self {
+            images: Vec::new(),
+            padding: 1,
+            bleed: 1,
+            // `max_texture_dimension_2d` of `wgpu::Limits::default()`, which `RendererState`
+            // requests. Downlevel and WebGL2 devices only guarantee 2048.
+            max_size: 8192,
+            filter: TextureFilter::default(),
+        }
+    }
+}
+
+impl AtlasBuilder {
+    /// Creates a builder with 1 pixel of padding and 1 pixel of bleed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an image, its region can be looked up by `name` in the built atlas.
+    pub fn add(&mut self, name: impl Into<String>, image: Texture) -> &mut Self {
+        self.images.push((name.into(), image));
+        self
+    }
+
+    /// Sets the number of transparent pixels between the images.
+    pub fn with_padding(self, padding: u32) -> Self {
+        Self { padding, ..self }
+    }
+
+    /// Sets the number of times the edge pixels of each image are repeated around it.
+    pub fn with_bleed(self, bleed: u32) -> Self {
+        Self { bleed, ..self }
+    }
+
+    /// Sets the largest width and height the atlas may grow to, 8192 by default.
+    /// Lower it to the device's `max_texture_dimension_2d` on downlevel targets.
+    pub fn with_max_size(self, max_size: u32) -> Self {
+        Self { max_size, ..self }
+    }
+
+    /// Sets the filter of the atlas texture.
+    pub fn with_filter(self, filter: TextureFilter) -> Self {
+        Self { filter, ..self }
+    }
+
+    /// Packs the images into the smallest power of two texture they fit in.
+    pub fn build(&self) -> Result<TextureAtlas, AtlasError> {
+        let mut names = HashSet::new();
+        if let Some((name, _)) = self.images.iter().find(|(name, _)| !names.insert(name)) {
+            return Err(AtlasError::DuplicateName(name.clone()));
+        }
+
+        // Every image takes its bleed on all sides and padding on its right and bottom,
+        // the padding on the left and top of the atlas is left out of the packing
+        let margin = 2 * self.bleed + self.padding;
+        let cells: Vec<(u32, u32)> = self
+            .images
+            .iter()
+            .map(|(_, image)| (image.width() + margin, image.height() + margin))
+            .collect();
+        let usable = self.max_size.saturating_sub(self.padding);
+        for ((name, _), &(width, height)) in self.images.iter().zip(&cells) {
+            if width > usable || height > usable {
+                return Err(AtlasError::ImageTooLarge(name.clone()));
+            }
+        }
+
+        let area: u64 = cells
+            .iter()
+            .map(|&(width, height)| width as u64 * height as u64)
+            .sum();
+        let size = ((area as f64).sqrt() as u32 + self.padding)
+            .max(1)
+            .next_power_of_two()
+            .min(self.max_size);
+        let mut size = (size, size);
+        let positions = loop {
+            let (width, height) = (
+                size.0.saturating_sub(self.padding),
+                size.1.saturating_sub(self.padding),
+            );
+            if let Some(positions) = pack(&cells, width, height) {
+                break positions;
+            }
+            if size.0 >= self.max_size && size.1 >= self.max_size {
+                return Err(AtlasError::OutOfSpace {
+                    max_size: self.max_size,
+                });
+            }
+            // Grow the shorter side, so the atlas stays close to a square
+            size = if size.0 <= size.1 && size.0 < self.max_size {
+                ((size.0 * 2).min(self.max_size), size.1)
+            } else {
+                (size.0, (size.1 * 2).min(self.max_size))
+            };
+        };
+
+        let (width, height) = size;
+        let mut pixels = vec![0; width as usize * height as usize * 4];
+        let mut regions = HashMap::new();
+        for ((name, image), (x, y)) in self.images.iter().zip(positions) {
+            let region = TextureRegion::new(
+                x + self.padding + self.bleed,
+                y + self.padding + self.bleed,
+                image.width(),
+                image.height(),
+            );
+            copy_with_bleed(image, region, self.bleed, &mut pixels, width);
+            regions.insert(name.clone(), region);
+        }
+
+        let texture = Texture::from_rgba8(width, height, pixels)?.with_filter(self.filter);
+        Ok(TextureAtlas { texture, regions })
+    }
+}
+
+/// Places rectangles of `sizes` into an area of `width` x `height` with a bottom-left skyline,
+/// the bottom being the top of the texture. Returns the top-left corner of each rectangle,
+/// or `None` if they do not all fit.
+fn pack(sizes: &[(u32, u32)], width: u32, height: u32) -> Option<Vec<(u32, u32)>> {
+    // Placing the tallest rectangles first leaves the fewest holes
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| (std::cmp::Reverse(sizes[i].1), std::cmp::Reverse(sizes[i].0)));
+
+    // Segments of the skyline as (x, y, width), sorted by x and covering the whole width
+    let mut skyline = vec![(0, 0, width)];
+    let mut positions = vec![(0, 0); sizes.len()];
+    for i in order {
+        let (rect_width, rect_height) = sizes[i];
+
+        let mut best: Option<(u32, u32)> = None;
+        for start in 0..skyline.len() {
+            let x = skyline[start].0;
+            if x + rect_width > width {
+                break;
+            }
+            // The rectangle rests on the highest segment below it
+            let mut y = 0;
+            let mut covered = 0;
+            for &(_, segment_y, segment_width) in &skyline[start..] {
+                y = y.max(segment_y);
+                covered += segment_width;
+                if covered >= rect_width {
+                    break;
+                }
+            }
+            if y + rect_height <= height
+                && best.is_none_or(|(best_x, best_y)| (y, x) < (best_y, best_x))
+            {
+                best = Some((x, y));
+            }
+        }
+
+        let (x, y) = best?;
+        positions[i] = (x, y);
+        raise_skyline(&mut skyline, x, rect_width, y + rect_height);
+    }
+
+    Some(positions)
+}
+
+/// Replaces the part of the skyline from `x` to `x + width` with one segment at `y`.
+fn raise_skyline(skyline: &mut Vec<(u32, u32, u32)>, x: u32, width: u32, y: u32) {
+    let end = x + width;
+    let mut raised = Vec::with_capacity(skyline.len() + 2);
+    for &(segment_x, segment_y, segment_width) in skyline.iter() {
+        let segment_end = segment_x + segment_width;
+        if segment_end <= x || segment_x >= end {
+            raised.push((segment_x, segment_y, segment_width));
+            continue;
+        }
+        if segment_x < x {
+            raised.push((segment_x, segment_y, x - segment_x));
+        }
+        if segment_x <= x {
+            raised.push((x, y, width));
+        }
+        if segment_end > end {
+            raised.push((end, segment_y, segment_end - end));
+        }
+    }
+
+    // Merge neighbours at the same height
+    raised.dedup_by(|next, previous| {
+        if previous.1 == next.1 {
+            previous.2 += next.2;
+            true
+        } else {
+            false
+        }
+    });
+    *skyline = raised;
+}
+
+/// Copies `image` into `region` of the atlas `pixels`, repeating its edge pixels `bleed` times
+/// around it.
+fn copy_with_bleed(
+    image: &Texture,
+    region: TextureRegion,
+    bleed: u32,
+    pixels: &mut [u8],
+    atlas_width: u32,
+) {
+    let bleed = bleed as i64;
+    let (width, height) = (image.width() as i64, image.height() as i64);
+    for y in -bleed..height + bleed {
+        for x in -bleed..width + bleed {
+            let source = (y.clamp(0, height - 1) * width + x.clamp(0, width - 1)) as usize * 4;
+            let target_x = (region.x as i64 + x) as usize;
+            let target_y = (region.y as i64 + y) as usize;
+            let target = (target_y * atlas_width as usize + target_x) * 4;
+            pixels[target..target + 4].copy_from_slice(&image.pixels()[source..source + 4]);
+        }
+    }
+}
+
+impl fmt::Display for AtlasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateName(name) => {
+                write!(f, "atlas already contains an image named {name:?}")
+            }
+            Self::ImageTooLarge(name) => {
+                write!(f, "image {name:?} is larger than the maximum atlas size")
+            }
+            Self::OutOfSpace { max_size } => {
+                write!(f, "images do not fit into a {max_size}x{max_size} atlas")
+            }
+            Self::Texture(error) => write!(f, "failed to create atlas texture: {error}"),
+        }
+    }
+}
+
+impl Error for AtlasError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Texture(error) => Some(error),
+            Self::DuplicateName(_) | Self::ImageTooLarge(_) | Self::OutOfSpace { .. } => None,
+        }
+    }
+}
+
+impl From<TextureError> for AtlasError {
+    fn from(error: TextureError) -> Self {
+        AtlasError::Texture(error)
+    }
+}
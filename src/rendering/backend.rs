@@ -0,0 +1,27 @@
+use crate::rendering::queue::{RenderBuffers, RenderQueue};
+
+/// The drawing contract shared by all renderers.
+///
+/// A backend receives the vertices and indices produced by `RenderQueue` and turns them into pixels.
+/// The vertex positions are in world units and are transformed by the `Camera2D` of the backend.
+/// The indices form a triangle list. Triangles are front facing when their vertices are
+/// counter-clockwise and back facing triangles are culled.
+pub trait RenderBackend {
+    type Error;
+
+    /// Size of the render target in pixels.
+    fn target_size(&self) -> (u32, u32);
+
+    /// Number of pixels one world unit covers, used to tessellate curved shapes finely enough.
+    fn pixels_per_unit(&self) -> f32 {
+        1.0
+    }
+
+    /// Fills the render target with its background and draws the triangles from `buffers` into it.
+    fn draw(&mut self, buffers: &RenderBuffers) -> Result<(), Self::Error>;
+
+    /// Updates the buffers of `queue` and draws them.
+    fn draw_queue(&mut self, queue: &mut RenderQueue) -> Result<(), Self::Error> {
+        self.draw(queue.buffers(self.pixels_per_unit()))
+    }
+}
@@ -0,0 +1,89 @@
+use std::f32::consts::TAU;
+
+use crate::rendering::{color::Color, index::Index, vertex::Vertex};
+
+/// Number of triangles used to draw `Background::RadialGradient`.
+const RADIAL_SEGMENTS: usize = 64;
+
+/// Upper bound of the number of vertices any background needs.
+pub(super) const MAX_VERTICES: usize = RADIAL_SEGMENTS + 1;
+/// Upper bound of the number of indices any background needs.
+pub(super) const MAX_INDICES: usize = RADIAL_SEGMENTS * 3;
+
+/// What the renderer fills the frame with before drawing the shapes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Background {
+    /// The whole frame is cleared to one color.
+    Solid(Color),
+    /// Blends from `top` at the top edge of the frame to `bottom` at the bottom edge.
+    VerticalGradient { top: Color, bottom: Color },
+    /// Blends from `center` in the middle of the frame to `edge` in its corners.
+    RadialGradient { center: Color, edge: Color },
+    /// The frame is not cleared, shapes are drawn over the previous frame.
+    /// Useful for trail effects.
+    Keep,
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Background::Solid(Color::rgb(0.1, 0.2, 0.3))
+    }
+}
+
+impl Background {
+    /// Color the frame is cleared to, `None` if it should be kept.
+    pub(super) fn clear_color(&self) -> Option<Color> {
+        match self {
+            Background::Solid(color) => Some(*color),
+            Background::VerticalGradient { .. } | Background::RadialGradient { .. } => {
+                Some(Color::TRANSPARENT)
+            }
+            Background::Keep => None,
+        }
+    }
+
+    /// Triangles covering the whole frame, drawn with `BlendMode::Alpha` right after clearing.
+    /// The positions are already in the range -1.0..1.0.
+    pub(super) fn geometry(&self, width: f32, height: f32) -> Option<(Vec<Vertex>, Vec<Index>)> {
+        let vertex = |x: f32, y: f32, color: Color| Vertex {
+            position: [x, y],
+            color: color.to_array(),
+            uv: [0.0; 2],
+        };
+
+        match *self {
+            Background::Solid(_) | Background::Keep => None,
+            Background::VerticalGradient { top, bottom } => {
+                let vertices = vec![
+                    vertex(-1.0, 1.0, top),
+                    vertex(-1.0, -1.0, bottom),
+                    vertex(1.0, -1.0, bottom),
+                    vertex(1.0, 1.0, top),
+                ];
+                Some((vertices, vec![0, 1, 2, 0, 2, 3]))
+            }
+            Background::RadialGradient { center, edge } => {
+                // The ring is a circle in pixels which reaches the corners of the frame
+                let (half_width, half_height) = (width * 0.5, height * 0.5);
+                let radius = half_width.hypot(half_height);
+
+                let mut vertices = vec![vertex(0.0, 0.0, center)];
+                vertices.extend((0..RADIAL_SEGMENTS).map(|i| {
+                    let angle = i as f32 / RADIAL_SEGMENTS as f32 * TAU;
+                    vertex(
+                        radius * angle.cos() / half_width,
+                        radius * angle.sin() / half_height,
+                        edge,
+                    )
+                }));
+
+                let indices = (0..RADIAL_SEGMENTS)
+                    .flat_map(|i| [0, i + 1, (i + 1) % RADIAL_SEGMENTS + 1])
+                    .map(|index| index as Index)
+                    .collect();
+
+                Some((vertices, indices))
+            }
+        }
+    }
+}
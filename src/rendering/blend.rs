@@ -0,0 +1,89 @@
+/// How the color of a shape is combined with the colors already drawn below it.
+///
+/// All modes respect the alpha of the shape, so fully transparent shapes never change the target.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// Regular transparency, the shape is painted over the target with its alpha as opacity.
+    #[default]
+    Alpha,
+    /// Like `Alpha`, but the colors of the shape are expected to already be multiplied by their alpha.
+    PremultipliedAlpha,
+    /// The color of the shape scaled by its alpha is added to the target. Used for glows and lights.
+    Additive,
+    /// The target is multiplied by the color of the shape. Used for shadows and tinting.
+    Multiply,
+}
+
+impl BlendMode {
+    pub const ALL: [BlendMode; 4] = [
+        BlendMode::Alpha,
+        BlendMode::PremultipliedAlpha,
+        BlendMode::Additive,
+        BlendMode::Multiply,
+    ];
+
+    /// Whether the shader has to multiply the color by its alpha before blending.
+    pub(super) fn premultiplies_source(self) -> bool {
+        matches!(self, BlendMode::Multiply)
+    }
+
+    pub(super) fn to_wgpu(self) -> wgpu::BlendState {
+        // Every mode except the alpha ones keeps the alpha of the target
+        let keep_alpha = wgpu::BlendComponent {
+            src_factor: wgpu::BlendFactor::Zero,
+            dst_factor: wgpu::BlendFactor::One,
+            operation: wgpu::BlendOperation::Add,
+        };
+
+        match self {
+            BlendMode::Alpha => wgpu::BlendState::ALPHA_BLENDING,
+            BlendMode::PremultipliedAlpha => wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING,
+            BlendMode::Additive => wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: keep_alpha,
+            },
+            // The source is premultiplied by the shader, so this results in `dst * lerp(1, src, alpha)`
+            BlendMode::Multiply => wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::Dst,
+                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: keep_alpha,
+            },
+        }
+    }
+
+    /// Blends the linear colors `src` and `dst` the same way the GPU does with `BlendMode::to_wgpu`.
+    pub(super) fn blend(self, src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+        let [sr, sg, sb, sa] = src;
+        let [dr, dg, db, da] = dst;
+
+        match self {
+            BlendMode::Alpha => [
+                sr * sa + dr * (1.0 - sa),
+                sg * sa + dg * (1.0 - sa),
+                sb * sa + db * (1.0 - sa),
+                sa + da * (1.0 - sa),
+            ],
+            BlendMode::PremultipliedAlpha => [
+                sr + dr * (1.0 - sa),
+                sg + dg * (1.0 - sa),
+                sb + db * (1.0 - sa),
+                sa + da * (1.0 - sa),
+            ],
+            BlendMode::Additive => [dr + sr * sa, dg + sg * sa, db + sb * sa, da],
+            BlendMode::Multiply => [
+                sr * sa * dr + dr * (1.0 - sa),
+                sg * sa * dg + dg * (1.0 - sa),
+                sb * sa * db + db * (1.0 - sa),
+                da,
+            ],
+        }
+        .map(|value| value.clamp(0.0, 1.0))
+    }
+}
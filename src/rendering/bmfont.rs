@@ -0,0 +1,396 @@
+use std::{collections::HashMap, error::Error, fmt, fs, io, path::Path};
+
+use crate::{
+    math::Vector2,
+    rendering::{
+        atlas::{AtlasBuilder, AtlasError},
+        font::{Glyph, GlyphAtlas, Kerning},
+        texture::{Texture, TextureError, TextureRegion},
+    },
+};
+
+/// Error returned when an AngelCode BMFont file can't be loaded.
+#[derive(Debug)]
+pub enum BmFontError {
+    /// The file can't be read.
+    Io(io::Error),
+    /// A page image can't be loaded.
+    Texture(TextureError),
+    /// The pages do not fit into one atlas.
+    Atlas(AtlasError),
+    /// The line of the text file, counted from 1, is malformed.
+    InvalidLine(usize),
+    /// The binary file ends in the middle of a block.
+    Truncated,
+    /// The binary file has a version other than 3.
+    UnsupportedVersion(u8),
+    /// The file has no `common` block with the line height and baseline.
+    MissingCommon,
+    /// The character with this id is on a page that does not exist.
+    MissingPage(u32),
+    /// The character with this id reaches outside of its page.
+    CharOutOfBounds(u32),
+}
+
+/// The parts of a BMFont file needed for drawing, shared by the text and binary formats.
+#[derive(Default)]
+struct FontFile {
+    /// Distance between the baselines of two lines.
+    line_height: u32,
+    /// Distance from the top of a line to its baseline.
+    base: u32,
+    /// Image file of each page, by page id.
+    pages: Vec<String>,
+    chars: Vec<FontChar>,
+    /// The first and second character ids and the adjustment between them.
+    kernings: Vec<(u32, u32, i32)>,
+    has_common: bool,
+}
+
+struct FontChar {
+    id: u32,
+    region: TextureRegion,
+    offset: Vector2<i32>,
+    advance: i32,
+    page: u32,
+}
+
+impl GlyphAtlas {
+    /// Reads an AngelCode BMFont `.fnt` file in the text or binary format, and its page images
+    /// relative to it.
+    ///
+    /// The atlas is sized by line height: `Text` of size 1.0 has lines one world unit apart.
+    pub fn load_bmfont(path: impl AsRef<Path>) -> Result<Self, BmFontError> {
+        let path = path.as_ref();
+        let file = FontFile::parse(&fs::read(path)?)?;
+        let pages = file
+            .pages
+            .iter()
+            .map(|page| Texture::load(path.with_file_name(page)))
+            .collect::<Result<_, _>>()?;
+
+        file.into_atlas(pages)
+    }
+
+    /// Reads a BMFont file from `bytes`, with its page images already loaded as `pages`
+    /// in the order of their ids.
+    pub fn from_bmfont(bytes: &[u8], pages: Vec<Texture>) -> Result<Self, BmFontError> {
+        FontFile::parse(bytes)?.into_atlas(pages)
+    }
+}
+
+impl FontFile {
+    fn parse(bytes: &[u8]) -> Result<Self, BmFontError> {
+        let file = match bytes.strip_prefix(b"BMF") {
+            Some(binary) => Self::parse_binary(binary)?,
+            None => Self::parse_text(&String::from_utf8_lossy(bytes))?,
+        };
+        if !file.has_common {
+            return Err(BmFontError::MissingCommon);
+        }
+        Ok(file)
+    }
+
+    /// Parses lines like `char id=65 x=0 y=0 width=8 ...`, values of `file` and `face` are quoted.
+    fn parse_text(text: &str) -> Result<Self, BmFontError> {
+        let mut file = FontFile::default();
+        for (index, line) in text.lines().enumerate() {
+            let invalid = || BmFontError::InvalidLine(index + 1);
+            let Some((tag, attributes)) = parse_line(line) else {
+                continue;
+            };
+            let attributes = attributes.ok_or_else(invalid)?;
+            let number = |key: &str| -> Result<i64, BmFontError> {
+                attributes
+                    .get(key)
+                    .and_then(|value| value.parse().ok())
+                    .ok_or_else(invalid)
+            };
+            let unsigned = |key: &str| -> Result<u32, BmFontError> {
+                u32::try_from(number(key)?).map_err(|_| invalid())
+            };
+            let signed = |key: &str| -> Result<i32, BmFontError> {
+                i32::try_from(number(key)?).map_err(|_| invalid())
+            };
+
+            match tag {
+                "common" => {
+                    file.line_height = unsigned("lineHeight")?;
+                    file.base = unsigned("base")?;
+                    file.has_common = true;
+                }
+                "page" => {
+                    let id = unsigned("id")? as usize;
+                    let name = attributes.get("file").ok_or_else(invalid)?;
+                    if file.pages.len() <= id {
+                        file.pages.resize(id + 1, String::new());
+                    }
+                    file.pages[id] = name.clone();
+                }
+                "char" => file.chars.push(FontChar {
+                    // Some tools write the fallback glyph with the id -1
+                    id: match number("id")? {
+                        -1 => u32::MAX,
+                        id => u32::try_from(id).map_err(|_| invalid())?,
+                    },
+                    region: TextureRegion::new(
+                        unsigned("x")?,
+                        unsigned("y")?,
+                        unsigned("width")?,
+                        unsigned("height")?,
+                    ),
+                    offset: Vector2::new(signed("xoffset")?, signed("yoffset")?),
+                    advance: signed("xadvance")?,
+                    page: unsigned("page")?,
+                }),
+                "kerning" => {
+                    file.kernings
+                        .push((unsigned("first")?, unsigned("second")?, signed("amount")?))
+                }
+                // `info`, `chars` and `kernings` hold nothing needed for drawing
+                _ => {}
+            }
+        }
+        Ok(file)
+    }
+
+    /// Parses the blocks following the `BMF` signature, all numbers are little-endian.
+    fn parse_binary(bytes: &[u8]) -> Result<Self, BmFontError> {
+        let (&version, mut rest) = bytes.split_first().ok_or(BmFontError::Truncated)?;
+        if version != 3 {
+            return Err(BmFontError::UnsupportedVersion(version));
+        }
+
+        let mut file = FontFile::default();
+        while let Some((&kind, after_kind)) = rest.split_first() {
+            let (size, after_size) = after_kind
+                .split_first_chunk::<4>()
+                .ok_or(BmFontError::Truncated)?;
+            let size = u32::from_le_bytes(*size) as usize;
+            if after_size.len() < size {
+                return Err(BmFontError::Truncated);
+            }
+            let (block, after_block) = after_size.split_at(size);
+            rest = after_block;
+
+            let u16_at = |offset: usize| u16::from_le_bytes([block[offset], block[offset + 1]]);
+            let i16_at = |offset: usize| i16::from_le_bytes([block[offset], block[offset + 1]]);
+            let u32_at = |offset: usize| {
+                u32::from_le_bytes(block[offset..offset + 4].try_into().expect("4 bytes"))
+            };
+            match kind {
+                2 => {
+                    if block.len() < 4 {
+                        return Err(BmFontError::Truncated);
+                    }
+                    file.line_height = u16_at(0) as u32;
+                    file.base = u16_at(2) as u32;
+                    file.has_common = true;
+                }
+                3 => {
+                    file.pages = block
+                        .split(|&byte| byte == 0)
+                        .filter(|name| !name.is_empty())
+                        .map(|name| String::from_utf8_lossy(name).into_owned())
+                        .collect();
+                }
+                4 => {
+                    for start in (0..block.len() / 20).map(|i| i * 20) {
+                        file.chars.push(FontChar {
+                            id: u32_at(start),
+                            region: TextureRegion::new(
+                                u16_at(start + 4) as u32,
+                                u16_at(start + 6) as u32,
+                                u16_at(start + 8) as u32,
+                                u16_at(start + 10) as u32,
+                            ),
+                            offset: Vector2::new(
+                                i16_at(start + 12) as i32,
+                                i16_at(start + 14) as i32,
+                            ),
+                            advance: i16_at(start + 16) as i32,
+                            page: block[start + 18] as u32,
+                        });
+                    }
+                }
+                5 => {
+                    for start in (0..block.len() / 10).map(|i| i * 10) {
+                        file.kernings.push((
+                            u32_at(start),
+                            u32_at(start + 4),
+                            i16_at(start + 8) as i32,
+                        ));
+                    }
+                }
+                // The info block holds nothing needed for drawing
+                _ => {}
+            }
+        }
+        Ok(file)
+    }
+
+    /// Combines the pages into one texture and converts the characters into glyphs.
+    fn into_atlas(self, pages: Vec<Texture>) -> Result<GlyphAtlas, BmFontError> {
+        for font_char in &self.chars {
+            let page = pages
+                .get(font_char.page as usize)
+                .ok_or(BmFontError::MissingPage(font_char.id))?;
+            let region = font_char.region;
+            if region.x as u64 + region.width as u64 > page.width() as u64
+                || region.y as u64 + region.height as u64 > page.height() as u64
+            {
+                return Err(BmFontError::CharOutOfBounds(font_char.id));
+            }
+        }
+
+        // A single page is used as it is, so its texture can be shared
+        let (texture, page_origins) = match <[Texture; 1]>::try_from(pages) {
+            Ok([page]) => (page, vec![(0, 0)]),
+            Err(pages) => {
+                let filter = pages.first().map(Texture::filter).unwrap_or_default();
+                let mut builder = AtlasBuilder::new().with_bleed(0).with_filter(filter);
+                for (id, page) in pages.iter().enumerate() {
+                    builder.add(id.to_string(), page.clone());
+                }
+                let atlas = builder.build()?;
+                let origins = (0..pages.len())
+                    .map(|id| {
+                        let region = atlas.region(&id.to_string()).expect("page was added");
+                        (region.x, region.y)
+                    })
+                    .collect();
+                (atlas.texture().clone(), origins)
+            }
+        };
+
+        let glyphs = self
+            .chars
+            .iter()
+            .filter_map(|font_char| {
+                let character = char::from_u32(font_char.id)?;
+                let (x, y) = page_origins[font_char.page as usize];
+                let region = font_char.region;
+                let glyph = Glyph {
+                    region: (region.width > 0 && region.height > 0).then(|| {
+                        TextureRegion::new(x + region.x, y + region.y, region.width, region.height)
+                    }),
+                    // The offset is from the top of the line, not the baseline
+                    offset: Vector2::new(
+                        font_char.offset.x as f32,
+                        font_char.offset.y as f32 - self.base as f32,
+                    ),
+                    advance: font_char.advance as f32,
+                };
+                Some((character, glyph))
+            })
+            .collect();
+        let kerning = self
+            .kernings
+            .iter()
+            .filter_map(|&(first, second, amount)| {
+                Some((
+                    (char::from_u32(first)?, char::from_u32(second)?),
+                    amount as f32,
+                ))
+            })
+            .collect();
+
+        let line_height = self.line_height as f32;
+        let ascent = self.base as f32;
+        Ok(GlyphAtlas::new(
+            texture,
+            line_height,
+            ascent,
+            ascent - line_height,
+            0.0,
+            glyphs,
+            Kerning::Table(kerning),
+        ))
+    }
+}
+
+/// Splits a line of the text format into its tag and attributes, `None` for empty lines.
+/// The attributes are `None` if they are malformed.
+fn parse_line(line: &str) -> Option<(&str, Option<HashMap<&str, String>>)> {
+    let line = line.trim();
+    let (tag, mut rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    if tag.is_empty() {
+        return None;
+    }
+
+    let mut attributes = HashMap::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Some((tag, Some(attributes)));
+        }
+        let Some((key, after_key)) = rest.split_once('=') else {
+            return Some((tag, None));
+        };
+        let (value, after_value) = match after_key.strip_prefix('"') {
+            Some(quoted) => match quoted.split_once('"') {
+                Some(split) => split,
+                None => return Some((tag, None)),
+            },
+            None => after_key
+                .split_once(char::is_whitespace)
+                .unwrap_or((after_key, "")),
+        };
+        attributes.insert(key, value.to_owned());
+        rest = after_value;
+    }
+}
+
+impl fmt::Display for BmFontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read bitmap font: {error}"),
+            Self::Texture(error) => write!(f, "failed to load bitmap font page: {error}"),
+            Self::Atlas(error) => write!(f, "failed to combine bitmap font pages: {error}"),
+            Self::InvalidLine(line) => write!(f, "invalid bitmap font line {line}"),
+            Self::Truncated => write!(f, "bitmap font ends in the middle of a block"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported binary bitmap font version {version}")
+            }
+            Self::MissingCommon => write!(f, "bitmap font has no common block"),
+            Self::MissingPage(id) => write!(f, "character {id} is on a missing page"),
+            Self::CharOutOfBounds(id) => {
+                write!(f, "character {id} reaches outside of its page")
+            }
+        }
+    }
+}
+
+impl Error for BmFontError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Texture(error) => Some(error),
+            Self::Atlas(error) => Some(error),
+            Self::InvalidLine(_)
+            | Self::Truncated
+            | Self::UnsupportedVersion(_)
+            | Self::MissingCommon
+            | Self::MissingPage(_)
+            | Self::CharOutOfBounds(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for BmFontError {
+    fn from(error: io::Error) -> Self {
+        BmFontError::Io(error)
+    }
+}
+
+impl From<TextureError> for BmFontError {
+    fn from(error: TextureError) -> Self {
+        BmFontError::Texture(error)
+    }
+}
+
+impl From<AtlasError> for BmFontError {
+    fn from(error: AtlasError) -> Self {
+        BmFontError::Atlas(error)
+    }
+}
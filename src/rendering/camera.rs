@@ -0,0 +1,107 @@
+use crate::math::{Affine2, Vector2};
+
+/// A 2D camera that decides which part of the world ends up on the screen.
+///
+/// Shapes are positioned in world units. With the default camera one world unit is one pixel
+/// and the world origin is in the center of the screen, with the y axis pointing up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera2D {
+    /// World position shown in the center of the viewport.
+    pub position: Vector2<f32>,
+    /// Number of pixels per world unit. Values above 1.0 zoom in.
+    /// Zooms closer to zero than [`Camera2D::MIN_ZOOM`], or NaN, are used as `MIN_ZOOM`
+    /// so that converting between the screen and the world stays finite.
+    pub zoom: f32,
+    /// Counter-clockwise rotation of the camera in radians.
+    /// Rotating the camera rotates the world on the screen the other way.
+    pub rotation: f32,
+    /// Size of the area the camera renders to in pixels.
+    /// Renderers keep it in sync with the size of their render target.
+    pub viewport: Vector2<f32>,
+}
+
+impl Default for Camera2D {
+    fn default() -> Self {
+        Self {
+            position: Vector2::default(),
+            zoom: 1.0,
+            rotation: 0.0,
+            viewport: Vector2::new(1.0, 1.0),
+        }
+    }
+}
+
+impl Camera2D {
+    /// Smallest number of pixels per world unit the camera shows the world with.
+    pub const MIN_ZOOM: f32 = 1e-6;
+
+    /// Creates a camera at the world origin without any zoom or rotation.
+    pub fn new(viewport_width: f32, viewport_height: f32) -> Self {
+        Self {
+            viewport: Vector2::new(viewport_width, viewport_height),
+            ..Default::default()
+        }
+    }
+
+    /// The zoom that is used, kept at least `MIN_ZOOM` away from zero.
+    fn used_zoom(&self) -> f32 {
+        if self.zoom.is_nan() {
+            Self::MIN_ZOOM
+        } else {
+            self.zoom.signum() * self.zoom.abs().max(Self::MIN_ZOOM)
+        }
+    }
+
+    /// Number of pixels one world unit covers on screen.
+    pub fn pixels_per_unit(&self) -> f32 {
+        self.used_zoom().abs()
+    }
+
+    /// Scales from world units to clip space (-1.0..1.0).
+    fn clip_scale(&self) -> Vector2<f32> {
+        let zoom = self.used_zoom();
+        Vector2::new(2.0 * zoom / self.viewport.x, 2.0 * zoom / self.viewport.y)
+    }
+
+    /// Transform from world positions into clip space.
+    pub fn clip_transform(&self) -> Affine2<f32> {
+        Affine2::from_scale(self.clip_scale())
+            * Affine2::from_angle(-self.rotation)
+            * Affine2::from_translation(-self.position)
+    }
+
+    /// Column-major matrix transforming world positions into clip space, as used by the shader.
+    pub fn view_projection(&self) -> [[f32; 4]; 4] {
+        self.clip_transform().into()
+    }
+
+    /// Transforms a world position into clip space (-1.0..1.0).
+    pub(super) fn world_to_clip(&self, world: Vector2<f32>) -> Vector2<f32> {
+        self.clip_transform().transform_point(world)
+    }
+
+    /// Converts a world position into a position in pixels from the top-left corner of the viewport,
+    /// the same coordinates the window reports the cursor in.
+    pub fn world_to_screen(&self, world: Vector2<f32>) -> Vector2<f32> {
+        let clip = self.world_to_clip(world);
+
+        Vector2::new(
+            (clip.x + 1.0) * 0.5 * self.viewport.x,
+            (1.0 - clip.y) * 0.5 * self.viewport.y,
+        )
+    }
+
+    /// Converts a position in pixels from the top-left corner of the viewport into a world position.
+    pub fn screen_to_world(&self, screen: Vector2<f32>) -> Vector2<f32> {
+        let scale = self.clip_scale();
+        let clip = Vector2::new(
+            screen.x / self.viewport.x * 2.0 - 1.0,
+            1.0 - screen.y / self.viewport.y * 2.0,
+        );
+
+        let clip_to_world = Affine2::from_translation(self.position)
+            * Affine2::from_angle(self.rotation)
+            * Affine2::from_scale(Vector2::new(1.0 / scale.x, 1.0 / scale.y));
+        clip_to_world.transform_point(clip)
+    }
+}
@@ -0,0 +1,270 @@
+use std::{error::Error, fmt, str::FromStr};
+
+/// An RGBA color.
+///
+/// The components are stored in **linear** space with values in range 0.0..1.0,
+/// which is what the renderer works with. Colors that come from image editors, CSS or hex codes
+/// are sRGB encoded and should be created with `Color::from_srgb`, `Color::from_rgba8` or `Color::from_hex`.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const TRANSPARENT: Self = Self::rgba(0.0, 0.0, 0.0, 0.0);
+    pub const BLACK: Self = Self::rgb(0.0, 0.0, 0.0);
+    pub const WHITE: Self = Self::rgb(1.0, 1.0, 1.0);
+    /// sRGB `#808080`.
+    pub const GRAY: Self = Self::rgb(0.215_860_5, 0.215_860_5, 0.215_860_5);
+    pub const RED: Self = Self::rgb(1.0, 0.0, 0.0);
+    pub const GREEN: Self = Self::rgb(0.0, 1.0, 0.0);
+    pub const BLUE: Self = Self::rgb(0.0, 0.0, 1.0);
+    pub const YELLOW: Self = Self::rgb(1.0, 1.0, 0.0);
+    pub const CYAN: Self = Self::rgb(0.0, 1.0, 1.0);
+    pub const MAGENTA: Self = Self::rgb(1.0, 0.0, 1.0);
+    /// sRGB `#ffa500`.
+    pub const ORANGE: Self = Self::rgb(1.0, 0.376_262_3, 0.0);
+    /// sRGB `#800080`.
+    pub const PURPLE: Self = Self::rgb(0.215_860_5, 0.0, 0.215_860_5);
+
+    /// Creates an opaque color from linear components.
+    pub const fn rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::rgba(r, g, b, 1.0)
+    }
+
+    /// Creates a color from linear components.
+    pub const fn rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Creates an opaque color from sRGB encoded components in range 0.0..1.0.
+    pub fn from_srgb(r: f32, g: f32, b: f32) -> Self {
+        Self::from_srgba(r, g, b, 1.0)
+    }
+
+    /// Creates a color from sRGB encoded components in range 0.0..1.0.
+    /// The alpha is always linear.
+    pub fn from_srgba(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self::rgba(srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a)
+    }
+
+    /// Creates a color from 8-bit sRGB encoded components.
+    pub fn from_rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        let to_f32 = |value: u8| value as f32 / 255.0;
+        Self::from_srgba(to_f32(r), to_f32(g), to_f32(b), to_f32(a))
+    }
+
+    /// Parses a hex color code in the `rgb`, `rgba`, `rrggbb` or `rrggbbaa` form.
+    /// The leading `#` is optional.
+    /// ```
+    /// # use stellar_engine::rendering::Color;
+    /// assert_eq!(Color::from_hex("#ff0000").unwrap(), Color::RED);
+    /// assert_eq!(Color::from_hex("0000ff80").unwrap().to_rgba8(), [0, 0, 255, 128]);
+    /// ```
+    pub fn from_hex(hex: &str) -> Result<Self, ParseColorError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ParseColorError::InvalidDigit);
+        }
+
+        let parse = |digits: &str| {
+            u8::from_str_radix(digits, 16).map_err(|_| ParseColorError::InvalidDigit)
+        };
+        // Short forms repeat each digit, `f` -> `ff`
+        let parse_short = |index: usize| parse(&digits[index..=index]).map(|value| value * 17);
+        let parse_long = |index: usize| parse(&digits[index * 2..index * 2 + 2]);
+
+        let [r, g, b, a] = match digits.len() {
+            3 => [parse_short(0)?, parse_short(1)?, parse_short(2)?, 255],
+            4 => [
+                parse_short(0)?,
+                parse_short(1)?,
+                parse_short(2)?,
+                parse_short(3)?,
+            ],
+            6 => [parse_long(0)?, parse_long(1)?, parse_long(2)?, 255],
+            8 => [
+                parse_long(0)?,
+                parse_long(1)?,
+                parse_long(2)?,
+                parse_long(3)?,
+            ],
+            length => return Err(ParseColorError::InvalidLength(length)),
+        };
+
+        Ok(Self::from_rgba8(r, g, b, a))
+    }
+
+    /// Creates an opaque color from hue (in degrees), saturation and value.
+    /// Saturation and value are in range 0.0..1.0 and describe the sRGB encoded color.
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        let chroma = value * saturation;
+        let [r, g, b] = hue_to_rgb(hue, chroma, value - chroma);
+        Self::from_srgb(r, g, b)
+    }
+
+    /// Creates an opaque color from hue (in degrees), saturation and lightness.
+    /// Saturation and lightness are in range 0.0..1.0 and describe the sRGB encoded color.
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let [r, g, b] = hue_to_rgb(hue, chroma, lightness - chroma * 0.5);
+        Self::from_srgb(r, g, b)
+    }
+
+    /// Returns the same color with its alpha replaced.
+    pub const fn with_alpha(self, a: f32) -> Self {
+        Self { a, ..self }
+    }
+
+    /// Returns the sRGB encoded components `[r, g, b, a]`. The alpha stays linear.
+    pub fn to_srgba(self) -> [f32; 4] {
+        [
+            linear_to_srgb(self.r),
+            linear_to_srgb(self.g),
+            linear_to_srgb(self.b),
+            self.a,
+        ]
+    }
+
+    /// Returns the 8-bit sRGB encoded components `[r, g, b, a]`.
+    pub fn to_rgba8(self) -> [u8; 4] {
+        self.to_srgba()
+            .map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    /// Formats the color as `#rrggbb`, or `#rrggbbaa` if it is not opaque.
+    pub fn to_hex(self) -> String {
+        let [r, g, b, a] = self.to_rgba8();
+        if a == 255 {
+            format!("#{r:02x}{g:02x}{b:02x}")
+        } else {
+            format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+        }
+    }
+
+    /// Returns hue (in degrees), saturation and value of the sRGB encoded color.
+    pub fn to_hsv(self) -> [f32; 3] {
+        let [r, g, b, _] = self.to_srgba();
+        let max = r.max(g).max(b);
+        let chroma = max - r.min(g).min(b);
+        let saturation = if max == 0.0 { 0.0 } else { chroma / max };
+
+        [rgb_to_hue(r, g, b, max, chroma), saturation, max]
+    }
+
+    /// Returns hue (in degrees), saturation and lightness of the sRGB encoded color.
+    pub fn to_hsl(self) -> [f32; 3] {
+        let [r, g, b, _] = self.to_srgba();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+        let lightness = (max + min) * 0.5;
+        let saturation = if lightness == 0.0 || lightness == 1.0 {
+            0.0
+        } else {
+            chroma / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+
+        [rgb_to_hue(r, g, b, max, chroma), saturation, lightness]
+    }
+
+    /// Linear components in the order `[r, g, b, a]`.
+    pub const fn to_array(self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+}
+
+impl From<[f32; 3]> for Color {
+    fn from([r, g, b]: [f32; 3]) -> Self {
+        Self::rgb(r, g, b)
+    }
+}
+
+impl From<[f32; 4]> for Color {
+    fn from([r, g, b, a]: [f32; 4]) -> Self {
+        Self::rgba(r, g, b, a)
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
+}
+
+/// Error returned when parsing a hex color code fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// The code does not have 3, 4, 6 or 8 digits.
+    InvalidLength(usize),
+    /// The code contains a character that is not a hex digit.
+    InvalidDigit,
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(length) => {
+                write!(f, "hex color must have 3, 4, 6 or 8 digits, got {length}")
+            }
+            Self::InvalidDigit => write!(f, "hex color contains an invalid digit"),
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+pub(crate) fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.040_45 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub(crate) fn linear_to_srgb(value: f32) -> f32 {
+    let value = value.clamp(0.0, 1.0);
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Shared part of the HSV and HSL to RGB conversions.
+fn hue_to_rgb(hue: f32, chroma: f32, min: f32) -> [f32; 3] {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+    let [r, g, b] = match sector as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    };
+
+    [r + min, g + min, b + min]
+}
+
+fn rgb_to_hue(r: f32, g: f32, b: f32, max: f32, chroma: f32) -> f32 {
+    if chroma == 0.0 {
+        return 0.0;
+    }
+
+    let hue = if max == r {
+        ((g - b) / chroma).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / chroma + 2.0
+    } else {
+        (r - g) / chroma + 4.0
+    };
+
+    hue * 60.0
+}
@@ -0,0 +1,291 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    error::Error,
+    fmt, fs, io,
+    path::Path,
+    sync::Arc,
+};
+
+use ab_glyph::{Font as _, FontArc, InvalidFont, PxScale, ScaleFont};
+
+use crate::{
+    math::Vector2,
+    rendering::{
+        atlas::{AtlasBuilder, AtlasError},
+        texture::{Texture, TextureFilter, TextureRegion},
+    },
+};
+
+/// A TrueType or OpenType font.
+///
+/// Text is not drawn from the outlines directly. The characters it uses are rasterized once
+/// with `glyph_atlas` and `Text` shapes are drawn from the resulting texture.
+#[derive(Clone)]
+pub struct Font {
+    font: FontArc,
+}
+
+/// Glyphs rasterized at one size and packed into a texture, which `Text` shapes are drawn with.
+///
+/// All measures are in pixels of the texture, with y pointing down from the baseline.
+/// Cloning an atlas is cheap, the clones share the glyphs and the texture.
+#[derive(Clone)]
+pub struct GlyphAtlas {
+    data: Arc<GlyphAtlasData>,
+}
+
+#[derive(Clone)]
+struct GlyphAtlasData {
+    texture: Texture,
+    size: f32,
+    ascent: f32,
+    descent: f32,
+    line_gap: f32,
+    glyphs: HashMap<char, Glyph>,
+    kerning: Kerning,
+}
+
+/// Where the kerning of a `GlyphAtlas` comes from.
+#[derive(Clone)]
+pub(super) enum Kerning {
+    /// Pairs listed up front, like in a BMFont file.
+    Table(HashMap<(char, char), f32>),
+    /// Looked up in the font when text is laid out, as a table of every pair of glyphs
+    /// grows with the square of their count.
+    Font { font: FontArc, scale: PxScale },
+}
+
+/// One character of a `GlyphAtlas`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glyph {
+    /// Where the glyph is in the texture, `None` for glyphs without pixels like spaces.
+    pub region: Option<TextureRegion>,
+    /// Position of the top-left corner of `region` relative to the pen on the baseline.
+    pub offset: Vector2<f32>,
+    /// How far the pen moves to the next glyph.
+    pub advance: f32,
+}
+
+/// Error returned when a font can't be loaded or rasterized.
+#[derive(Debug)]
+pub enum FontError {
+    /// The file can't be read.
+    Io(io::Error),
+    /// The data is not a valid TrueType or OpenType font.
+    InvalidFont,
+    /// The glyphs do not fit into an atlas.
+    Atlas(AtlasError),
+}
+
+impl Font {
+    /// Reads a `.ttf` or `.otf` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, FontError> {
+        Self::from_bytes(fs::read(path)?)
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, FontError> {
+        Ok(Self {
+            font: FontArc::try_from_vec(bytes)?,
+        })
+    }
+
+    /// Whether the font has a glyph for `character`.
+    pub fn has_glyph(&self, character: char) -> bool {
+        self.font.glyph_id(character).0 != 0
+    }
+
+    /// Rasterizes `characters` into an atlas. `size` is the height in pixels from the lowest
+    /// descender to the highest ascender. Characters the font has no glyph for are left out.
+    ///
+    /// `' '..='~'` covers printable ASCII.
+    pub fn glyph_atlas(
+        &self,
+        size: f32,
+        characters: impl IntoIterator<Item = char>,
+    ) -> Result<GlyphAtlas, FontError> {
+        let scaled = self.font.as_scaled(PxScale::from(size));
+        let characters: BTreeSet<char> = characters
+            .into_iter()
+            .filter(|&character| self.has_glyph(character))
+            .collect();
+
+        // No bleed, the transparent padding is what linear filtering should blend in
+        let mut builder = AtlasBuilder::new().with_padding(1).with_bleed(0);
+        let mut glyphs = HashMap::with_capacity(characters.len());
+        for &character in &characters {
+            let id = self.font.glyph_id(character);
+            let mut glyph = Glyph {
+                region: None,
+                offset: Vector2::new(0.0, 0.0),
+                advance: scaled.h_advance(id),
+            };
+            let outline = self.font.outline_glyph(id.with_scale(scaled.scale()));
+            if let Some(outline) = outline {
+                let bounds = outline.px_bounds();
+                let (width, height) = (bounds.width() as u32, bounds.height() as u32);
+                if width > 0 && height > 0 {
+                    let mut pixels = [255, 255, 255, 0].repeat((width * height) as usize);
+                    outline.draw(|x, y, coverage| {
+                        let alpha = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+                        pixels[((y * width + x) * 4 + 3) as usize] = alpha;
+                    });
+                    let image =
+                        Texture::from_rgba8(width, height, pixels).map_err(AtlasError::from)?;
+                    builder.add(character.to_string(), image);
+                    glyph.offset = Vector2::new(bounds.min.x, bounds.min.y);
+                }
+            }
+            glyphs.insert(character, glyph);
+        }
+
+        let atlas = builder.build()?;
+        for (character, glyph) in &mut glyphs {
+            glyph.region = atlas.region(&character.to_string());
+        }
+
+        Ok(GlyphAtlas::new(
+            atlas.texture().clone(),
+            size,
+            scaled.ascent(),
+            scaled.descent(),
+            scaled.line_gap(),
+            glyphs,
+            Kerning::Font {
+                font: self.font.clone(),
+                scale: scaled.scale(),
+            },
+        ))
+    }
+}
+
+impl GlyphAtlas {
+    /// Creates an atlas from glyphs measured in pixels of `texture`.
+    pub(super) fn new(
+        texture: Texture,
+        size: f32,
+        ascent: f32,
+        descent: f32,
+        line_gap: f32,
+        glyphs: HashMap<char, Glyph>,
+        kerning: Kerning,
+    ) -> Self {
+        GlyphAtlas {
+            data: Arc::new(GlyphAtlasData {
+                texture,
+                size,
+                ascent,
+                descent,
+                line_gap,
+                glyphs,
+                kerning,
+            }),
+        }
+    }
+
+    /// Sets the filter of the texture, `Nearest` keeps pixel fonts sharp.
+    pub fn with_filter(self, filter: TextureFilter) -> Self {
+        let mut data = Arc::unwrap_or_clone(self.data);
+        data.texture = data.texture.with_filter(filter);
+        GlyphAtlas {
+            data: Arc::new(data),
+        }
+    }
+
+    pub fn texture(&self) -> &Texture {
+        &self.data.texture
+    }
+
+    /// Height in pixels from the lowest descender to the highest ascender.
+    pub fn size(&self) -> f32 {
+        self.data.size
+    }
+
+    /// Distance from the baseline up to the highest ascender.
+    pub fn ascent(&self) -> f32 {
+        self.data.ascent
+    }
+
+    /// Distance from the baseline down to the lowest descender, usually negative.
+    pub fn descent(&self) -> f32 {
+        self.data.descent
+    }
+
+    /// Distance between the baselines of two lines.
+    pub fn line_height(&self) -> f32 {
+        self.data.ascent - self.data.descent + self.data.line_gap
+    }
+
+    pub fn glyph(&self, character: char) -> Option<&Glyph> {
+        self.data.glyphs.get(&character)
+    }
+
+    /// Adjustment of the advance between `first` and the `second` following it.
+    pub fn kerning(&self, first: char, second: char) -> f32 {
+        match &self.data.kerning {
+            Kerning::Table(pairs) => pairs.get(&(first, second)).copied().unwrap_or(0.0),
+            Kerning::Font { font, scale } => {
+                if self.glyph(first).is_none() || self.glyph(second).is_none() {
+                    return 0.0;
+                }
+                font.as_scaled(*scale)
+                    .kern(font.glyph_id(first), font.glyph_id(second))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Font {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Font")
+            .field("glyphs", &self.font.glyph_count())
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Debug for GlyphAtlas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlyphAtlas")
+            .field("texture", &self.data.texture)
+            .field("size", &self.data.size)
+            .field("glyphs", &self.data.glyphs.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read font: {error}"),
+            Self::InvalidFont => write!(f, "invalid TrueType or OpenType font"),
+            Self::Atlas(error) => write!(f, "failed to pack glyphs: {error}"),
+        }
+    }
+}
+
+impl Error for FontError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Atlas(error) => Some(error),
+            Self::InvalidFont => None,
+        }
+    }
+}
+
+impl From<io::Error> for FontError {
+    fn from(error: io::Error) -> Self {
+        FontError::Io(error)
+    }
+}
+
+impl From<InvalidFont> for FontError {
+    fn from(_: InvalidFont) -> Self {
+        FontError::InvalidFont
+    }
+}
+
+impl From<AtlasError> for FontError {
+    fn from(error: AtlasError) -> Self {
+        FontError::Atlas(error)
+    }
+}
@@ -1 +1,6 @@
-pub(super) type Index = u16;
+/// Type of the indices into the vertex buffer.
+/// 32 bits wide, so a single draw call can reference any number of vertices a scene needs.
+pub type Index = u32;
+
+/// `wgpu` format matching `Index`.
+pub(super) const INDEX_FORMAT: wgpu::IndexFormat = wgpu::IndexFormat::Uint32;
@@ -1,7 +1,33 @@
+mod atlas;
+mod backend;
+mod background;
+mod blend;
+mod bmfont;
+mod camera;
+mod color;
+mod font;
 mod index;
 mod queue;
 pub mod shapes;
+mod software;
+mod sprite_sheet;
 mod state;
+pub mod svg;
+mod texture;
 mod vertex;
 
+pub use atlas::{AtlasBuilder, AtlasError, TextureAtlas};
+pub use backend::RenderBackend;
+pub use background::Background;
+pub use blend::BlendMode;
+pub use bmfont::BmFontError;
+pub use camera::Camera2D;
+pub use color::{Color, ParseColorError};
+pub use font::{Font, FontError, Glyph, GlyphAtlas};
+pub use index::Index;
+pub use queue::{DrawBatch, DrawParams, RenderBuffers, RenderQueue, ShapeHandle};
+pub use software::SoftwareRenderer;
+pub use sprite_sheet::{FrameTag, SheetFrame, SpriteSheet, SpriteSheetError, TagDirection};
 pub use state::RendererState;
+pub use texture::{Texture, TextureError, TextureFilter, TextureRegion};
+pub use vertex::Vertex;
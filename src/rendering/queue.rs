@@ -1,48 +1,502 @@
-use crate::rendering::{index::Index, shapes::Shape, vertex::Vertex};
+use std::{
+    ops::Range,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::rendering::{
+    blend::BlendMode, index::Index, shapes::Shape, texture::Texture, vertex::Vertex,
+};
 
 /// To render a shape it must be added to the `RenderQueue`.
 /// The shapes added will be drawn to the screen on the next call of `RendererState::render`.
+///
+/// Shapes stay in the queue until they are removed, so they can be changed in place
+/// through the `ShapeHandle` returned when adding them.
+///
+/// Shapes are drawn layer by layer, starting with the lowest one.
+/// Shapes on the same layer are drawn in the order they were added.
+///
+/// The queue keeps the vertices and indices of the last frame and only tessellates
+/// the shapes that changed since then.
 #[derive(Debug, Default)]
 pub struct RenderQueue {
-    shapes: Vec<Shape>,
+    slots: Vec<Slot>,
+    /// Indices of the slots without a shape, reused by the next `add`.
+    free_slots: Vec<u32>,
+    /// Incremented on every `add`, keeps the insertion order when slots are reused.
+    next_order: u64,
+    buffers: RenderBuffers,
+    /// Set when shapes were added, removed, hidden or reordered, so the buffers must be rebuilt.
+    layout_changed: bool,
+    /// Set when any shape is dirty.
+    shapes_changed: bool,
+    /// Scale the curved shapes were last tessellated with.
+    pixels_per_unit: f32,
+}
+
+/// Refers to a shape in a `RenderQueue`.
+///
+/// A handle stays valid until its shape is removed. After that it never refers to
+/// another shape, even if the queue reuses the place of the removed one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShapeHandle {
+    index: u32,
+    generation: u32,
+}
+
+/// Controls how a shape in the `RenderQueue` is drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DrawParams {
+    /// How the shape is blended with the shapes drawn before it.
+    pub blend_mode: BlendMode,
+    /// Shapes on lower layers are drawn first, so they end up below shapes on higher layers.
+    pub layer: i32,
+}
+
+impl DrawParams {
+    pub fn with_blend_mode(self, blend_mode: BlendMode) -> Self {
+        Self { blend_mode, ..self }
+    }
+
+    pub fn with_layer(self, layer: i32) -> Self {
+        Self { layer, ..self }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Slot {
+    /// Incremented every time the shape of the slot is removed.
+    generation: u32,
+    shape: Option<QueuedShape>,
+}
+
+#[derive(Debug)]
+struct QueuedShape {
+    shape: Shape,
+    params: DrawParams,
+    visible: bool,
+    order: u64,
+    /// The shape changed since it was last tessellated.
+    dirty: bool,
+    /// Where the tessellated shape is in `RenderBuffers`, if it was drawn in the last build.
+    vertices: Option<Range<u32>>,
+    indices: Option<Range<u32>>,
+    /// Id of the texture the shape was drawn with in the last build.
+    texture: Option<u64>,
 }
 
 /// Returned by `RenderQueue`.
-/// Contains vectors of the new vertices and indices.
-pub(super) struct RenderBuffers {
+/// Contains vectors of the vertices and indices of all visible shapes.
+///
+/// Every change of the contents gets a new revision, unique among all queues.
+/// A backend which uploaded `previous_revision` only has to upload the dirty ranges,
+/// everything else is unchanged.
+#[derive(Debug)]
+pub struct RenderBuffers {
     pub(super) vertices: Vec<Vertex>,
     pub(super) indices: Vec<Index>,
+    pub(super) batches: Vec<DrawBatch>,
+    revision: u64,
+    previous_revision: Option<u64>,
+    dirty_vertices: Vec<Range<u32>>,
+    dirty_indices: Vec<Range<u32>>,
+}
+
+/// A run of consecutive triangles that share the same `BlendMode` and texture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrawBatch {
+    pub blend_mode: BlendMode,
+    /// Texture the triangles are drawn with, `None` if they only use their vertex colors.
+    pub texture: Option<Texture>,
+    /// Range of `RenderBuffers::indices` drawn by this batch.
+    pub indices: Range<u32>,
+}
+
+/// Source of the revisions of all `RenderBuffers`.
+static NEXT_REVISION: AtomicU64 = AtomicU64::new(0);
+
+fn next_revision() -> u64 {
+    NEXT_REVISION.fetch_add(1, Ordering::Relaxed)
+}
+
+impl Default for RenderBuffers {
+    fn default() -> Self {
+        Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            batches: Vec::new(),
+            revision: next_revision(),
+            previous_revision: None,
+            dirty_vertices: Vec::new(),
+            dirty_indices: Vec::new(),
+        }
+    }
+}
+
+impl RenderBuffers {
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+
+    /// Indices into `vertices`, every three of them form one triangle.
+    pub fn indices(&self) -> &[Index] {
+        &self.indices
+    }
+
+    /// Batches covering all of `indices`, in the order they must be drawn.
+    pub fn batches(&self) -> &[DrawBatch] {
+        &self.batches
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Revision the dirty ranges are relative to, `None` if everything is new.
+    pub fn previous_revision(&self) -> Option<u64> {
+        self.previous_revision
+    }
+
+    /// Ranges of `vertices` that changed since `previous_revision`, sorted and not overlapping.
+    pub fn dirty_vertices(&self) -> &[Range<u32>] {
+        &self.dirty_vertices
+    }
+
+    /// Ranges of `indices` that changed since `previous_revision`, sorted and not overlapping.
+    pub fn dirty_indices(&self) -> &[Range<u32>] {
+        &self.dirty_indices
+    }
+
+    /// Starts a new revision in which nothing is dirty yet.
+    fn next_revision(&mut self) {
+        self.previous_revision = Some(self.revision);
+        self.revision = next_revision();
+        self.dirty_vertices.clear();
+        self.dirty_indices.clear();
+    }
 }
 
 impl RenderQueue {
-    pub fn add(&mut self, shape: Shape) {
-        self.shapes.push(shape);
+    /// Adds a shape drawn with the default `DrawParams`.
+    pub fn add(&mut self, shape: Shape) -> ShapeHandle {
+        self.add_with(shape, DrawParams::default())
     }
 
+    pub fn add_with(&mut self, shape: Shape, params: DrawParams) -> ShapeHandle {
+        let queued = QueuedShape {
+            shape,
+            params,
+            visible: true,
+            order: self.next_order,
+            dirty: true,
+            vertices: None,
+            indices: None,
+            texture: None,
+        };
+        self.next_order += 1;
+        self.layout_changed = true;
+
+        let index = match self.free_slots.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(Slot::default());
+                (self.slots.len() - 1) as u32
+            }
+        };
+        let slot = &mut self.slots[index as usize];
+        slot.shape = Some(queued);
+
+        ShapeHandle {
+            index,
+            generation: slot.generation,
+        }
+    }
+
+    pub fn get(&self, handle: ShapeHandle) -> Option<&Shape> {
+        self.queued(handle).map(|queued| &queued.shape)
+    }
+
+    /// The shape is tessellated again before the next frame is drawn.
+    pub fn get_mut(&mut self, handle: ShapeHandle) -> Option<&mut Shape> {
+        if !self.contains(handle) {
+            return None;
+        }
+        self.shapes_changed = true;
+
+        let queued = self.queued_mut(handle)?;
+        queued.dirty = true;
+        Some(&mut queued.shape)
+    }
+
+    /// Removes the shape from the queue and returns it.
+    /// Returns `None` if the handle is no longer valid.
+    pub fn remove(&mut self, handle: ShapeHandle) -> Option<Shape> {
+        self.queued(handle)?;
+
+        let slot = &mut self.slots[handle.index as usize];
+        let queued = slot.shape.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free_slots.push(handle.index);
+        self.layout_changed = true;
+
+        Some(queued.shape)
+    }
+
+    /// Whether the handle still refers to a shape in the queue.
+    pub fn contains(&self, handle: ShapeHandle) -> bool {
+        self.queued(handle).is_some()
+    }
+
+    pub fn params(&self, handle: ShapeHandle) -> Option<DrawParams> {
+        self.queued(handle).map(|queued| queued.params)
+    }
+
+    /// Returns `false` if the handle is no longer valid.
+    pub fn set_params(&mut self, handle: ShapeHandle, params: DrawParams) -> bool {
+        let Some(queued) = self.queued_mut(handle) else {
+            return false;
+        };
+        if queued.params != params {
+            queued.params = params;
+            self.layout_changed = true;
+        }
+        true
+    }
+
+    pub fn is_visible(&self, handle: ShapeHandle) -> Option<bool> {
+        self.queued(handle).map(|queued| queued.visible)
+    }
+
+    /// Hidden shapes stay in the queue but are not drawn.
+    /// Returns `false` if the handle is no longer valid.
+    pub fn set_visible(&mut self, handle: ShapeHandle, visible: bool) -> bool {
+        let Some(queued) = self.queued_mut(handle) else {
+            return false;
+        };
+        if queued.visible != visible {
+            queued.visible = visible;
+            self.layout_changed = true;
+        }
+        true
+    }
+
+    /// Number of shapes in the queue, including hidden ones.
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free_slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over all shapes in the queue, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (ShapeHandle, &Shape)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let queued = slot.shape.as_ref()?;
+            Some((Self::handle(index, slot.generation), &queued.shape))
+        })
+    }
+
+    /// Iterates mutably over all shapes in the queue, in no particular order.
+    /// Every shape is tessellated again before the next frame, prefer `get_mut`
+    /// when only a few of them change.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (ShapeHandle, &mut Shape)> {
+        self.shapes_changed = true;
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let queued = slot.shape.as_mut()?;
+                queued.dirty = true;
+                Some((Self::handle(index, slot.generation), &mut queued.shape))
+            })
+    }
+
+    /// Removes all shapes. Handles of the removed shapes are no longer valid.
     pub fn clear(&mut self) {
-        self.shapes.clear();
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot.shape.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free_slots.push(index as u32);
+                self.layout_changed = true;
+            }
+        }
+    }
+
+    fn handle(index: usize, generation: u32) -> ShapeHandle {
+        ShapeHandle {
+            index: index as u32,
+            generation,
+        }
     }
 
-    pub(super) fn buffers(&self, screen_width: f32, screen_height: f32) -> RenderBuffers {
+    fn queued(&self, handle: ShapeHandle) -> Option<&QueuedShape> {
+        let slot = self.slots.get(handle.index as usize)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        slot.shape.as_ref()
+    }
+
+    fn queued_mut(&mut self, handle: ShapeHandle) -> Option<&mut QueuedShape> {
+        let slot = self.slots.get_mut(handle.index as usize)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        slot.shape.as_mut()
+    }
+
+    /// Brings the buffers up to date with the shapes and returns them.
+    /// `pixels_per_unit` is the scale of the world on screen, curved shapes are tessellated
+    /// again when it changes.
+    pub(super) fn buffers(&mut self, pixels_per_unit: f32) -> &RenderBuffers {
+        if self.pixels_per_unit != pixels_per_unit {
+            self.pixels_per_unit = pixels_per_unit;
+            for queued in self.slots.iter_mut().filter_map(|slot| slot.shape.as_mut()) {
+                if queued.shape.is_curved() {
+                    queued.dirty = true;
+                    self.shapes_changed = true;
+                }
+            }
+        }
+        if self.layout_changed || (self.shapes_changed && !self.update_in_place()) {
+            self.rebuild();
+        }
+        self.layout_changed = false;
+        self.shapes_changed = false;
+
+        &self.buffers
+    }
+
+    /// Writes the dirty shapes over their old vertices.
+    /// Fails without changing anything if a shape no longer fits in its old place
+    /// or is drawn with another texture, which needs another batch.
+    fn update_in_place(&mut self) -> bool {
+        let mut updates = Vec::new();
+        for queued in self.slots.iter().filter_map(|slot| slot.shape.as_ref()) {
+            if !queued.dirty || !queued.visible {
+                continue;
+            }
+            let (Some(vertex_range), Some(index_range)) = (&queued.vertices, &queued.indices)
+            else {
+                return false;
+            };
+            if queued.shape.texture().map(Texture::id) != queued.texture {
+                return false;
+            }
+
+            let (vertices, indices) = queued.shape.tessellate(self.pixels_per_unit);
+            let old_indices =
+                &self.buffers.indices[index_range.start as usize..index_range.end as usize];
+            let same_indices = indices.len() == old_indices.len()
+                && indices
+                    .iter()
+                    .zip(old_indices)
+                    .all(|(index, old)| index + vertex_range.start == *old);
+            if vertices.len() != vertex_range.len() || !same_indices {
+                return false;
+            }
+
+            updates.push((vertex_range.clone(), vertices));
+        }
+
+        self.buffers.next_revision();
+        updates.sort_by_key(|(range, _)| range.start);
+        for (range, vertices) in updates {
+            self.buffers.vertices[range.start as usize..range.end as usize]
+                .copy_from_slice(&vertices);
+            push_range(&mut self.buffers.dirty_vertices, range);
+        }
+        for queued in self.slots.iter_mut().filter_map(|slot| slot.shape.as_mut()) {
+            queued.dirty &= !queued.visible;
+        }
+
+        true
+    }
+
+    /// Lays out all visible shapes again. Only dirty shapes are tessellated,
+    /// the others are copied from the previous buffers.
+    fn rebuild(&mut self) {
+        let pixels_per_unit = self.pixels_per_unit;
         let mut vertices = Vec::new();
         let mut indices = Vec::new();
-        let mut next_index = 0;
+        let mut batches: Vec<DrawBatch> = Vec::new();
+
+        let mut ordered: Vec<&mut QueuedShape> = self
+            .slots
+            .iter_mut()
+            .filter_map(|slot| slot.shape.as_mut())
+            .collect();
+        ordered.sort_unstable_by_key(|queued| (queued.params.layer, queued.order));
 
-        for shape in &self.shapes {
-            let local_vertices = shape.get_vertices(screen_width, screen_height);
-            let local_indices = shape.get_indices();
-            let local_indices = local_indices
-                .into_iter()
-                .map(move |index| next_index + index);
+        for queued in ordered {
+            if !queued.visible {
+                queued.vertices = None;
+                queued.indices = None;
+                continue;
+            }
 
-            next_index += local_vertices.len() as u16;
-            vertices.extend(local_vertices);
-            indices.extend(local_indices);
+            let next_index = vertices.len() as Index;
+            let batch_start = indices.len() as u32;
+            match (&queued.vertices, &queued.indices, queued.dirty) {
+                (Some(old_vertices), Some(old_indices), false) => {
+                    let old_vertices = old_vertices.start as usize..old_vertices.end as usize;
+                    let old_indices = old_indices.start as usize..old_indices.end as usize;
+                    let old_start = old_vertices.start as Index;
+
+                    vertices.extend_from_slice(&self.buffers.vertices[old_vertices]);
+                    indices.extend(
+                        self.buffers.indices[old_indices]
+                            .iter()
+                            .map(|index| index - old_start + next_index),
+                    );
+                }
+                _ => {
+                    let (local_vertices, local_indices) = queued.shape.tessellate(pixels_per_unit);
+                    vertices.extend(local_vertices);
+                    indices.extend(local_indices.into_iter().map(|index| next_index + index));
+                }
+            }
+            let batch_end = indices.len() as u32;
+
+            let texture = queued.shape.texture();
+            queued.dirty = false;
+            queued.vertices = Some(next_index..vertices.len() as u32);
+            queued.indices = Some(batch_start..batch_end);
+            queued.texture = texture.map(Texture::id);
+
+            match batches.last_mut() {
+                Some(batch)
+                    if batch.blend_mode == queued.params.blend_mode
+                        && batch.texture.as_ref() == texture =>
+                {
+                    batch.indices.end = batch_end;
+                }
+                _ => batches.push(DrawBatch {
+                    blend_mode: queued.params.blend_mode,
+                    texture: texture.cloned(),
+                    indices: batch_start..batch_end,
+                }),
+            }
         }
 
-        dbg!(&vertices);
-        dbg!(&indices);
+        let buffers = &mut self.buffers;
+        buffers.next_revision();
+        push_range(&mut buffers.dirty_vertices, 0..vertices.len() as u32);
+        push_range(&mut buffers.dirty_indices, 0..indices.len() as u32);
+        buffers.vertices = vertices;
+        buffers.indices = indices;
+        buffers.batches = batches;
+    }
+}
 
-        RenderBuffers { vertices, indices }
+/// Adds `range` to sorted `ranges`, merging it with the last one if they touch.
+fn push_range(ranges: &mut Vec<Range<u32>>, range: Range<u32>) {
+    if range.is_empty() {
+        return;
+    }
+    match ranges.last_mut() {
+        Some(last) if last.end >= range.start => last.end = last.end.max(range.end),
+        _ => ranges.push(range),
     }
 }
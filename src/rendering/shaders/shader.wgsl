@@ -1,11 +1,26 @@
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+// Shapes without a texture are drawn with a white pixel.
+@group(1) @binding(0)
+var t_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var s_texture: sampler;
+
 struct VertexInput {
     @location(0) position: vec2<f32>,
-    @location(1) color: vec3<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) uv: vec2<f32>,
 };
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
-    @location(0) color: vec3<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
 };
 
 @vertex
@@ -14,12 +29,24 @@ fn vs_main(
 ) -> VertexOutput {
     var out: VertexOutput;
     out.color = model.color;
-    out.clip_position = vec4<f32>(model.position, 0.0, 1.0);
+    out.uv = model.uv;
+    out.clip_position = camera.view_proj * vec4<f32>(model.position, 0.0, 1.0);
 
     return out;
 }
 
+fn shade(in: VertexOutput) -> vec4<f32> {
+    return in.color * textureSample(t_texture, s_texture, in.uv);
+}
+
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    return vec4<f32>(in.color, 1.0);
+    return shade(in);
+}
+
+// Used by blend modes that need the color multiplied by its alpha.
+@fragment
+fn fs_premultiplied(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = shade(in);
+    return vec4<f32>(color.rgb * color.a, color.a);
 }
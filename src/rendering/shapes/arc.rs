@@ -0,0 +1,127 @@
+use std::f32::consts::TAU;
+
+use crate::{
+    math::Vector2,
+    rendering::{
+        color::Color,
+        index::Index,
+        shapes::{
+            DEFAULT_COLOR, point_to_vertex,
+            tessellation::{Tessellation, arc_angles, band_indices, ellipse_point},
+            transform::Transform2D,
+        },
+        vertex::Vertex,
+    },
+};
+
+/// A curved band following a part of a circle, like a ring or a progress indicator.
+#[derive(Debug, Clone)]
+pub struct Arc {
+    pub center: Vector2<f32>,
+    /// Distance from the center to the middle of the band.
+    pub radius: f32,
+    /// Width of the band, centered on `radius`.
+    pub thickness: f32,
+    /// Angle where the band starts in radians, counter-clockwise from the x axis.
+    pub start_angle: f32,
+    /// Angle the band spans in radians.
+    /// Positive values go counter-clockwise, negative ones clockwise.
+    /// Sweeps past a full turn are drawn as one full turn.
+    pub sweep_angle: f32,
+    pub inner_color: Color,
+    /// Color of the outer edge. The colors are blended across the band.
+    pub outer_color: Color,
+    pub tessellation: Tessellation,
+    /// Placement in the world, rotating and scaling around its center by default.
+    pub transform: Transform2D,
+}
+
+impl Arc {
+    /// Creates an arc filled with `DEFAULT_COLOR`.
+    pub fn new(
+        center: Vector2<f32>,
+        radius: f32,
+        thickness: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+    ) -> Self {
+        Arc {
+            center,
+            radius,
+            thickness,
+            start_angle,
+            sweep_angle,
+            inner_color: DEFAULT_COLOR,
+            outer_color: DEFAULT_COLOR,
+            tessellation: Tessellation::default(),
+            transform: Transform2D::default(),
+        }
+    }
+
+    /// Replaces the transform, which is applied when the arc is drawn.
+    pub fn with_transform(self, transform: Transform2D) -> Self {
+        Arc { transform, ..self }
+    }
+
+    /// Fills the whole arc with `color`.
+    pub fn with_color(self, color: Color) -> Self {
+        self.with_gradient(color, color)
+    }
+
+    /// Blends from `inner` on the inner edge to `outer` on the outer edge.
+    pub fn with_gradient(self, inner: Color, outer: Color) -> Self {
+        Arc {
+            inner_color: inner,
+            outer_color: outer,
+            ..self
+        }
+    }
+
+    pub fn with_tessellation(self, tessellation: Tessellation) -> Self {
+        Arc {
+            tessellation,
+            ..self
+        }
+    }
+
+    fn radii(&self) -> (f32, f32) {
+        let half_thickness = self.thickness.abs() * 0.5;
+        (
+            (self.radius - half_thickness).max(0.0),
+            self.radius + half_thickness,
+        )
+    }
+
+    /// The sweep clamped to one turn, more would draw over itself.
+    fn sweep(&self) -> f32 {
+        self.sweep_angle.clamp(-TAU, TAU)
+    }
+
+    fn segments(&self, pixels_per_unit: f32) -> u32 {
+        let (_, outer) = self.radii();
+        self.tessellation
+            .segments(outer, self.sweep(), pixels_per_unit)
+    }
+
+    pub(super) fn get_vertices(&self, pixels_per_unit: f32) -> Vec<Vertex> {
+        let (inner, outer) = self.radii();
+        let segments = self.segments(pixels_per_unit);
+
+        arc_angles(self.start_angle, self.sweep(), segments)
+            .flat_map(|angle| {
+                let point = |radius| {
+                    let radii = Vector2::new(radius, radius);
+                    ellipse_point(self.center, radii, 0.0, angle)
+                };
+                [
+                    point_to_vertex(point(outer), self.outer_color),
+                    point_to_vertex(point(inner), self.inner_color),
+                ]
+            })
+            .collect()
+    }
+
+    pub(super) fn get_indices(&self, pixels_per_unit: f32) -> Vec<Index> {
+        band_indices(self.segments(pixels_per_unit), self.sweep_angle >= 0.0)
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{
+    math::Vector2,
+    rendering::{
+        color::Color,
+        index::Index,
+        shapes::{
+            DEFAULT_COLOR, ellipse::Ellipse, tessellation::Tessellation, transform::Transform2D,
+        },
+        vertex::Vertex,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct Circle {
+    pub center: Vector2<f32>,
+    pub radius: f32,
+    pub center_color: Color,
+    /// Color of the outline. The colors are blended from the center to the outline.
+    pub edge_color: Color,
+    pub tessellation: Tessellation,
+    /// Placement in the world, rotating and scaling around its center by default.
+    pub transform: Transform2D,
+}
+
+impl Circle {
+    /// Creates a circle filled with `DEFAULT_COLOR`.
+    pub fn new(center: Vector2<f32>, radius: f32) -> Self {
+        Circle {
+            center,
+            radius,
+            center_color: DEFAULT_COLOR,
+            edge_color: DEFAULT_COLOR,
+            tessellation: Tessellation::default(),
+            transform: Transform2D::default(),
+        }
+    }
+
+    /// Replaces the transform, which is applied when the circle is drawn.
+    pub fn with_transform(self, transform: Transform2D) -> Self {
+        Circle { transform, ..self }
+    }
+
+    /// Fills the whole circle with `color`.
+    pub fn with_color(self, color: Color) -> Self {
+        self.with_gradient(color, color)
+    }
+
+    /// Blends from `center` in the middle to `edge` on the outline.
+    pub fn with_gradient(self, center: Color, edge: Color) -> Self {
+        Circle {
+            center_color: center,
+            edge_color: edge,
+            ..self
+        }
+    }
+
+    pub fn with_tessellation(self, tessellation: Tessellation) -> Self {
+        Circle {
+            tessellation,
+            ..self
+        }
+    }
+
+    fn to_ellipse(&self) -> Ellipse {
+        Ellipse::new(self.center, Vector2::new(self.radius, self.radius))
+            .with_gradient(self.center_color, self.edge_color)
+            .with_tessellation(self.tessellation)
+    }
+
+    pub(super) fn get_vertices(&self, pixels_per_unit: f32) -> Vec<Vertex> {
+        self.to_ellipse().get_vertices(pixels_per_unit)
+    }
+
+    pub(super) fn get_indices(&self, pixels_per_unit: f32) -> Vec<Index> {
+        self.to_ellipse().get_indices(pixels_per_unit)
+    }
+}
@@ -0,0 +1,101 @@
+use std::f32::consts::TAU;
+
+use crate::{
+    math::Vector2,
+    rendering::{
+        color::Color,
+        index::Index,
+        shapes::{
+            DEFAULT_COLOR, point_to_vertex,
+            tessellation::{Tessellation, arc_angles, ellipse_point, fan_indices},
+            transform::Transform2D,
+        },
+        vertex::Vertex,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct Ellipse {
+    pub center: Vector2<f32>,
+    /// Radius along the first and the second axis.
+    pub radii: Vector2<f32>,
+    /// Counter-clockwise rotation of the axes in radians.
+    pub rotation: f32,
+    pub center_color: Color,
+    /// Color of the outline. The colors are blended from the center to the outline.
+    pub edge_color: Color,
+    pub tessellation: Tessellation,
+    /// Placement in the world, rotating and scaling around its center by default.
+    pub transform: Transform2D,
+}
+
+impl Ellipse {
+    /// Creates an ellipse with axes along x and y, filled with `DEFAULT_COLOR`.
+    pub fn new(center: Vector2<f32>, radii: Vector2<f32>) -> Self {
+        Ellipse {
+            center,
+            radii,
+            rotation: 0.0,
+            center_color: DEFAULT_COLOR,
+            edge_color: DEFAULT_COLOR,
+            tessellation: Tessellation::default(),
+            transform: Transform2D::default(),
+        }
+    }
+
+    /// Replaces the transform, which is applied when the ellipse is drawn.
+    pub fn with_transform(self, transform: Transform2D) -> Self {
+        Ellipse { transform, ..self }
+    }
+
+    /// Rotates the axes counter-clockwise by another `angle` radians around the center.
+    pub fn rotated(self, angle: f32) -> Self {
+        Ellipse {
+            rotation: self.rotation + angle,
+            ..self
+        }
+    }
+
+    /// Fills the whole ellipse with `color`.
+    pub fn with_color(self, color: Color) -> Self {
+        self.with_gradient(color, color)
+    }
+
+    /// Blends from `center` in the middle to `edge` on the outline.
+    pub fn with_gradient(self, center: Color, edge: Color) -> Self {
+        Ellipse {
+            center_color: center,
+            edge_color: edge,
+            ..self
+        }
+    }
+
+    pub fn with_tessellation(self, tessellation: Tessellation) -> Self {
+        Ellipse {
+            tessellation,
+            ..self
+        }
+    }
+
+    fn segments(&self, pixels_per_unit: f32) -> u32 {
+        let radius = self.radii.x.abs().max(self.radii.y.abs());
+        self.tessellation.segments(radius, TAU, pixels_per_unit)
+    }
+
+    pub(super) fn get_vertices(&self, pixels_per_unit: f32) -> Vec<Vertex> {
+        let outline = arc_angles(0.0, TAU, self.segments(pixels_per_unit)).map(|angle| {
+            let point = ellipse_point(self.center, self.radii, self.rotation, angle);
+            point_to_vertex(point, self.edge_color)
+        });
+
+        std::iter::once(point_to_vertex(self.center, self.center_color))
+            .chain(outline)
+            .collect()
+    }
+
+    pub(super) fn get_indices(&self, pixels_per_unit: f32) -> Vec<Index> {
+        // A negative radius mirrors the ellipse, which turns the outline around
+        let counter_clockwise = self.radii.x * self.radii.y >= 0.0;
+        fan_indices(self.segments(pixels_per_unit), counter_clockwise)
+    }
+}
@@ -0,0 +1,177 @@
+use std::cmp::Ordering;
+
+use crate::{math::Vector2, rendering::index::Index};
+
+/// Decides which parts of overlapping or self-intersecting contours are inside.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FillRule {
+    /// A point is inside when the contours wind around it a non-zero number of times,
+    /// counting counter-clockwise and clockwise turns against each other.
+    #[default]
+    NonZero,
+    /// A point is inside when a ray from it crosses the contours an odd number of times.
+    EvenOdd,
+}
+
+impl FillRule {
+    fn is_inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// An edge of a contour, stored from its lower to its upper end.
+struct Edge {
+    bottom: Vector2<f32>,
+    top: Vector2<f32>,
+    /// +1 if the contour goes up along the edge, -1 if it goes down.
+    winding: i32,
+}
+
+impl Edge {
+    fn x_at(&self, y: f32) -> f32 {
+        if y <= self.bottom.y {
+            return self.bottom.x;
+        }
+        if y >= self.top.y {
+            return self.top.x;
+        }
+        let t = (y - self.bottom.y) / (self.top.y - self.bottom.y);
+        self.bottom.x + (self.top.x - self.bottom.x) * t
+    }
+}
+
+/// Fills closed contours, which may overlap and intersect themselves, according to `rule`.
+///
+/// The area is cut into horizontal slabs at every corner and intersection, and the inside
+/// of each slab into trapezoids. Returns the corners and counter-clockwise triangles.
+///
+/// The slabs are visited from the bottom up while keeping a list of the edges spanning the
+/// current one, so only edges at the same height are ever compared with each other.
+pub(super) fn fill(
+    contours: &[Vec<Vector2<f32>>],
+    rule: FillRule,
+) -> (Vec<Vector2<f32>>, Vec<Index>) {
+    let mut edges = Vec::new();
+    for contour in contours {
+        for (i, &start) in contour.iter().enumerate() {
+            let end = contour[(i + 1) % contour.len()];
+            match start.y.partial_cmp(&end.y) {
+                Some(Ordering::Less) => edges.push(Edge {
+                    bottom: start,
+                    top: end,
+                    winding: 1,
+                }),
+                Some(Ordering::Greater) => edges.push(Edge {
+                    bottom: end,
+                    top: start,
+                    winding: -1,
+                }),
+                // Horizontal edges don't change the winding of any slab
+                _ => {}
+            }
+        }
+    }
+    edges.sort_by(|a, b| a.bottom.y.total_cmp(&b.bottom.y));
+
+    let mut ends: Vec<f32> = edges
+        .iter()
+        .flat_map(|edge| [edge.bottom.y, edge.top.y])
+        .collect();
+    ends.sort_by(f32::total_cmp);
+    ends.dedup();
+
+    let mut points = Vec::new();
+    let mut indices = Vec::new();
+    let mut active: Vec<&Edge> = Vec::new();
+    let mut next_edge = 0;
+    let mut order = Vec::new();
+    let mut slab_bounds = Vec::new();
+    let mut crossing = Vec::new();
+    for ends in ends.windows(2) {
+        let (bottom, top) = (ends[0], ends[1]);
+        // Every edge either spans the whole range between two ends or stays out of it
+        active.retain(|edge| edge.top.y > bottom);
+        while let Some(edge) = edges.get(next_edge)
+            && edge.bottom.y <= bottom
+        {
+            active.push(edge);
+            next_edge += 1;
+        }
+
+        // Sorted by x at the bottom, the edges crossing in between are the pairs that are
+        // in the opposite order at the top, and each swap of insertion sort is one of them
+        order.clear();
+        order.extend(active.iter().copied());
+        order.sort_by(|a, b| {
+            a.x_at(bottom)
+                .total_cmp(&b.x_at(bottom))
+                .then(a.x_at(top).total_cmp(&b.x_at(top)))
+        });
+        slab_bounds.clear();
+        slab_bounds.extend([bottom, top]);
+        for i in 1..order.len() {
+            let mut j = i;
+            while j > 0 && order[j - 1].x_at(top) > order[j].x_at(top) {
+                slab_bounds.extend(intersection_y(order[j - 1], order[j], bottom, top));
+                order.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+        slab_bounds.sort_by(f32::total_cmp);
+        slab_bounds.dedup();
+
+        for bounds in slab_bounds.windows(2) {
+            let (bottom, top) = (bounds[0], bounds[1]);
+            let middle = (bottom + top) * 0.5;
+
+            crossing.clear();
+            crossing.extend(active.iter().map(|edge| (edge.x_at(middle), *edge)));
+            crossing.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for pair in crossing.windows(2) {
+                let (_, left) = pair[0];
+                let (_, right) = pair[1];
+                winding += left.winding;
+                if !rule.is_inside(winding) {
+                    continue;
+                }
+
+                let (bottom_left, bottom_right) = (left.x_at(bottom), right.x_at(bottom));
+                let (top_left, top_right) = (left.x_at(top), right.x_at(top));
+                let start = points.len() as Index;
+                points.extend([
+                    Vector2::new(bottom_left, bottom),
+                    Vector2::new(bottom_right, bottom),
+                    Vector2::new(top_right, top),
+                    Vector2::new(top_left, top),
+                ]);
+                // Where the edges meet the trapezoid is a triangle
+                if bottom_right > bottom_left {
+                    indices.extend([0, 1, 2].map(|index| start + index));
+                }
+                if top_right > top_left {
+                    indices.extend([0, 2, 3].map(|index| start + index));
+                }
+            }
+        }
+    }
+
+    (points, indices)
+}
+
+/// Height at which two edges spanning `bottom..top` cross, if they do so strictly in between.
+fn intersection_y(first: &Edge, second: &Edge, bottom: f32, top: f32) -> Option<f32> {
+    // Compare the horizontal order of the edges at both ends of the height range
+    let below = first.x_at(bottom) - second.x_at(bottom);
+    let above = first.x_at(top) - second.x_at(top);
+    if below * above >= 0.0 {
+        return None;
+    }
+
+    let t = below / (below - above);
+    Some(bottom + (top - bottom) * t).filter(|y| *y > bottom && *y < top)
+}
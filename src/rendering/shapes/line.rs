@@ -0,0 +1,45 @@
+use crate::{
+    math::Vector2,
+    rendering::{
+        index::Index,
+        shapes::{stroke::Stroke, transform::Transform2D},
+        vertex::Vertex,
+    },
+};
+
+/// A straight line between two points.
+#[derive(Debug, Clone)]
+pub struct Line {
+    pub start: Vector2<f32>,
+    pub end: Vector2<f32>,
+    pub stroke: Stroke,
+    /// Placement in the world, rotating and scaling around its middle by default.
+    pub transform: Transform2D,
+}
+
+impl Line {
+    /// Creates a line drawn with the default `Stroke`.
+    pub fn new(start: Vector2<f32>, end: Vector2<f32>) -> Self {
+        Line {
+            start,
+            end,
+            stroke: Stroke::default(),
+            transform: Transform2D::default(),
+        }
+    }
+
+    /// Replaces the transform, which is applied when the line is drawn.
+    pub fn with_transform(self, transform: Transform2D) -> Self {
+        Line { transform, ..self }
+    }
+
+    pub fn with_stroke(self, stroke: Stroke) -> Self {
+        Line { stroke, ..self }
+    }
+
+    pub(super) fn tessellate(&self, pixels_per_unit: f32) -> (Vec<Vertex>, Vec<Index>) {
+        let points = [self.start, self.end];
+        self.stroke
+            .tessellate([(points.as_slice(), false)], pixels_per_unit)
+    }
+}
@@ -1,24 +1,65 @@
+mod arc;
+mod circle;
+mod ellipse;
+mod fill;
+mod line;
+mod path;
+mod polygon;
+mod polyline;
 mod quad;
+mod sector;
 mod shape;
+mod sprite;
+mod stroke;
+mod svg_path;
+mod tessellation;
+mod text;
+mod transform;
 mod triangle;
+mod triangulation;
 
+pub use arc::Arc;
+pub use circle::Circle;
+pub use ellipse::Ellipse;
+pub use fill::FillRule;
+pub use line::Line;
+pub use path::Path;
+pub use polygon::Polygon;
+pub use polyline::Polyline;
 pub use quad::Quad;
+pub use sector::Sector;
 pub use shape::Shape;
+pub use sprite::Sprite;
+pub use stroke::{LineCap, LineJoin, Stroke};
+pub use svg_path::ParsePathError;
+pub use tessellation::Tessellation;
+pub use text::{Text, TextAlign};
+pub use transform::Transform2D;
 pub use triangle::Triangle;
 
-use crate::{math::Vector2, rendering::vertex::Vertex};
+use crate::{
+    math::Vector2,
+    rendering::{color::Color, vertex::Vertex},
+};
 
-pub(super) fn point_to_vertex(
-    point: Vector2<f32>,
-    screen_width: f32,
-    screen_height: f32,
-    color: [f32; 3],
-) -> Vertex {
-    let x = point.x / (screen_width * 0.5);
-    let y = point.y / (screen_height * 0.5);
+/// Color of shapes that were not given any.
+pub const DEFAULT_COLOR: Color = Color::rgb(1.0, 0.2, 0.0);
 
+pub(super) fn point_to_vertex(point: Vector2<f32>, color: Color) -> Vertex {
     Vertex {
-        position: [x, y],
-        color,
+        position: [point.x, point.y],
+        color: color.to_array(),
+        uv: [0.0; 2],
     }
 }
+
+/// Average of `points`, the origin if there are none.
+pub(super) fn center_of(points: &[Vector2<f32>]) -> Vector2<f32> {
+    if points.is_empty() {
+        return Vector2::default();
+    }
+    let sum = points
+        .iter()
+        .fold(Vector2::default(), |sum, &point| sum + point);
+    sum / points.len() as f32
+}
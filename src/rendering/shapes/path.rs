@@ -0,0 +1,472 @@
+use std::f32::consts::{PI, TAU};
+
+use crate::{
+    math::{Affine2, Vector2},
+    rendering::{
+        color::Color,
+        index::Index,
+        shapes::{
+            DEFAULT_COLOR,
+            fill::{FillRule, fill},
+            point_to_vertex,
+            stroke::Stroke,
+            tessellation::{MAX_SEGMENTS, Tessellation, ellipse_point},
+            transform::Transform2D,
+        },
+        vertex::Vertex,
+    },
+};
+
+/// A shape made of straight lines and curves, which can be filled, stroked or both.
+///
+/// ```
+/// # use stellar_engine::{math::Vector2, rendering::shapes::Path};
+/// let drop = Path::new()
+///     .move_to(Vector2::new(0.0, 20.0))
+///     .cubic_to(Vector2::new(10.0, 5.0), Vector2::new(10.0, -10.0), Vector2::new(0.0, -10.0))
+///     .cubic_to(Vector2::new(-10.0, -10.0), Vector2::new(-10.0, 5.0), Vector2::new(0.0, 20.0))
+///     .close();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Path {
+    commands: Vec<PathCommand>,
+    /// Color the inside is filled with, `None` to only draw the stroke.
+    pub fill: Option<Color>,
+    pub fill_rule: FillRule,
+    /// Line drawn along the path, on top of the fill.
+    pub stroke: Option<Stroke>,
+    /// How finely the curves are split into straight segments.
+    pub tessellation: Tessellation,
+    /// Placement in the world, rotating and scaling around its center by default.
+    pub transform: Transform2D,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PathCommand {
+    MoveTo(Vector2<f32>),
+    LineTo(Vector2<f32>),
+    QuadTo(Vector2<f32>, Vector2<f32>),
+    CubicTo(Vector2<f32>, Vector2<f32>, Vector2<f32>),
+    ArcTo {
+        radii: Vector2<f32>,
+        rotation: f32,
+        large_arc: bool,
+        counter_clockwise: bool,
+        to: Vector2<f32>,
+    },
+    Close,
+}
+
+impl Path {
+    /// Creates an empty path, filled with `DEFAULT_COLOR` and without a stroke.
+    pub fn new() -> Self {
+        Path {
+            fill: Some(DEFAULT_COLOR),
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the transform, which is applied when the path is drawn.
+    pub fn with_transform(self, transform: Transform2D) -> Self {
+        Path { transform, ..self }
+    }
+
+    /// Starts a new subpath at `point`.
+    pub fn move_to(mut self, point: Vector2<f32>) -> Self {
+        self.commands.push(PathCommand::MoveTo(point));
+        self
+    }
+
+    pub fn line_to(mut self, point: Vector2<f32>) -> Self {
+        self.commands.push(PathCommand::LineTo(point));
+        self
+    }
+
+    /// Adds a quadratic Bézier curve bent towards `control`.
+    pub fn quad_to(mut self, control: Vector2<f32>, to: Vector2<f32>) -> Self {
+        self.commands.push(PathCommand::QuadTo(control, to));
+        self
+    }
+
+    /// Adds a cubic Bézier curve, leaving towards `control1` and arriving from `control2`.
+    pub fn cubic_to(
+        mut self,
+        control1: Vector2<f32>,
+        control2: Vector2<f32>,
+        to: Vector2<f32>,
+    ) -> Self {
+        self.commands
+            .push(PathCommand::CubicTo(control1, control2, to));
+        self
+    }
+
+    /// Adds a part of an ellipse with the given radii, whose axes are rotated by `rotation` radians.
+    /// Like in SVG, two ellipses and two arcs on each of them connect the points,
+    /// `large_arc` and `counter_clockwise` pick one of them.
+    pub fn arc_to(
+        mut self,
+        radii: Vector2<f32>,
+        rotation: f32,
+        large_arc: bool,
+        counter_clockwise: bool,
+        to: Vector2<f32>,
+    ) -> Self {
+        self.commands.push(PathCommand::ArcTo {
+            radii,
+            rotation,
+            large_arc,
+            counter_clockwise,
+            to,
+        });
+        self
+    }
+
+    /// Connects the current point back to the start of the subpath.
+    pub fn close(mut self) -> Self {
+        self.commands.push(PathCommand::Close);
+        self
+    }
+
+    pub fn with_fill(self, color: Color) -> Self {
+        Path {
+            fill: Some(color),
+            ..self
+        }
+    }
+
+    /// Only draws the stroke of the path.
+    pub fn without_fill(self) -> Self {
+        Path { fill: None, ..self }
+    }
+
+    pub fn with_fill_rule(self, fill_rule: FillRule) -> Self {
+        Path { fill_rule, ..self }
+    }
+
+    pub fn with_stroke(self, stroke: Stroke) -> Self {
+        Path {
+            stroke: Some(stroke),
+            ..self
+        }
+    }
+
+    pub fn with_tessellation(self, tessellation: Tessellation) -> Self {
+        Path {
+            tessellation,
+            ..self
+        }
+    }
+
+    /// Applies `transform` to every point of the path, and to its stroke widths.
+    pub(crate) fn transformed(self, transform: &Affine2<f32>) -> Self {
+        let point = |point| transform.transform_point(point);
+        let commands = self
+            .commands
+            .iter()
+            .map(|command| match *command {
+                PathCommand::MoveTo(to) => PathCommand::MoveTo(point(to)),
+                PathCommand::LineTo(to) => PathCommand::LineTo(point(to)),
+                PathCommand::QuadTo(control, to) => PathCommand::QuadTo(point(control), point(to)),
+                PathCommand::CubicTo(control1, control2, to) => {
+                    PathCommand::CubicTo(point(control1), point(control2), point(to))
+                }
+                PathCommand::ArcTo {
+                    radii,
+                    rotation,
+                    large_arc,
+                    counter_clockwise,
+                    to,
+                } => {
+                    let (radii, rotation) = transform_ellipse(transform, radii, rotation);
+                    PathCommand::ArcTo {
+                        radii,
+                        rotation,
+                        large_arc,
+                        // Mirroring turns the direction around
+                        counter_clockwise: counter_clockwise != (transform.determinant() < 0.0),
+                        to: point(to),
+                    }
+                }
+                PathCommand::Close => PathCommand::Close,
+            })
+            .collect();
+
+        // Lengths are scaled by this factor on average
+        let scale = transform.determinant().abs().sqrt();
+        let stroke = self.stroke.map(|stroke| Stroke {
+            thickness: stroke.thickness * scale,
+            dash_pattern: stroke
+                .dash_pattern
+                .iter()
+                .map(|length| length * scale)
+                .collect(),
+            dash_offset: stroke.dash_offset * scale,
+            ..stroke
+        });
+
+        Path {
+            commands,
+            stroke,
+            ..self
+        }
+    }
+
+    /// Middle of the box around the path, with curves flattened at one pixel per unit
+    /// so the point does not move with the camera zoom.
+    pub(super) fn center(&self) -> Vector2<f32> {
+        let mut points = self.flatten(1.0).into_iter().flat_map(|(points, _)| points);
+        let Some(first) = points.next() else {
+            return Vector2::default();
+        };
+        let (min, max) = points.fold((first, first), |(min, max), point| {
+            (min.min(point), max.max(point))
+        });
+        (min + max) * 0.5
+    }
+
+    /// Splits the path into subpaths of straight segments, returned as their points
+    /// and whether they are closed. `pixels_per_unit` is the scale of the path on screen.
+    pub fn flatten(&self, pixels_per_unit: f32) -> Vec<(Vec<Vector2<f32>>, bool)> {
+        let mut contours = Vec::new();
+        let mut points: Vec<Vector2<f32>> = Vec::new();
+        let mut start = Vector2::default();
+        let mut current = Vector2::default();
+
+        let finish = |points: &mut Vec<Vector2<f32>>, contours: &mut Vec<_>, closed: bool| {
+            if points.len() > 1 {
+                contours.push((std::mem::take(points), closed));
+            }
+            points.clear();
+        };
+
+        for command in &self.commands {
+            // Drawing without a `move_to` continues from the current point
+            if points.is_empty() && !matches!(command, PathCommand::MoveTo(_)) {
+                points.push(current);
+                start = current;
+            }
+
+            match *command {
+                PathCommand::MoveTo(point) => {
+                    finish(&mut points, &mut contours, false);
+                    points.push(point);
+                    start = point;
+                    current = point;
+                }
+                PathCommand::LineTo(point) => {
+                    points.push(point);
+                    current = point;
+                }
+                PathCommand::QuadTo(control, to) => {
+                    let bend = (current - control * 2.0 + to).length();
+                    let segments = self.curve_segments(bend * 0.25, pixels_per_unit);
+                    let from = current;
+                    points.extend((1..=segments).map(|i| {
+                        let t = i as f32 / segments as f32;
+                        let u = 1.0 - t;
+                        from * (u * u) + control * (2.0 * u * t) + to * (t * t)
+                    }));
+                    current = to;
+                }
+                PathCommand::CubicTo(control1, control2, to) => {
+                    let bend = (current - control1 * 2.0 + control2)
+                        .length()
+                        .max((control1 - control2 * 2.0 + to).length());
+                    let segments = self.curve_segments(bend * 0.75, pixels_per_unit);
+                    let from = current;
+                    points.extend((1..=segments).map(|i| {
+                        let t = i as f32 / segments as f32;
+                        let u = 1.0 - t;
+                        from * (u * u * u)
+                            + control1 * (3.0 * u * u * t)
+                            + control2 * (3.0 * u * t * t)
+                            + to * (t * t * t)
+                    }));
+                    current = to;
+                }
+                PathCommand::ArcTo {
+                    radii,
+                    rotation,
+                    large_arc,
+                    counter_clockwise,
+                    to,
+                } => {
+                    let arc = EndpointArc {
+                        from: current,
+                        to,
+                        radii,
+                        rotation,
+                        large_arc,
+                        counter_clockwise,
+                    };
+                    arc.flatten(self.tessellation, pixels_per_unit, &mut points);
+                    current = to;
+                }
+                PathCommand::Close => {
+                    finish(&mut points, &mut contours, true);
+                    current = start;
+                }
+            }
+        }
+        finish(&mut points, &mut contours, false);
+
+        contours
+    }
+
+    /// Number of segments for a curve whose straight segments would leave at most
+    /// `deviation` world units between them and the curve if there was only one of them.
+    ///
+    /// `Tessellation::Segments` counts segments per full turn, and a Bézier curve is treated as
+    /// a quarter turn, so a circle drawn with four curves gets as many segments as a `Circle`.
+    fn curve_segments(&self, deviation: f32, pixels_per_unit: f32) -> u32 {
+        match self.tessellation {
+            Tessellation::Tolerance(tolerance) if tolerance.is_nan() || tolerance <= 0.0 => {
+                MAX_SEGMENTS
+            }
+            Tessellation::Tolerance(tolerance) => {
+                // The deviation falls with the square of the number of segments
+                let segments = (deviation * pixels_per_unit / tolerance).sqrt().ceil();
+                (segments as u32).clamp(1, MAX_SEGMENTS)
+            }
+            Tessellation::Segments(segments) => (segments / 4).max(1),
+        }
+    }
+
+    pub(super) fn tessellate(&self, pixels_per_unit: f32) -> (Vec<Vertex>, Vec<Index>) {
+        let contours = self.flatten(pixels_per_unit);
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        if let Some(color) = self.fill {
+            // Every subpath is filled as if it was closed
+            let outlines: Vec<Vec<Vector2<f32>>> =
+                contours.iter().map(|(points, _)| points.clone()).collect();
+            let (points, fill_indices) = fill(&outlines, self.fill_rule);
+            vertices.extend(
+                points
+                    .into_iter()
+                    .map(|point| point_to_vertex(point, color)),
+            );
+            indices.extend(fill_indices);
+        }
+
+        if let Some(stroke) = &self.stroke {
+            let contours = contours
+                .iter()
+                .map(|(points, closed)| (points.as_slice(), *closed));
+            let (stroke_vertices, stroke_indices) = stroke.tessellate(contours, pixels_per_unit);
+            let offset = vertices.len() as Index;
+            vertices.extend(stroke_vertices);
+            indices.extend(stroke_indices.into_iter().map(|index| index + offset));
+        }
+
+        (vertices, indices)
+    }
+}
+
+/// An elliptical arc given by its end points, as in SVG.
+struct EndpointArc {
+    from: Vector2<f32>,
+    to: Vector2<f32>,
+    radii: Vector2<f32>,
+    rotation: f32,
+    large_arc: bool,
+    counter_clockwise: bool,
+}
+
+impl EndpointArc {
+    /// Appends the points after `from` to `points`.
+    fn flatten(
+        &self,
+        tessellation: Tessellation,
+        pixels_per_unit: f32,
+        points: &mut Vec<Vector2<f32>>,
+    ) {
+        let (from, to) = (self.from, self.to);
+        if from == to {
+            return;
+        }
+        let mut radii = Vector2::new(self.radii.x.abs(), self.radii.y.abs());
+        if radii.x == 0.0 || radii.y == 0.0 {
+            points.push(to);
+            return;
+        }
+
+        // Conversion to the center parameterization from the SVG specification
+        let (sin, cos) = self.rotation.sin_cos();
+        let half_difference = (from - to) * 0.5;
+        let x1 = cos * half_difference.x + sin * half_difference.y;
+        let y1 = -sin * half_difference.x + cos * half_difference.y;
+
+        // Radii too small to reach are scaled up until they do
+        let lambda = (x1 * x1) / (radii.x * radii.x) + (y1 * y1) / (radii.y * radii.y);
+        if lambda > 1.0 {
+            radii *= lambda.sqrt();
+        }
+        let (rx, ry) = (radii.x, radii.y);
+
+        let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let sign = if self.large_arc == self.counter_clockwise {
+            -1.0
+        } else {
+            1.0
+        };
+        let coefficient = sign * (numerator / denominator).max(0.0).sqrt();
+        let center_x = coefficient * rx * y1 / ry;
+        let center_y = -coefficient * ry * x1 / rx;
+
+        let middle = (from + to) * 0.5;
+        let center = middle
+            + Vector2::new(
+                cos * center_x - sin * center_y,
+                sin * center_x + cos * center_y,
+            );
+
+        let start_angle = ((y1 - center_y) / ry).atan2((x1 - center_x) / rx);
+        let end_angle = ((-y1 - center_y) / ry).atan2((-x1 - center_x) / rx);
+        let mut sweep = (end_angle - start_angle).rem_euclid(TAU);
+        if !self.counter_clockwise && sweep > 0.0 {
+            sweep -= TAU;
+        }
+        if sweep == 0.0 && self.large_arc {
+            sweep = if self.counter_clockwise { TAU } else { -TAU };
+        }
+        // Half turns are ambiguous in floating point, the flags decide
+        if (sweep.abs() - PI).abs() < 1e-6 {
+            sweep = if self.counter_clockwise { PI } else { -PI };
+        }
+
+        let segments = tessellation.segments(rx.max(ry), sweep, pixels_per_unit);
+        points.extend((1..segments).map(|i| {
+            let angle = start_angle + sweep * i as f32 / segments as f32;
+            ellipse_point(center, radii, self.rotation, angle)
+        }));
+        points.push(to);
+    }
+}
+
+/// Maps an ellipse with the given radii, rotated by `rotation` radians, to the radii
+/// and rotation of the ellipse `transform` turns it into.
+fn transform_ellipse(
+    transform: &Affine2<f32>,
+    radii: Vector2<f32>,
+    rotation: f32,
+) -> (Vector2<f32>, f32) {
+    // Columns of the matrix that maps the unit circle to the transformed ellipse
+    let x_axis = transform.transform_vector(Vector2::from_angle(rotation) * radii.x);
+    let y_axis =
+        transform.transform_vector(Vector2::from_angle(rotation).perpendicular() * radii.y);
+
+    // The axes of the ellipse are the eigenvectors of M * Mᵀ, its radii the square roots
+    // of the eigenvalues
+    let p = x_axis.x * x_axis.x + y_axis.x * y_axis.x;
+    let q = x_axis.y * x_axis.y + y_axis.y * y_axis.y;
+    let r = x_axis.x * x_axis.y + y_axis.x * y_axis.y;
+    let spread = ((p - q) * (p - q) + 4.0 * r * r).sqrt();
+    let major = ((p + q + spread) * 0.5).max(0.0).sqrt();
+    let minor = ((p + q - spread) * 0.5).max(0.0).sqrt();
+    let angle = 0.5 * (2.0 * r).atan2(p - q);
+
+    (Vector2::new(major, minor), angle)
+}
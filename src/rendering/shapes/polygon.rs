@@ -0,0 +1,85 @@
+use crate::{
+    math::Vector2,
+    rendering::{
+        color::Color,
+        index::Index,
+        shapes::{
+            DEFAULT_COLOR, center_of, point_to_vertex, polyline::Polyline, stroke::Stroke,
+            transform::Transform2D, triangulation::triangulate,
+        },
+        vertex::Vertex,
+    },
+};
+
+/// Any simple polygon, convex or concave, optionally with holes.
+///
+/// The outline and the holes can be given in either winding order,
+/// the triangles are always generated facing the camera.
+#[derive(Debug, Clone)]
+pub struct Polygon {
+    /// Corners of the outline. The last one is connected back to the first one.
+    pub points: Vec<Vector2<f32>>,
+    /// Outlines of the holes, which must lie inside of the polygon and not overlap.
+    pub holes: Vec<Vec<Vector2<f32>>>,
+    pub color: Color,
+    /// Placement in the world, rotating and scaling around the center of its points by default.
+    pub transform: Transform2D,
+}
+
+impl Polygon {
+    /// Creates a polygon without holes filled with `DEFAULT_COLOR`.
+    pub fn new(points: Vec<Vector2<f32>>) -> Self {
+        Polygon {
+            points,
+            holes: Vec::new(),
+            color: DEFAULT_COLOR,
+            transform: Transform2D::default(),
+        }
+    }
+
+    /// Replaces the transform, which is applied when the polygon is drawn.
+    pub fn with_transform(self, transform: Transform2D) -> Self {
+        Polygon { transform, ..self }
+    }
+
+    /// Cuts a hole with the outline `points` out of the polygon.
+    pub fn with_hole(mut self, points: Vec<Vector2<f32>>) -> Self {
+        self.holes.push(points);
+        self
+    }
+
+    /// Fills the whole polygon with `color`.
+    pub fn with_color(self, color: Color) -> Self {
+        Polygon { color, ..self }
+    }
+
+    /// The closed outlines of the polygon and each of its holes, without the inside.
+    pub fn outline(&self, stroke: Stroke) -> Vec<Polyline> {
+        // The holes have centers of their own, so they keep turning around the polygon's
+        let transform = Transform2D {
+            pivot: Some(self.transform.pivot.unwrap_or(center_of(&self.points))),
+            ..self.transform
+        };
+        std::iter::once(&self.points)
+            .chain(&self.holes)
+            .map(|points| {
+                Polyline::new(points.clone())
+                    .closed()
+                    .with_stroke(stroke.clone())
+                    .with_transform(transform)
+            })
+            .collect()
+    }
+
+    pub(super) fn get_vertices(&self) -> Vec<Vertex> {
+        self.points
+            .iter()
+            .chain(self.holes.iter().flatten())
+            .map(|point| point_to_vertex(*point, self.color))
+            .collect()
+    }
+
+    pub(super) fn get_indices(&self) -> Vec<Index> {
+        triangulate(&self.points, &self.holes)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{
+    math::Vector2,
+    rendering::{
+        index::Index,
+        shapes::{stroke::Stroke, transform::Transform2D},
+        vertex::Vertex,
+    },
+};
+
+/// A line through a sequence of points.
+#[derive(Debug, Clone)]
+pub struct Polyline {
+    pub points: Vec<Vector2<f32>>,
+    /// Whether the last point is connected back to the first one.
+    pub closed: bool,
+    pub stroke: Stroke,
+    /// Placement in the world, rotating and scaling around the center of its points by default.
+    pub transform: Transform2D,
+}
+
+impl Polyline {
+    /// Creates an open polyline drawn with the default `Stroke`.
+    pub fn new(points: Vec<Vector2<f32>>) -> Self {
+        Polyline {
+            points,
+            closed: false,
+            stroke: Stroke::default(),
+            transform: Transform2D::default(),
+        }
+    }
+
+    /// Replaces the transform, which is applied when the polyline is drawn.
+    pub fn with_transform(self, transform: Transform2D) -> Self {
+        Polyline { transform, ..self }
+    }
+
+    /// Connects the last point back to the first one.
+    pub fn closed(self) -> Self {
+        Polyline {
+            closed: true,
+            ..self
+        }
+    }
+
+    pub fn with_stroke(self, stroke: Stroke) -> Self {
+        Polyline { stroke, ..self }
+    }
+
+    pub(super) fn tessellate(&self, pixels_per_unit: f32) -> (Vec<Vertex>, Vec<Index>) {
+        self.stroke
+            .tessellate([(self.points.as_slice(), self.closed)], pixels_per_unit)
+    }
+}
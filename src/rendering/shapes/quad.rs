@@ -1,43 +1,100 @@
 use crate::{
     math::Vector2,
-    rendering::{index::Index, shapes::point_to_vertex, vertex::Vertex},
+    rendering::{
+        color::Color,
+        index::Index,
+        shapes::{
+            DEFAULT_COLOR, point_to_vertex, polyline::Polyline, stroke::Stroke,
+            transform::Transform2D,
+        },
+        vertex::Vertex,
+    },
 };
 
 #[derive(Debug, Clone)]
 pub struct Quad {
     pub points: [Vector2<f32>; 4],
+    /// Color of each point. The colors are blended across the quad.
+    pub colors: [Color; 4],
+    /// Placement in the world, rotating and scaling around the center of its points by default.
+    pub transform: Transform2D,
 }
 
 impl Quad {
     /// Creates a square with all sides equal to `side` with center at `center`.
-    /// ```
-    /// A ---side--- D
-    /// |            |
-    /// |     +      |
-    /// |            |
-    /// B ---------- C
-    /// ```
     pub fn square(center: Vector2<f32>, side: f32) -> Self {
-        let half_side = side * 0.5;
+        Quad::rect(center, Vector2::new(side, side))
+    }
+
+    /// Creates a rectangle of `size` with center at `center`.
+    /// ```text
+    /// A ---size.x--- D
+    /// |              |
+    /// |      +    size.y
+    /// |              |
+    /// B ------------ C
+    /// ```
+    pub fn rect(center: Vector2<f32>, size: Vector2<f32>) -> Self {
+        let half_size = size * 0.5;
 
+        Quad::new([
+            center + Vector2::new(-half_size.x, half_size.y), // A
+            center + Vector2::new(-half_size.x, -half_size.y), // B
+            center + Vector2::new(half_size.x, -half_size.y), // C
+            center + Vector2::new(half_size.x, half_size.y),  // D
+        ])
+    }
+
+    /// Creates a quad filled with `DEFAULT_COLOR`.
+    /// The points should be in counter-clockwise order, otherwise the quad is culled.
+    pub fn new(points: [Vector2<f32>; 4]) -> Self {
         Quad {
-            points: [
-                center + Vector2::new(-half_side, half_side),  // A
-                center + Vector2::new(-half_side, -half_side), // B
-                center + Vector2::new(half_side, -half_side),  // C
-                center + Vector2::new(half_side, half_side),   // D
-            ],
+            points,
+            colors: [DEFAULT_COLOR; 4],
+            transform: Transform2D::default(),
         }
     }
 
-    pub(super) fn get_vertices(&self, screen_width: f32, screen_height: f32) -> [Vertex; 4] {
-        let color = [1.0, 0.2, 0.0];
+    /// Replaces the transform, which is applied when the quad is drawn.
+    pub fn with_transform(self, transform: Transform2D) -> Self {
+        Quad { transform, ..self }
+    }
+
+    /// Rotates the quad counter-clockwise by `angle` radians around its pivot,
+    /// adding to the rotation of its transform.
+    pub fn rotated(self, angle: f32) -> Self {
+        let transform = self
+            .transform
+            .with_rotation(self.transform.rotation + angle);
+        self.with_transform(transform)
+    }
+
+    /// Fills the whole quad with `color`.
+    pub fn with_color(self, color: Color) -> Self {
+        self.with_vertex_colors([color; 4])
+    }
+
+    /// Sets the color of each point, creating a gradient.
+    pub fn with_vertex_colors(self, colors: [Color; 4]) -> Self {
+        Quad { colors, ..self }
+    }
+
+    /// The closed outline of the quad, without its inside.
+    pub fn outline(&self, stroke: Stroke) -> Polyline {
+        Polyline::new(self.points.to_vec())
+            .closed()
+            .with_stroke(stroke)
+            .with_transform(self.transform)
+    }
+
+    pub(super) fn get_vertices(&self) -> [Vertex; 4] {
+        let [a, b, c, d] = self.colors;
 
         [
-            point_to_vertex(self.points[0], screen_width, screen_height, color), // A
-            point_to_vertex(self.points[1], screen_width, screen_height, color), // B
-            point_to_vertex(self.points[2], screen_width, screen_height, color), // C
-            point_to_vertex(self.points[3], screen_width, screen_height, color), // D
+            point_to_vertex(self.points[0], a), // A
+            point_to_vertex(self.points[1], b), // B
+            point_to_vertex(self.points[2], c), // C
+            point_to_vertex(self.points[3], d), // D
         ]
     }
 
@@ -0,0 +1,103 @@
+use std::f32::consts::TAU;
+
+use crate::{
+    math::Vector2,
+    rendering::{
+        color::Color,
+        index::Index,
+        shapes::{
+            DEFAULT_COLOR, point_to_vertex,
+            tessellation::{Tessellation, arc_angles, ellipse_point, fan_indices},
+            transform::Transform2D,
+        },
+        vertex::Vertex,
+    },
+};
+
+/// A slice of a circle, like a piece of a pie chart.
+#[derive(Debug, Clone)]
+pub struct Sector {
+    pub center: Vector2<f32>,
+    pub radius: f32,
+    /// Angle of the first edge in radians, counter-clockwise from the x axis.
+    pub start_angle: f32,
+    /// Angle between the first and the second edge in radians.
+    /// Positive values go counter-clockwise, negative ones clockwise.
+    /// Sweeps past a full turn are drawn as one full turn.
+    pub sweep_angle: f32,
+    pub center_color: Color,
+    /// Color of the curved edge. The colors are blended from the center to the curved edge.
+    pub edge_color: Color,
+    pub tessellation: Tessellation,
+    /// Placement in the world, rotating and scaling around its center by default.
+    pub transform: Transform2D,
+}
+
+impl Sector {
+    /// Creates a sector filled with `DEFAULT_COLOR`.
+    pub fn new(center: Vector2<f32>, radius: f32, start_angle: f32, sweep_angle: f32) -> Self {
+        Sector {
+            center,
+            radius,
+            start_angle,
+            sweep_angle,
+            center_color: DEFAULT_COLOR,
+            edge_color: DEFAULT_COLOR,
+            tessellation: Tessellation::default(),
+            transform: Transform2D::default(),
+        }
+    }
+
+    /// Replaces the transform, which is applied when the sector is drawn.
+    pub fn with_transform(self, transform: Transform2D) -> Self {
+        Sector { transform, ..self }
+    }
+
+    /// Fills the whole sector with `color`.
+    pub fn with_color(self, color: Color) -> Self {
+        self.with_gradient(color, color)
+    }
+
+    /// Blends from `center` at the tip to `edge` on the curved edge.
+    pub fn with_gradient(self, center: Color, edge: Color) -> Self {
+        Sector {
+            center_color: center,
+            edge_color: edge,
+            ..self
+        }
+    }
+
+    pub fn with_tessellation(self, tessellation: Tessellation) -> Self {
+        Sector {
+            tessellation,
+            ..self
+        }
+    }
+
+    /// The sweep clamped to one turn, more would draw over itself.
+    fn sweep(&self) -> f32 {
+        self.sweep_angle.clamp(-TAU, TAU)
+    }
+
+    fn segments(&self, pixels_per_unit: f32) -> u32 {
+        self.tessellation
+            .segments(self.radius, self.sweep(), pixels_per_unit)
+    }
+
+    pub(super) fn get_vertices(&self, pixels_per_unit: f32) -> Vec<Vertex> {
+        let radii = Vector2::new(self.radius, self.radius);
+        let segments = self.segments(pixels_per_unit);
+        let edge = arc_angles(self.start_angle, self.sweep(), segments).map(|angle| {
+            let point = ellipse_point(self.center, radii, 0.0, angle);
+            point_to_vertex(point, self.edge_color)
+        });
+
+        std::iter::once(point_to_vertex(self.center, self.center_color))
+            .chain(edge)
+            .collect()
+    }
+
+    pub(super) fn get_indices(&self, pixels_per_unit: f32) -> Vec<Index> {
+        fan_indices(self.segments(pixels_per_unit), self.sweep_angle >= 0.0)
+    }
+}
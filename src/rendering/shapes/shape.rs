@@ -1,31 +1,158 @@
-use crate::rendering::{
-    index::Index,
-    shapes::{quad::Quad, triangle::Triangle},
-    vertex::Vertex,
+use crate::{
+    math::Vector2,
+    rendering::{
+        index::Index,
+        shapes::{
+            arc::Arc, center_of, circle::Circle, ellipse::Ellipse, line::Line, path::Path,
+            polygon::Polygon, polyline::Polyline, quad::Quad, sector::Sector, sprite::Sprite,
+            text::Text, transform::Transform2D, triangle::Triangle,
+        },
+        texture::Texture,
+        vertex::Vertex,
+    },
 };
 
 #[derive(Debug)]
 pub enum Shape {
     Triangle(Triangle),
     Quad(Quad),
+    Circle(Circle),
+    Ellipse(Ellipse),
+    Arc(Arc),
+    Sector(Sector),
+    Polygon(Polygon),
+    Line(Line),
+    Polyline(Polyline),
+    Path(Path),
+    Sprite(Sprite),
+    Text(Text),
 }
 
 impl Shape {
-    pub(in crate::rendering) fn get_vertices(
-        &self,
-        screen_width: f32,
-        screen_height: f32,
-    ) -> Vec<Vertex> {
+    /// Whether the tessellation depends on how large the shape is on screen.
+    pub(in crate::rendering) fn is_curved(&self) -> bool {
+        match self {
+            Shape::Triangle(_)
+            | Shape::Quad(_)
+            | Shape::Polygon(_)
+            | Shape::Sprite(_)
+            | Shape::Text(_) => false,
+            Shape::Circle(_)
+            | Shape::Ellipse(_)
+            | Shape::Arc(_)
+            | Shape::Sector(_)
+            | Shape::Path(_) => true,
+            // Round joins and caps
+            Shape::Line(_) | Shape::Polyline(_) => true,
+        }
+    }
+
+    /// Where the shape is placed in the world, applied when it is tessellated.
+    pub fn transform(&self) -> &Transform2D {
+        match self {
+            Shape::Triangle(triangle) => &triangle.transform,
+            Shape::Quad(quad) => &quad.transform,
+            Shape::Circle(circle) => &circle.transform,
+            Shape::Ellipse(ellipse) => &ellipse.transform,
+            Shape::Arc(arc) => &arc.transform,
+            Shape::Sector(sector) => &sector.transform,
+            Shape::Polygon(polygon) => &polygon.transform,
+            Shape::Line(line) => &line.transform,
+            Shape::Polyline(polyline) => &polyline.transform,
+            Shape::Path(path) => &path.transform,
+            Shape::Sprite(sprite) => &sprite.transform,
+            Shape::Text(text) => &text.transform,
+        }
+    }
+
+    /// Point the transform rotates and scales around when it has no pivot of its own.
+    pub fn center(&self) -> Vector2<f32> {
         match self {
-            Shape::Triangle(triangle) => triangle.get_vertices(screen_width, screen_height).into(),
-            Shape::Quad(quad) => quad.get_vertices(screen_width, screen_height).into(),
+            Shape::Triangle(triangle) => center_of(&triangle.points),
+            Shape::Quad(quad) => center_of(&quad.points),
+            Shape::Circle(circle) => circle.center,
+            Shape::Ellipse(ellipse) => ellipse.center,
+            Shape::Arc(arc) => arc.center,
+            Shape::Sector(sector) => sector.center,
+            Shape::Polygon(polygon) => center_of(&polygon.points),
+            Shape::Line(line) => (line.start + line.end) * 0.5,
+            Shape::Polyline(polyline) => center_of(&polyline.points),
+            Shape::Path(path) => path.center(),
+            Shape::Sprite(sprite) => sprite.center,
+            Shape::Text(text) => text.center(),
         }
     }
 
-    pub(in crate::rendering) fn get_indices(&self) -> Vec<Index> {
+    /// Changes where the shape is placed, like rotating it a bit more every frame.
+    pub fn transform_mut(&mut self) -> &mut Transform2D {
+        match self {
+            Shape::Triangle(triangle) => &mut triangle.transform,
+            Shape::Quad(quad) => &mut quad.transform,
+            Shape::Circle(circle) => &mut circle.transform,
+            Shape::Ellipse(ellipse) => &mut ellipse.transform,
+            Shape::Arc(arc) => &mut arc.transform,
+            Shape::Sector(sector) => &mut sector.transform,
+            Shape::Polygon(polygon) => &mut polygon.transform,
+            Shape::Line(line) => &mut line.transform,
+            Shape::Polyline(polyline) => &mut polyline.transform,
+            Shape::Path(path) => &mut path.transform,
+            Shape::Sprite(sprite) => &mut sprite.transform,
+            Shape::Text(text) => &mut text.transform,
+        }
+    }
+
+    /// Splits the shape into triangles, returned as vertices and indices into them,
+    /// with its transform applied.
+    ///
+    /// `pixels_per_unit` is the number of pixels one world unit covers on screen,
+    /// curved shapes use it to choose their number of segments.
+    pub(in crate::rendering) fn tessellate(
+        &self,
+        pixels_per_unit: f32,
+    ) -> (Vec<Vertex>, Vec<Index>) {
+        let transform = self.transform();
+        // Scaled up shapes cover more pixels per unit of their own
+        let pixels_per_unit = pixels_per_unit * transform.max_scale();
+
+        let (mut vertices, mut indices) = match self {
+            Shape::Triangle(triangle) => (
+                triangle.get_vertices().into(),
+                triangle.get_indices().into(),
+            ),
+            Shape::Quad(quad) => (quad.get_vertices().into(), quad.get_indices().into()),
+            Shape::Circle(circle) => (
+                circle.get_vertices(pixels_per_unit),
+                circle.get_indices(pixels_per_unit),
+            ),
+            Shape::Ellipse(ellipse) => (
+                ellipse.get_vertices(pixels_per_unit),
+                ellipse.get_indices(pixels_per_unit),
+            ),
+            Shape::Arc(arc) => (
+                arc.get_vertices(pixels_per_unit),
+                arc.get_indices(pixels_per_unit),
+            ),
+            Shape::Sector(sector) => (
+                sector.get_vertices(pixels_per_unit),
+                sector.get_indices(pixels_per_unit),
+            ),
+            Shape::Polygon(polygon) => (polygon.get_vertices(), polygon.get_indices()),
+            Shape::Line(line) => line.tessellate(pixels_per_unit),
+            Shape::Polyline(polyline) => polyline.tessellate(pixels_per_unit),
+            Shape::Path(path) => path.tessellate(pixels_per_unit),
+            Shape::Sprite(sprite) => (sprite.get_vertices().into(), sprite.get_indices().into()),
+            Shape::Text(text) => text.tessellate(),
+        };
+        transform.apply_to_mesh(self.center(), &mut vertices, &mut indices);
+        (vertices, indices)
+    }
+
+    /// The texture the shape is drawn with, `None` if it only uses vertex colors.
+    pub(in crate::rendering) fn texture(&self) -> Option<&Texture> {
         match self {
-            Shape::Triangle(triangle) => triangle.get_indices().into(),
-            Shape::Quad(quad) => quad.get_indices().into(),
+            Shape::Sprite(sprite) => Some(&sprite.texture),
+            Shape::Text(text) => Some(text.font.texture()),
+            _ => None,
         }
     }
 }
@@ -0,0 +1,119 @@
+use crate::{
+    math::Vector2,
+    rendering::{
+        color::Color,
+        index::Index,
+        shapes::transform::Transform2D,
+        texture::{Texture, TextureRegion},
+        vertex::Vertex,
+    },
+};
+
+/// A rectangle showing a texture, or a region of it.
+#[derive(Debug, Clone)]
+pub struct Sprite {
+    pub texture: Texture,
+    pub center: Vector2<f32>,
+    /// Size in world units, `None` covers one world unit with each pixel of `region`.
+    pub size: Option<Vector2<f32>>,
+    /// Part of the texture that is shown, `None` shows the whole texture.
+    pub region: Option<TextureRegion>,
+    /// Multiplied with the colors of the texture, white shows them unchanged.
+    pub tint: Color,
+    /// Mirrors the texture horizontally.
+    pub flip_x: bool,
+    /// Mirrors the texture vertically.
+    pub flip_y: bool,
+    /// Placement in the world, rotating and scaling around its center by default.
+    pub transform: Transform2D,
+}
+
+impl Sprite {
+    /// Creates a sprite showing the whole texture with one world unit per pixel.
+    pub fn new(texture: Texture, center: Vector2<f32>) -> Self {
+        Sprite {
+            texture,
+            center,
+            size: None,
+            region: None,
+            tint: Color::WHITE,
+            flip_x: false,
+            flip_y: false,
+            transform: Transform2D::default(),
+        }
+    }
+
+    /// Replaces the transform, which is applied when the sprite is drawn.
+    pub fn with_transform(self, transform: Transform2D) -> Self {
+        Sprite { transform, ..self }
+    }
+
+    /// Stretches the sprite to `size` world units. Use the flips instead of negative sizes,
+    /// which turn the sprite into a back face that is culled.
+    pub fn with_size(self, size: Vector2<f32>) -> Self {
+        Sprite {
+            size: Some(size),
+            ..self
+        }
+    }
+
+    /// Shows only `region` of the texture, like one frame of a sprite sheet.
+    pub fn with_region(self, region: TextureRegion) -> Self {
+        Sprite {
+            region: Some(region),
+            ..self
+        }
+    }
+
+    pub fn with_tint(self, tint: Color) -> Self {
+        Sprite { tint, ..self }
+    }
+
+    pub fn with_flip(self, flip_x: bool, flip_y: bool) -> Self {
+        Sprite {
+            flip_x,
+            flip_y,
+            ..self
+        }
+    }
+
+    /// Size in world units the sprite is drawn with.
+    pub fn drawn_size(&self) -> Vector2<f32> {
+        self.size.unwrap_or_else(|| {
+            let region = self.region();
+            Vector2::new(region.width as f32, region.height as f32)
+        })
+    }
+
+    fn region(&self) -> TextureRegion {
+        self.region.unwrap_or_else(|| self.texture.region())
+    }
+
+    pub(super) fn get_vertices(&self) -> [Vertex; 4] {
+        let half_size = self.drawn_size() * 0.5;
+        let (mut min_uv, mut max_uv) = self.texture.uv_bounds(self.region());
+        if self.flip_x {
+            std::mem::swap(&mut min_uv[0], &mut max_uv[0]);
+        }
+        if self.flip_y {
+            std::mem::swap(&mut min_uv[1], &mut max_uv[1]);
+        }
+
+        let vertex = |x: f32, y: f32, uv: [f32; 2]| Vertex {
+            position: [self.center.x + x, self.center.y + y],
+            color: self.tint.to_array(),
+            uv,
+        };
+        // The texture is stored from its top row, which ends up at the top of the world
+        [
+            vertex(-half_size.x, half_size.y, min_uv), // top left
+            vertex(-half_size.x, -half_size.y, [min_uv[0], max_uv[1]]), // bottom left
+            vertex(half_size.x, -half_size.y, max_uv), // bottom right
+            vertex(half_size.x, half_size.y, [max_uv[0], min_uv[1]]), // top right
+        ]
+    }
+
+    pub(super) fn get_indices(&self) -> [Index; 6] {
+        [0, 1, 2, 0, 2, 3]
+    }
+}
@@ -0,0 +1,456 @@
+use std::f32::consts::PI;
+
+use crate::{
+    math::Vector2,
+    rendering::{
+        color::Color,
+        index::Index,
+        shapes::{
+            DEFAULT_COLOR, point_to_vertex,
+            tessellation::{Tessellation, arc_angles},
+        },
+        vertex::Vertex,
+    },
+};
+
+/// How two segments of a stroke are connected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineJoin {
+    /// The outer edges are extended until they meet, unless that exceeds `Stroke::miter_limit`.
+    #[default]
+    Miter,
+    /// The outer corners are connected with a straight edge.
+    Bevel,
+    /// The outer corners are connected with a circular arc.
+    Round,
+}
+
+/// How the open ends of a stroke look.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineCap {
+    /// The stroke ends exactly at its end points.
+    #[default]
+    Butt,
+    /// The stroke is extended by half its thickness past its end points.
+    Square,
+    /// The stroke ends with a half circle around its end points.
+    Round,
+}
+
+/// How a line is drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke {
+    /// Width of the line in world units, centered on it.
+    pub thickness: f32,
+    pub color: Color,
+    pub join: LineJoin,
+    pub cap: LineCap,
+    /// Longest miter allowed, relative to half the thickness. Longer miters become bevels.
+    pub miter_limit: f32,
+    /// Lengths of alternating dashes and gaps, starting with a dash.
+    /// The line is solid when it is empty, or when a contour would be split into more than
+    /// `MAX_DASHES` dashes.
+    pub dash_pattern: Vec<f32>,
+    /// Distance into the dash pattern at which the line starts.
+    pub dash_offset: f32,
+    /// Used for round joins and caps.
+    pub tessellation: Tessellation,
+}
+
+impl Default for Stroke {
+    fn default() -> Self {
+        Stroke::new(1.0)
+    }
+}
+
+impl Stroke {
+    /// Creates a solid stroke in `DEFAULT_COLOR` with miter joins and butt caps.
+    pub fn new(thickness: f32) -> Self {
+        Stroke {
+            thickness,
+            color: DEFAULT_COLOR,
+            join: LineJoin::default(),
+            cap: LineCap::default(),
+            miter_limit: 4.0,
+            dash_pattern: Vec::new(),
+            dash_offset: 0.0,
+            tessellation: Tessellation::default(),
+        }
+    }
+
+    pub fn with_color(self, color: Color) -> Self {
+        Stroke { color, ..self }
+    }
+
+    pub fn with_join(self, join: LineJoin) -> Self {
+        Stroke { join, ..self }
+    }
+
+    pub fn with_cap(self, cap: LineCap) -> Self {
+        Stroke { cap, ..self }
+    }
+
+    pub fn with_miter_limit(self, miter_limit: f32) -> Self {
+        Stroke {
+            miter_limit,
+            ..self
+        }
+    }
+
+    /// Makes the stroke dashed, see `Stroke::dash_pattern`.
+    pub fn with_dashes(self, pattern: Vec<f32>, offset: f32) -> Self {
+        Stroke {
+            dash_pattern: pattern,
+            dash_offset: offset,
+            ..self
+        }
+    }
+
+    pub fn with_tessellation(self, tessellation: Tessellation) -> Self {
+        Stroke {
+            tessellation,
+            ..self
+        }
+    }
+
+    /// Tessellates the stroke along each contour, given as its points and whether it is closed.
+    pub(super) fn tessellate<'a>(
+        &self,
+        contours: impl IntoIterator<Item = (&'a [Vector2<f32>], bool)>,
+        pixels_per_unit: f32,
+    ) -> (Vec<Vertex>, Vec<Index>) {
+        let mut builder = StrokeBuilder {
+            stroke: self,
+            half_thickness: self.thickness.abs() * 0.5,
+            pixels_per_unit,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        };
+
+        for (points, closed) in contours {
+            let mut points = points.to_vec();
+            points.dedup();
+            if closed && points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+
+            match dashes(&points, closed, &self.dash_pattern, self.dash_offset) {
+                Some(dashes) => {
+                    for mut dash in dashes {
+                        dash.dedup();
+                        builder.contour(&dash, false);
+                    }
+                }
+                None => builder.contour(&points, closed),
+            }
+        }
+
+        (builder.vertices, builder.indices)
+    }
+}
+
+struct StrokeBuilder<'a> {
+    stroke: &'a Stroke,
+    half_thickness: f32,
+    pixels_per_unit: f32,
+    vertices: Vec<Vertex>,
+    indices: Vec<Index>,
+}
+
+impl StrokeBuilder<'_> {
+    /// Adds a convex polygon as a fan of triangles facing the camera.
+    fn convex(&mut self, points: &[Vector2<f32>]) {
+        if points.len() < 3 {
+            return;
+        }
+        let area: f32 = (1..points.len() - 1)
+            .map(|i| (points[i] - points[0]).perp_dot(points[i + 1] - points[0]))
+            .sum();
+        if area == 0.0 {
+            return;
+        }
+
+        let start = self.vertices.len() as Index;
+        let color = self.stroke.color;
+        self.vertices
+            .extend(points.iter().map(|point| point_to_vertex(*point, color)));
+        for i in 1..points.len() as Index - 1 {
+            let triangle = if area > 0.0 {
+                [0, i, i + 1]
+            } else {
+                [0, i + 1, i]
+            };
+            self.indices.extend(triangle.map(|index| start + index));
+        }
+    }
+
+    /// Adds an arc of `offset`-long spokes around `center`, turning by `sweep` radians.
+    fn fan(&mut self, center: Vector2<f32>, offset: Vector2<f32>, sweep: f32) {
+        let radius = self.half_thickness;
+        let segments = self
+            .stroke
+            .tessellation
+            .segments(radius, sweep, self.pixels_per_unit);
+        let start = offset.y.atan2(offset.x);
+
+        let mut points = vec![center];
+        points.extend(
+            arc_angles(start, sweep, segments)
+                .map(|angle| center + Vector2::new(angle.cos(), angle.sin()) * radius),
+        );
+        self.convex(&points);
+    }
+
+    fn contour(&mut self, points: &[Vector2<f32>], closed: bool) {
+        let half = self.half_thickness;
+        if half == 0.0 {
+            return;
+        }
+
+        if points.len() == 1 {
+            // A dot, only visible with caps that reach past the end points
+            let point = points[0];
+            match self.stroke.cap {
+                LineCap::Butt => {}
+                LineCap::Square => self.convex(&[
+                    point + Vector2::new(-half, -half),
+                    point + Vector2::new(half, -half),
+                    point + Vector2::new(half, half),
+                    point + Vector2::new(-half, half),
+                ]),
+                LineCap::Round => self.fan(point, Vector2::new(half, 0.0), 2.0 * PI),
+            }
+            return;
+        }
+        if points.len() < 2 || (closed && points.len() < 3) {
+            return;
+        }
+
+        let segment_count = if closed {
+            points.len()
+        } else {
+            points.len() - 1
+        };
+        let segment = |i: usize| (points[i], points[(i + 1) % points.len()]);
+
+        // Consecutive segments overlap on the inside of a join, which translucent strokes
+        // would show. They are cut off where their inner edges cross instead.
+        let corners: Vec<_> = (0..points.len())
+            .map(|i| {
+                let joined = closed || (i > 0 && i < points.len() - 1);
+                let previous = points[(i + points.len() - 1) % points.len()];
+                let next = points[(i + 1) % points.len()];
+                joined
+                    .then(|| self.inner_corner(previous, points[i], next))
+                    .flatten()
+            })
+            .collect();
+
+        for i in 0..segment_count {
+            let (mut start, mut end) = segment(i);
+            let direction = (end - start).try_normalize().unwrap_or_default();
+            let normal = direction.perpendicular() * half;
+
+            if !closed && self.stroke.cap == LineCap::Square {
+                if i == 0 {
+                    start -= direction * half;
+                }
+                if i == segment_count - 1 {
+                    end += direction * half;
+                }
+            }
+
+            let mut outline = match corners[i] {
+                Some((corner, true)) => vec![corner, start, start - normal],
+                Some((corner, false)) => vec![start + normal, start, corner],
+                None => vec![start + normal, start - normal],
+            };
+            match corners[(i + 1) % points.len()] {
+                Some((corner, true)) => outline.extend([end - normal, end, corner]),
+                Some((corner, false)) => outline.extend([corner, end, end + normal]),
+                None => outline.extend([end - normal, end + normal]),
+            }
+            self.convex(&outline);
+        }
+
+        // Joins between consecutive segments
+        let joins = if closed {
+            0..points.len()
+        } else {
+            1..points.len() - 1
+        };
+        for i in joins {
+            let previous = points[(i + points.len() - 1) % points.len()];
+            let point = points[i];
+            let next = points[(i + 1) % points.len()];
+            self.join(previous, point, next);
+        }
+
+        if !closed && self.stroke.cap == LineCap::Round {
+            let last = points.len() - 1;
+            for (end, before) in [(points[0], points[1]), (points[last], points[last - 1])] {
+                let direction = (end - before).try_normalize().unwrap_or_default();
+                self.fan(end, direction.perpendicular() * -half, PI);
+            }
+        }
+    }
+
+    /// Where the inner edges of the segments meeting at `point` cross, and whether that is
+    /// on their left. `None` if the segments are too short to be cut off there.
+    fn inner_corner(
+        &self,
+        previous: Vector2<f32>,
+        point: Vector2<f32>,
+        next: Vector2<f32>,
+    ) -> Option<(Vector2<f32>, bool)> {
+        let incoming = (point - previous).try_normalize()?;
+        let outgoing = (next - point).try_normalize()?;
+        let left = incoming.perp_dot(outgoing) > 0.0;
+        let half = if left {
+            self.half_thickness
+        } else {
+            -self.half_thickness
+        };
+
+        let inner_incoming = incoming.perpendicular() * half;
+        let middle = (inner_incoming + outgoing.perpendicular() * half).try_normalize()?;
+        let corner = point + middle * (half * half / middle.dot(inner_incoming));
+
+        // Both segments are cut by the same length, leave room for a cut at their other end
+        let cut = (point - corner).dot(incoming).abs();
+        let room = (point - previous).length().min((next - point).length()) * 0.5;
+        (cut.is_finite() && cut <= room).then_some((corner, left))
+    }
+
+    fn join(&mut self, previous: Vector2<f32>, point: Vector2<f32>, next: Vector2<f32>) {
+        let incoming = (point - previous).try_normalize().unwrap_or_default();
+        let outgoing = (next - point).try_normalize().unwrap_or_default();
+        let turn = incoming.perp_dot(outgoing);
+        if turn.abs() < 1e-6 && incoming.dot(outgoing) > 0.0 {
+            // Straight on, the segments already meet
+            return;
+        }
+
+        // The gap to fill is on the outside of the turn
+        let side = if turn > 0.0 { -1.0 } else { 1.0 };
+        let half = self.half_thickness;
+        let outer_incoming = incoming.perpendicular() * (half * side);
+        let outer_outgoing = outgoing.perpendicular() * (half * side);
+
+        match self.stroke.join {
+            LineJoin::Round => {
+                let sweep = outer_incoming.angle_between(outer_outgoing);
+                self.fan(point, outer_incoming, sweep);
+            }
+            LineJoin::Miter | LineJoin::Bevel => {
+                let bevel = [point, point + outer_incoming, point + outer_outgoing];
+                let middle = (outer_incoming + outer_outgoing)
+                    .try_normalize()
+                    .unwrap_or_default();
+                // Ratio of the miter length to half the thickness
+                let cos_half_angle = middle.dot(outer_incoming) / half;
+                let miter_ratio = 1.0 / cos_half_angle;
+
+                if self.stroke.join == LineJoin::Bevel
+                    || !miter_ratio.is_finite()
+                    || miter_ratio > self.stroke.miter_limit
+                {
+                    self.convex(&bevel);
+                } else {
+                    let miter = point + middle * (half * miter_ratio);
+                    self.convex(&[bevel[0], bevel[1], miter, bevel[2]]);
+                }
+            }
+        }
+    }
+}
+
+/// Most dashes a single contour is split into, which keeps a tiny pattern on a huge
+/// contour from producing millions of vertices.
+const MAX_DASHES: f64 = 65_536.0;
+
+/// Splits a contour into the dashes of `pattern`, `None` if the pattern is solid or would
+/// need more than `MAX_DASHES` dashes.
+fn dashes(
+    points: &[Vector2<f32>],
+    closed: bool,
+    pattern: &[f32],
+    offset: f32,
+) -> Option<Vec<Vec<Vector2<f32>>>> {
+    if pattern.is_empty()
+        || pattern
+            .iter()
+            .any(|length| length.is_nan() || *length < 0.0)
+        || pattern.iter().sum::<f32>() <= 0.0
+    {
+        return None;
+    }
+    // Like in SVG, an odd number of lengths is repeated to get dashes and gaps of each
+    let pattern: Vec<f32> = if pattern.len() % 2 == 1 {
+        pattern.iter().chain(pattern).copied().collect()
+    } else {
+        pattern.to_vec()
+    };
+    let total: f32 = pattern.iter().sum();
+
+    let segment_count = if closed {
+        points.len()
+    } else {
+        points.len().saturating_sub(1)
+    };
+    let segments = (0..segment_count).map(|i| (points[i], points[(i + 1) % points.len()]));
+    let contour_length: f64 = segments
+        .clone()
+        .map(|(start, end)| (end - start).length() as f64)
+        .sum();
+    if contour_length / total as f64 * (pattern.len() / 2) as f64 > MAX_DASHES {
+        return None;
+    }
+
+    // Positions along a segment are tracked in f64, as adding a short dash to a long
+    // distance in f32 can leave the position unchanged
+    let mut index = 0;
+    let mut remaining = pattern[0] as f64;
+    // Advance through the pattern by the offset
+    let mut skip = offset.rem_euclid(total) as f64;
+    while skip >= remaining {
+        skip -= remaining;
+        index = (index + 1) % pattern.len();
+        remaining = pattern[index] as f64;
+    }
+    remaining -= skip;
+
+    let mut dashes = Vec::new();
+    let mut dash = Vec::new();
+    let mut drawing = index % 2 == 0;
+    if drawing && let Some(first) = points.first() {
+        dash.push(*first);
+    }
+
+    for (start, end) in segments {
+        let length = (end - start).length() as f64;
+        let mut position = 0.0;
+
+        while length - position > remaining {
+            position += remaining;
+            let point = start + (end - start) * (position / length) as f32;
+            dash.push(point);
+            if drawing {
+                dashes.push(std::mem::take(&mut dash));
+            }
+            drawing = !drawing;
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index] as f64;
+        }
+
+        remaining -= length - position;
+        if drawing {
+            dash.push(end);
+        }
+    }
+    if drawing && !dash.is_empty() {
+        dashes.push(dash);
+    }
+
+    Some(dashes)
+}
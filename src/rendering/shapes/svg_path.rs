@@ -0,0 +1,252 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::{math::Vector2, rendering::shapes::path::Path};
+
+impl Path {
+    /// Parses the path data of an SVG `d` attribute, like `"M 0 0 L 10 0 Q 10 10 0 10 Z"`.
+    ///
+    /// Coordinates are taken over as they are. SVG points its y axis down, so the path
+    /// comes out mirrored unless it is flipped, the directions of arcs stay consistent with it.
+    pub fn from_svg(data: &str) -> Result<Self, ParsePathError> {
+        let mut parser = Parser { data, position: 0 };
+        let mut path = Path::new();
+        let mut current = Vector2::default();
+        let mut start = Vector2::default();
+        // Control point the next smooth curve mirrors, if the previous command was a curve
+        let mut last_cubic_control: Option<Vector2<f32>> = None;
+        let mut last_quad_control: Option<Vector2<f32>> = None;
+        let mut command: Option<u8> = None;
+
+        loop {
+            parser.skip_separators();
+            let Some(next) = parser.peek() else {
+                break;
+            };
+
+            let name = if next.is_ascii_alphabetic() {
+                parser.position += 1;
+                next
+            } else {
+                // Numbers without a command letter repeat the previous command
+                match command {
+                    None => return Err(ParsePathError::MissingMoveTo),
+                    Some(b'Z' | b'z') => return Err(parser.unexpected_character()),
+                    Some(previous) => previous,
+                }
+            };
+            if command.is_none() && !matches!(name, b'M' | b'm') {
+                return Err(ParsePathError::MissingMoveTo);
+            }
+            command = Some(name);
+
+            let relative = name.is_ascii_lowercase();
+            let origin = if relative {
+                current
+            } else {
+                Vector2::default()
+            };
+            let mut cubic_control = None;
+            let mut quad_control = None;
+
+            match name.to_ascii_uppercase() {
+                b'M' => {
+                    let point = origin + parser.point()?;
+                    path = path.move_to(point);
+                    current = point;
+                    start = point;
+                    // Further coordinate pairs are lines
+                    command = Some(if relative { b'l' } else { b'L' });
+                }
+                b'L' => {
+                    current = origin + parser.point()?;
+                    path = path.line_to(current);
+                }
+                b'H' => {
+                    current.x = origin.x + parser.number()?;
+                    path = path.line_to(current);
+                }
+                b'V' => {
+                    current.y = origin.y + parser.number()?;
+                    path = path.line_to(current);
+                }
+                b'C' | b'S' => {
+                    let control1 = if name.eq_ignore_ascii_case(&b'C') {
+                        origin + parser.point()?
+                    } else {
+                        last_cubic_control.map_or(current, |control| current * 2.0 - control)
+                    };
+                    let control2 = origin + parser.point()?;
+                    let to = origin + parser.point()?;
+                    path = path.cubic_to(control1, control2, to);
+                    cubic_control = Some(control2);
+                    current = to;
+                }
+                b'Q' | b'T' => {
+                    let control = if name.eq_ignore_ascii_case(&b'Q') {
+                        origin + parser.point()?
+                    } else {
+                        last_quad_control.map_or(current, |control| current * 2.0 - control)
+                    };
+                    let to = origin + parser.point()?;
+                    path = path.quad_to(control, to);
+                    quad_control = Some(control);
+                    current = to;
+                }
+                b'A' => {
+                    let radii = Vector2::new(parser.number()?, parser.number()?);
+                    let rotation = parser.number()?.to_radians();
+                    let large_arc = parser.flag()?;
+                    let sweep = parser.flag()?;
+                    let to = origin + parser.point()?;
+                    path = path.arc_to(radii, rotation, large_arc, sweep, to);
+                    current = to;
+                }
+                b'Z' => {
+                    path = path.close();
+                    current = start;
+                }
+                _ => {
+                    parser.position -= 1;
+                    return Err(parser.unexpected_character());
+                }
+            }
+
+            last_cubic_control = cubic_control;
+            last_quad_control = quad_control;
+        }
+
+        Ok(path)
+    }
+}
+
+impl FromStr for Path {
+    type Err = ParsePathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_svg(s)
+    }
+}
+
+/// Error returned when parsing SVG path data fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParsePathError {
+    /// The path data does not start with a move command.
+    MissingMoveTo,
+    /// A character that is not part of any command or number.
+    UnexpectedCharacter { character: char, position: usize },
+    /// A command is missing a number at the byte position.
+    ExpectedNumber(usize),
+    /// An arc is missing its `0` or `1` flag at the byte position.
+    ExpectedFlag(usize),
+}
+
+impl fmt::Display for ParsePathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMoveTo => write!(f, "path data must start with a move command"),
+            Self::UnexpectedCharacter {
+                character,
+                position,
+            } => write!(
+                f,
+                "unexpected character {character:?} at {position} in path data"
+            ),
+            Self::ExpectedNumber(position) => {
+                write!(f, "expected a number at {position} in path data")
+            }
+            Self::ExpectedFlag(position) => {
+                write!(f, "expected a flag of 0 or 1 at {position} in path data")
+            }
+        }
+    }
+}
+
+impl Error for ParsePathError {}
+
+struct Parser<'a> {
+    data: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.data.as_bytes().get(self.position).copied()
+    }
+
+    fn unexpected_character(&self) -> ParsePathError {
+        ParsePathError::UnexpectedCharacter {
+            character: self.data[self.position..]
+                .chars()
+                .next()
+                .unwrap_or_default(),
+            position: self.position,
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some(byte) = self.peek()
+            && (byte.is_ascii_whitespace() || byte == b',')
+        {
+            self.position += 1;
+        }
+    }
+
+    fn skip_digits(&mut self) -> usize {
+        let start = self.position;
+        while self.peek().is_some_and(|byte| byte.is_ascii_digit()) {
+            self.position += 1;
+        }
+        self.position - start
+    }
+
+    /// Reads a number, which may directly follow the previous one as in `"1.5.5"` or `"1-2"`.
+    fn number(&mut self) -> Result<f32, ParsePathError> {
+        self.skip_separators();
+        let start = self.position;
+
+        if matches!(self.peek(), Some(b'+' | b'-')) {
+            self.position += 1;
+        }
+        let mut digits = self.skip_digits();
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            digits += self.skip_digits();
+        }
+        if digits == 0 {
+            self.position = start;
+            return Err(ParsePathError::ExpectedNumber(start));
+        }
+
+        // An exponent only counts if digits follow, `e` alone is not a command either way
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            let mantissa_end = self.position;
+            self.position += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.position += 1;
+            }
+            if self.skip_digits() == 0 {
+                self.position = mantissa_end;
+            }
+        }
+
+        self.data[start..self.position]
+            .parse()
+            .map_err(|_| ParsePathError::ExpectedNumber(start))
+    }
+
+    fn point(&mut self) -> Result<Vector2<f32>, ParsePathError> {
+        Ok(Vector2::new(self.number()?, self.number()?))
+    }
+
+    /// Reads an arc flag, which needs no separator before the next value as in `"a1 1 0 01 1 1"`.
+    fn flag(&mut self) -> Result<bool, ParsePathError> {
+        self.skip_separators();
+        let flag = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(ParsePathError::ExpectedFlag(self.position)),
+        };
+        self.position += 1;
+        Ok(flag)
+    }
+}
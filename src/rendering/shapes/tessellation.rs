@@ -0,0 +1,106 @@
+use std::f32::consts::TAU;
+
+use crate::{math::Vector2, rendering::index::Index};
+
+/// Largest number of segments a full curve is split into.
+pub(super) const MAX_SEGMENTS: u32 = 1024;
+
+/// How finely curved shapes are split into straight segments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tessellation {
+    /// The straight segments are never further than this many pixels from the real curve.
+    /// The segment count adapts to the on-screen size, so it changes with the camera zoom.
+    /// A tolerance that isn't positive, or is NaN, can't be met and gives the most segments.
+    Tolerance(f32),
+    /// A fixed number of segments for a full turn, arcs use a part of them.
+    Segments(u32),
+}
+
+impl Default for Tessellation {
+    fn default() -> Self {
+        Tessellation::Tolerance(0.25)
+    }
+}
+
+impl Tessellation {
+    /// Number of segments needed for an arc of `radius` world units spanning `sweep` radians,
+    /// with `pixels_per_unit` pixels per world unit on screen. Always at least one.
+    /// Sweeps past a full turn get the segments of one turn, so callers clamp them.
+    pub(super) fn segments(self, radius: f32, sweep: f32, pixels_per_unit: f32) -> u32 {
+        let turns = (sweep.abs() / TAU).min(1.0);
+        let full_turn = match self {
+            Tessellation::Tolerance(tolerance) if tolerance.is_nan() || tolerance <= 0.0 => {
+                MAX_SEGMENTS
+            }
+            Tessellation::Tolerance(tolerance) => {
+                let radius = radius.abs() * pixels_per_unit;
+                if radius <= tolerance {
+                    3
+                } else {
+                    // Each segment leaves at most `tolerance` pixels between its middle and the curve
+                    let angle = 2.0 * (1.0 - tolerance / radius).acos();
+                    (TAU / angle).ceil() as u32
+                }
+            }
+            Tessellation::Segments(segments) => segments,
+        };
+
+        ((full_turn.clamp(3, MAX_SEGMENTS) as f32 * turns).ceil() as u32).max(1)
+    }
+}
+
+/// Point at `angle` radians on an ellipse, counted counter-clockwise from its first axis.
+/// `rotation` turns the axes of the ellipse counter-clockwise.
+pub(super) fn ellipse_point(
+    center: Vector2<f32>,
+    radii: Vector2<f32>,
+    rotation: f32,
+    angle: f32,
+) -> Vector2<f32> {
+    let (sin, cos) = angle.sin_cos();
+    let (rotation_sin, rotation_cos) = rotation.sin_cos();
+    let (x, y) = (radii.x * cos, radii.y * sin);
+
+    center
+        + Vector2::new(
+            x * rotation_cos - y * rotation_sin,
+            x * rotation_sin + y * rotation_cos,
+        )
+}
+
+/// Angles of the `segments + 1` points splitting an arc into equal segments.
+pub(super) fn arc_angles(start: f32, sweep: f32, segments: u32) -> impl Iterator<Item = f32> {
+    (0..=segments).map(move |i| start + sweep * i as f32 / segments as f32)
+}
+
+/// Indices of a triangle fan from vertex 0 through the `segments + 1` vertices after it.
+/// The triangles are counter-clockwise when the vertices go around counter-clockwise,
+/// otherwise they are flipped so they are not culled.
+pub(super) fn fan_indices(segments: u32, counter_clockwise: bool) -> Vec<Index> {
+    (1..=segments as Index)
+        .flat_map(|i| oriented([0, i, i + 1], counter_clockwise))
+        .collect()
+}
+
+/// Indices of a band between two rows of `segments + 1` vertices,
+/// interleaved as outer and inner vertex of each row.
+pub(super) fn band_indices(segments: u32, counter_clockwise: bool) -> Vec<Index> {
+    (0..segments as Index)
+        .flat_map(|i| {
+            let [outer, inner, next_outer, next_inner] = [0, 1, 2, 3].map(|offset| i * 2 + offset);
+            [
+                oriented([inner, outer, next_outer], counter_clockwise),
+                oriented([inner, next_outer, next_inner], counter_clockwise),
+            ]
+        })
+        .flatten()
+        .collect()
+}
+
+fn oriented([a, b, c]: [Index; 3], counter_clockwise: bool) -> [Index; 3] {
+    if counter_clockwise {
+        [a, b, c]
+    } else {
+        [a, c, b]
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{
+    math::Vector2,
+    rendering::{
+        color::Color, font::GlyphAtlas, index::Index, shapes::transform::Transform2D,
+        vertex::Vertex,
+    },
+};
+
+/// Lines of text drawn from the glyphs of a `GlyphAtlas`.
+#[derive(Debug, Clone)]
+pub struct Text {
+    pub text: String,
+    pub font: GlyphAtlas,
+    /// Top of the first line, at its left edge, center or right edge depending on `align`.
+    pub position: Vector2<f32>,
+    /// Height of a line from the lowest descender to the highest ascender, in world units.
+    pub size: f32,
+    pub color: Color,
+    pub align: TextAlign,
+    /// Lines wider than this many world units are wrapped at spaces, or between the
+    /// characters of words that do not fit on a line of their own.
+    pub max_width: Option<f32>,
+    /// Placement in the world, rotating and scaling around its center by default.
+    pub transform: Transform2D,
+}
+
+/// Where the lines of a `Text` are placed relative to its position.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl Text {
+    /// Creates white, left aligned text with one world unit per pixel of the atlas.
+    pub fn new(text: impl Into<String>, font: GlyphAtlas, position: Vector2<f32>) -> Self {
+        Text {
+            text: text.into(),
+            size: font.size(),
+            font,
+            position,
+            color: Color::WHITE,
+            align: TextAlign::default(),
+            max_width: None,
+            transform: Transform2D::default(),
+        }
+    }
+
+    /// Replaces the transform, which is applied when the text is drawn.
+    pub fn with_transform(self, transform: Transform2D) -> Self {
+        Text { transform, ..self }
+    }
+
+    pub fn with_size(self, size: f32) -> Self {
+        Text { size, ..self }
+    }
+
+    pub fn with_color(self, color: Color) -> Self {
+        Text { color, ..self }
+    }
+
+    pub fn with_align(self, align: TextAlign) -> Self {
+        Text { align, ..self }
+    }
+
+    pub fn with_max_width(self, max_width: f32) -> Self {
+        Text {
+            max_width: Some(max_width),
+            ..self
+        }
+    }
+
+    /// The text split into the lines it is drawn as, after wrapping.
+    pub fn lines(&self) -> Vec<String> {
+        let max_width = self.max_width.map(|width| width / self.scale());
+        let mut lines = Vec::new();
+        for paragraph in self.text.split('\n') {
+            let Some(max_width) = max_width else {
+                lines.push(paragraph.to_owned());
+                continue;
+            };
+
+            let mut line = String::new();
+            for word in paragraph.split(' ') {
+                let joined = if line.is_empty() {
+                    word.to_owned()
+                } else {
+                    format!("{line} {word}")
+                };
+                if self.advance(&joined) <= max_width {
+                    line = joined;
+                    continue;
+                }
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                // Break words that are too wide on their own between characters
+                for character in word.chars() {
+                    line.push(character);
+                    if line.chars().count() > 1 && self.advance(&line) > max_width {
+                        line.pop();
+                        lines.push(std::mem::replace(&mut line, character.to_string()));
+                    }
+                }
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Width of the widest line and height of all lines, in world units.
+    pub fn drawn_size(&self) -> Vector2<f32> {
+        let lines = self.lines();
+        let width = lines
+            .iter()
+            .map(|line| self.advance(line))
+            .fold(0.0, f32::max);
+        let height = self.font.ascent() - self.font.descent()
+            + (lines.len() - 1) as f32 * self.font.line_height();
+        Vector2::new(width, height) * self.scale()
+    }
+
+    /// Middle of the box the lines are drawn in.
+    pub(super) fn center(&self) -> Vector2<f32> {
+        let size = self.drawn_size();
+        let left = match self.align {
+            TextAlign::Left => self.position.x,
+            TextAlign::Center => self.position.x - size.x * 0.5,
+            TextAlign::Right => self.position.x - size.x,
+        };
+        // The lines go down from the position
+        Vector2::new(left + size.x * 0.5, self.position.y - size.y * 0.5)
+    }
+
+    pub(super) fn tessellate(&self) -> (Vec<Vertex>, Vec<Index>) {
+        let scale = self.scale();
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        for (row, line) in self.lines().iter().enumerate() {
+            let width = self.advance(line) * scale;
+            let mut pen_x = match self.align {
+                TextAlign::Left => self.position.x,
+                TextAlign::Center => self.position.x - width * 0.5,
+                TextAlign::Right => self.position.x - width,
+            };
+            let baseline = self.position.y
+                - (self.font.ascent() + row as f32 * self.font.line_height()) * scale;
+
+            let mut previous = None;
+            for character in line.chars() {
+                let Some(glyph) = self.font.glyph(character) else {
+                    continue;
+                };
+                if let Some(previous) = previous {
+                    pen_x += self.font.kerning(previous, character) * scale;
+                }
+                previous = Some(character);
+
+                if let Some(region) = glyph.region {
+                    let (min_uv, max_uv) = self.font.texture().uv_bounds(region);
+                    let left = pen_x + glyph.offset.x * scale;
+                    let right = left + region.width as f32 * scale;
+                    // The offset points down from the baseline
+                    let top = baseline - glyph.offset.y * scale;
+                    let bottom = top - region.height as f32 * scale;
+
+                    let vertex = |x: f32, y: f32, uv: [f32; 2]| Vertex {
+                        position: [x, y],
+                        color: self.color.to_array(),
+                        uv,
+                    };
+                    let start = vertices.len() as Index;
+                    vertices.extend([
+                        vertex(left, top, min_uv),
+                        vertex(left, bottom, [min_uv[0], max_uv[1]]),
+                        vertex(right, bottom, max_uv),
+                        vertex(right, top, [max_uv[0], min_uv[1]]),
+                    ]);
+                    indices.extend([0, 1, 2, 0, 2, 3].map(|index| start + index));
+                }
+                pen_x += glyph.advance * scale;
+            }
+        }
+        (vertices, indices)
+    }
+
+    /// World units per pixel of the atlas.
+    fn scale(&self) -> f32 {
+        self.size / self.font.size()
+    }
+
+    /// Width of `line` in pixels of the atlas, from the start of the first glyph to the pen
+    /// position after the last one.
+    fn advance(&self, line: &str) -> f32 {
+        let mut advance = 0.0;
+        let mut previous = None;
+        for character in line.chars() {
+            let Some(glyph) = self.font.glyph(character) else {
+                continue;
+            };
+            if let Some(previous) = previous {
+                advance += self.font.kerning(previous, character);
+            }
+            advance += glyph.advance;
+            previous = Some(character);
+        }
+        advance
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{
+    math::{Affine2, Vector2},
+    rendering::{index::Index, vertex::Vertex},
+};
+
+/// Places a shape in the world: scales and rotates it around its pivot, then moves it
+/// by `position`. The points of the shape itself stay unchanged, so changing the transform
+/// every frame is enough to make a shape spin or pulse.
+///
+/// ```
+/// # use stellar_engine::{math::Vector2, rendering::shapes::Transform2D};
+/// let transform = Transform2D::default().with_rotation(std::f32::consts::PI);
+/// // Without a pivot of its own, the shape turns around its center
+/// let center = Vector2::new(10.0, 0.0);
+/// let turned = transform.to_affine(center) * Vector2::new(0.0, 0.0);
+/// assert!(turned.approx_eq(Vector2::new(20.0, 0.0), 1e-5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform2D {
+    /// Offset added after rotating and scaling.
+    pub position: Vector2<f32>,
+    /// Counter-clockwise rotation around the pivot in radians.
+    pub rotation: f32,
+    /// Scale around the pivot along the x and y axes of the shape.
+    /// Negative values mirror the shape.
+    pub scale: Vector2<f32>,
+    /// Point, in the coordinates of the shape, that rotating and scaling keep in place.
+    /// `None` follows the center of the shape as it moves.
+    pub pivot: Option<Vector2<f32>>,
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self {
+            position: Vector2::default(),
+            rotation: 0.0,
+            scale: Vector2::new(1.0, 1.0),
+            pivot: None,
+        }
+    }
+}
+
+impl Transform2D {
+    pub fn with_position(self, position: Vector2<f32>) -> Self {
+        Self { position, ..self }
+    }
+
+    pub fn with_rotation(self, rotation: f32) -> Self {
+        Self { rotation, ..self }
+    }
+
+    pub fn with_scale(self, scale: Vector2<f32>) -> Self {
+        Self { scale, ..self }
+    }
+
+    /// Rotates and scales around `pivot` instead of the center of the shape.
+    pub fn with_pivot(self, pivot: Vector2<f32>) -> Self {
+        Self {
+            pivot: Some(pivot),
+            ..self
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.position == Vector2::default()
+            && self.rotation == 0.0
+            && self.scale == Vector2::new(1.0, 1.0)
+    }
+
+    /// The transform as a matrix, mapping points of the shape to world positions.
+    /// `center` is the pivot when the transform has none.
+    pub fn to_affine(&self, center: Vector2<f32>) -> Affine2<f32> {
+        let pivot = self.pivot.unwrap_or(center);
+        Affine2::from_scale_angle_translation(self.scale, self.rotation, self.position + pivot)
+            * Affine2::from_translation(-pivot)
+    }
+
+    /// Factor the size of the shape on screen grows by, used to tessellate curves finely enough.
+    pub(super) fn max_scale(&self) -> f32 {
+        self.scale.x.abs().max(self.scale.y.abs())
+    }
+
+    /// Moves tessellated vertices of a shape with the given `center` into place.
+    /// Mirroring turns triangles into back faces, so their winding is reversed to keep
+    /// them visible.
+    pub(super) fn apply_to_mesh(
+        &self,
+        center: Vector2<f32>,
+        vertices: &mut [Vertex],
+        indices: &mut [Index],
+    ) {
+        if self.is_identity() {
+            return;
+        }
+
+        let affine = self.to_affine(center);
+        for vertex in vertices {
+            let [x, y] = vertex.position;
+            vertex.position = affine.transform_point(Vector2::new(x, y)).into();
+        }
+        if affine.determinant() < 0.0 {
+            for triangle in indices.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+    }
+}
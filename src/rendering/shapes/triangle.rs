@@ -1,21 +1,73 @@
 use crate::{
     math::Vector2,
-    rendering::{index::Index, shapes::point_to_vertex, vertex::Vertex},
+    rendering::{
+        color::Color,
+        index::Index,
+        shapes::{
+            DEFAULT_COLOR, point_to_vertex, polyline::Polyline, stroke::Stroke,
+            transform::Transform2D,
+        },
+        vertex::Vertex,
+    },
 };
 
 #[derive(Debug, Clone)]
 pub struct Triangle {
     pub points: [Vector2<f32>; 3],
+    /// Color of each point. The colors are blended across the triangle.
+    pub colors: [Color; 3],
+    /// Placement in the world, rotating and scaling around the center of its points by default.
+    pub transform: Transform2D,
 }
 
 impl Triangle {
-    pub(super) fn get_vertices(&self, screen_width: f32, screen_height: f32) -> [Vertex; 3] {
-        let color = [1.0, 0.2, 0.0];
+    /// Creates a triangle filled with `DEFAULT_COLOR`.
+    /// The points should be in counter-clockwise order, otherwise the triangle is culled.
+    pub fn new(points: [Vector2<f32>; 3]) -> Self {
+        Triangle {
+            points,
+            colors: [DEFAULT_COLOR; 3],
+            transform: Transform2D::default(),
+        }
+    }
+
+    /// Replaces the transform, which is applied when the triangle is drawn.
+    pub fn with_transform(self, transform: Transform2D) -> Self {
+        Triangle { transform, ..self }
+    }
+
+    /// Rotates the triangle counter-clockwise by `angle` radians around its pivot,
+    /// adding to the rotation of its transform.
+    pub fn rotated(self, angle: f32) -> Self {
+        let transform = self
+            .transform
+            .with_rotation(self.transform.rotation + angle);
+        self.with_transform(transform)
+    }
+
+    /// Fills the whole triangle with `color`.
+    pub fn with_color(self, color: Color) -> Self {
+        self.with_vertex_colors([color; 3])
+    }
+
+    /// Sets the color of each point, creating a gradient.
+    pub fn with_vertex_colors(self, colors: [Color; 3]) -> Self {
+        Triangle { colors, ..self }
+    }
+
+    /// The closed outline of the triangle, without its inside.
+    pub fn outline(&self, stroke: Stroke) -> Polyline {
+        Polyline::new(self.points.to_vec())
+            .closed()
+            .with_stroke(stroke)
+            .with_transform(self.transform)
+    }
 
+    pub(super) fn get_vertices(&self) -> [Vertex; 3] {
         [
-            point_to_vertex(self.points[0], screen_width, screen_height, color),
-            point_to_vertex(self.points[1], screen_width, screen_height, color),
-            point_to_vertex(self.points[2], screen_width, screen_height, color),
+            point_to_vertex(self.points[0], self.colors[0]),
+            point_to_vertex(self.points[1], self.colors[1]),
+            point_to_vertex(self.points[2], self.colors[2]),
         ]
     }
 
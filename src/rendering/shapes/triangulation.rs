@@ -0,0 +1,210 @@
+use std::cmp::Ordering;
+
+use crate::{math::Vector2, rendering::index::Index};
+
+/// Splits a simple polygon with optional holes into counter-clockwise triangles by ear clipping.
+///
+/// The returned indices refer to `outline` followed by all points of `holes`, in order.
+/// The outline and the holes may be in either winding order.
+/// Self-intersecting input does not panic, but parts of it may be missing.
+pub(super) fn triangulate(outline: &[Vector2<f32>], holes: &[Vec<Vector2<f32>>]) -> Vec<Index> {
+    if outline.len() < 3 {
+        return Vec::new();
+    }
+
+    let mut points = outline.to_vec();
+    let mut polygon: Vec<usize> = (0..outline.len()).collect();
+    if signed_area(&points, &polygon) < 0.0 {
+        polygon.reverse();
+    }
+
+    let mut hole_polygons = Vec::new();
+    for hole in holes {
+        let start = points.len();
+        points.extend_from_slice(hole);
+        if hole.len() < 3 {
+            continue;
+        }
+
+        // Holes go around clockwise, so the merged polygon keeps the inside on its left
+        let mut hole_polygon: Vec<usize> = (start..points.len()).collect();
+        if signed_area(&points, &hole_polygon) > 0.0 {
+            hole_polygon.reverse();
+        }
+        hole_polygons.push(hole_polygon);
+    }
+
+    // Holes further right are merged first, so the bridges of later holes can't cross them
+    hole_polygons.sort_by(|a, b| {
+        let max_x = |hole: &[usize]| rightmost(&points, hole).1;
+        max_x(b).partial_cmp(&max_x(a)).unwrap_or(Ordering::Equal)
+    });
+    for hole in hole_polygons {
+        merge_hole(&points, &mut polygon, &hole);
+    }
+
+    clip_ears(&points, polygon)
+}
+
+/// Twice the signed area of the polygon, positive when it goes around counter-clockwise.
+fn signed_area(points: &[Vector2<f32>], polygon: &[usize]) -> f32 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&a, &b)| points[a].x * points[b].y - points[b].x * points[a].y)
+        .sum()
+}
+
+/// Positive when `a`, `b`, `c` turn counter-clockwise.
+fn cross(a: Vector2<f32>, b: Vector2<f32>, c: Vector2<f32>) -> f32 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Whether `p` is inside of the counter-clockwise triangle `a`, `b`, `c` or on its edges.
+fn in_triangle(p: Vector2<f32>, a: Vector2<f32>, b: Vector2<f32>, c: Vector2<f32>) -> bool {
+    cross(a, b, p) >= 0.0 && cross(b, c, p) >= 0.0 && cross(c, a, p) >= 0.0
+}
+
+/// Position in `polygon` and x of its rightmost point.
+fn rightmost(points: &[Vector2<f32>], polygon: &[usize]) -> (usize, f32) {
+    polygon
+        .iter()
+        .enumerate()
+        .map(|(position, &index)| (position, points[index].x))
+        .fold((0, f32::NEG_INFINITY), |best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+}
+
+/// Connects the hole to a visible point of the polygon with a bridge of two edges,
+/// which turns both into one polygon.
+fn merge_hole(points: &[Vector2<f32>], polygon: &mut Vec<usize>, hole: &[usize]) {
+    let (hole_position, _) = rightmost(points, hole);
+    let m = points[hole[hole_position]];
+
+    // Find the closest edge hit by a ray going from `m` to the right
+    let mut hit: Option<(f32, usize)> = None;
+    for position in 0..polygon.len() {
+        let a = points[polygon[position]];
+        let b = points[polygon[(position + 1) % polygon.len()]];
+        // Only edges going up can be seen from the inside, looking to the right
+        if !(a.y <= m.y && m.y <= b.y && a.y < b.y) {
+            continue;
+        }
+        let x = a.x + (m.y - a.y) / (b.y - a.y) * (b.x - a.x);
+        if x >= m.x && hit.is_none_or(|(closest, _)| x < closest) {
+            // Of the two endpoints, the one further right is the candidate for the bridge
+            let candidate = if a.x > b.x {
+                position
+            } else {
+                (position + 1) % polygon.len()
+            };
+            hit = Some((x, candidate));
+        }
+    }
+    let Some((hit_x, mut bridge)) = hit else {
+        // The hole is not inside of the polygon
+        return;
+    };
+
+    // Reflex points inside of the triangle between `m`, the hit and the candidate
+    // block the view, the one closest in angle to the ray is visible instead
+    let intersection = Vector2::new(hit_x, m.y);
+    let candidate = points[polygon[bridge]];
+    let (a, b) = if candidate.y < m.y {
+        (candidate, intersection)
+    } else {
+        (intersection, candidate)
+    };
+    let mut best_angle = f32::INFINITY;
+    let mut best_distance = f32::INFINITY;
+    for position in 0..polygon.len() {
+        let previous = points[polygon[(position + polygon.len() - 1) % polygon.len()]];
+        let point = points[polygon[position]];
+        let next = points[polygon[(position + 1) % polygon.len()]];
+        if point == candidate || cross(previous, point, next) > 0.0 || !in_triangle(point, m, a, b)
+        {
+            continue;
+        }
+
+        let offset = point - m;
+        let angle = offset.y.abs().atan2(offset.x);
+        let distance = offset.length_squared();
+        if angle < best_angle || (angle == best_angle && distance < best_distance) {
+            best_angle = angle;
+            best_distance = distance;
+            bridge = position;
+        }
+    }
+
+    // polygon[..=bridge], the hole from `m` around back to `m`, then the bridge point again
+    let bridge_point = polygon[bridge];
+    let hole_loop = (0..=hole.len()).map(|offset| hole[(hole_position + offset) % hole.len()]);
+    let inserted: Vec<usize> = hole_loop.chain(std::iter::once(bridge_point)).collect();
+    polygon.splice(bridge + 1..bridge + 1, inserted);
+}
+
+/// Cuts off triangles at convex corners that contain no other corner, until none are left.
+fn clip_ears(points: &[Vector2<f32>], mut polygon: Vec<usize>) -> Vec<Index> {
+    let mut indices = Vec::with_capacity(polygon.len().saturating_sub(2) * 3);
+    let corner = |polygon: &[usize], position: usize| {
+        let len = polygon.len();
+        [
+            polygon[(position + len - 1) % len],
+            polygon[position],
+            polygon[(position + 1) % len],
+        ]
+    };
+
+    while polygon.len() > 3 {
+        let ear = (0..polygon.len()).find(|&position| {
+            let [a, b, c] = corner(&polygon, position).map(|index| points[index]);
+            cross(a, b, c) > 0.0
+                && (0..polygon.len()).all(|other| {
+                    let [previous, point, next] =
+                        corner(&polygon, other).map(|index| points[index]);
+                    // Only reflex corners can be inside of an ear. Corners on the ear itself,
+                    // like the duplicated ends of bridges, don't count.
+                    cross(previous, point, next) > 0.0
+                        || point == a
+                        || point == b
+                        || point == c
+                        || !in_triangle(point, a, b, c)
+                })
+        });
+
+        let position = ear.unwrap_or_else(|| {
+            // Without an ear the rest is degenerate or self-intersecting,
+            // drop its flattest corner to make progress
+            (0..polygon.len())
+                .min_by(|&first, &second| {
+                    let flatness = |position| {
+                        let [a, b, c] = corner(&polygon, position).map(|index| points[index]);
+                        cross(a, b, c).abs()
+                    };
+                    flatness(first)
+                        .partial_cmp(&flatness(second))
+                        .unwrap_or(Ordering::Equal)
+                })
+                .unwrap_or(0)
+        });
+
+        let triangle = corner(&polygon, position);
+        let [a, b, c] = triangle.map(|index| points[index]);
+        if cross(a, b, c) > 0.0 {
+            indices.extend(triangle.map(|index| index as Index));
+        }
+        polygon.remove(position);
+    }
+
+    let [a, b, c] = [polygon[0], polygon[1], polygon[2]];
+    if cross(points[a], points[b], points[c]) > 0.0 {
+        indices.extend([a, b, c].map(|index| index as Index));
+    }
+
+    indices
+}
@@ -0,0 +1,248 @@
+use std::convert::Infallible;
+
+use crate::{
+    math::Vector2,
+    rendering::{
+        backend::RenderBackend,
+        background::Background,
+        blend::BlendMode,
+        camera::Camera2D,
+        color::linear_to_srgb,
+        queue::{RenderBuffers, RenderQueue},
+        texture::Texture,
+        vertex::Vertex,
+    },
+};
+
+/// A renderer that rasterizes the triangles on the CPU.
+///
+/// It follows the same rules as the wgpu pipeline of `RendererState`:
+/// pixels are sampled at their centers, edges are resolved with the top-left rule,
+/// counter-clockwise triangles are front facing and back facing triangles are culled.
+/// Textures are filtered like the GPU samplers and clamped to their edges.
+/// This makes it usable on machines without working GPU drivers and in tests.
+pub struct SoftwareRenderer {
+    pub render_queue: RenderQueue,
+    pub background: Background,
+    /// Camera used to draw the shapes of `render_queue`.
+    /// Its viewport is updated by `SoftwareRenderer::resize`.
+    pub camera: Camera2D,
+
+    width: u32,
+    height: u32,
+    /// Linear RGBA colors, row by row from the top-left corner.
+    framebuffer: Vec<[f32; 4]>,
+}
+
+impl SoftwareRenderer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            render_queue: RenderQueue::default(),
+            background: Background::default(),
+            camera: Camera2D::new(width as f32, height as f32),
+
+            width,
+            height,
+            framebuffer: vec![[0.0; 4]; (width * height) as usize],
+        }
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.camera.viewport = Vector2::new(width as f32, height as f32);
+        self.width = width;
+        self.height = height;
+        self.framebuffer = vec![[0.0; 4]; (width * height) as usize];
+    }
+
+    pub fn render(&mut self) {
+        let mut queue = std::mem::take(&mut self.render_queue);
+        let Ok(()) = self.draw_queue(&mut queue);
+        self.render_queue = queue;
+    }
+
+    /// Color of the pixel at `x`, `y` (from the top-left corner) in the same
+    /// sRGB RGBA format as `RendererState::read_pixels` returns.
+    ///
+    /// # Panics
+    /// Panics if the position is outside of the framebuffer.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        assert!(
+            x < self.width && y < self.height,
+            "pixel ({x}, {y}) is outside of the {}x{} framebuffer",
+            self.width,
+            self.height
+        );
+
+        encode_pixel(self.framebuffer[(y * self.width + x) as usize])
+    }
+
+    /// Returns the last rendered frame row by row from the top-left corner, 4 bytes (RGBA) per pixel.
+    pub fn read_pixels(&self) -> Vec<u8> {
+        self.framebuffer
+            .iter()
+            .flat_map(|&color| encode_pixel(color))
+            .collect()
+    }
+
+    /// Fills a triangle whose vertices are already in clip space.
+    /// The vertex colors are multiplied with `texture`, if there is one.
+    fn fill_triangle(
+        &mut self,
+        [a, b, c]: [&Vertex; 3],
+        blend_mode: BlendMode,
+        texture: Option<&Texture>,
+    ) {
+        // Snap to a fixed-point grid like GPUs do, so that triangles sharing an edge
+        // never leave gaps or overlap
+        let to_framebuffer = |vertex: &Vertex| {
+            let x = (vertex.position[0] + 1.0) * 0.5 * self.width as f32;
+            let y = (1.0 - vertex.position[1]) * 0.5 * self.height as f32;
+            [to_fixed(x), to_fixed(y)]
+        };
+        let (pa, mut pb, mut pc) = (to_framebuffer(a), to_framebuffer(b), to_framebuffer(c));
+        let (mut b, mut c) = (b, c);
+
+        // The y axis points down in the framebuffer, so counter-clockwise triangles
+        // have a negative area here. Those are the front facing ones.
+        let area = edge(pa, pb, pc);
+        if area >= 0 {
+            return;
+        }
+        // Reorder to clockwise so that all edge functions are positive inside the triangle
+        std::mem::swap(&mut pb, &mut pc);
+        std::mem::swap(&mut b, &mut c);
+        let area = -area as f32;
+
+        let to_pixel = |fixed: i64, max: u32| (fixed >> SUBPIXEL_BITS).clamp(0, max as i64) as u32;
+        let min_x = to_pixel(pa[0].min(pb[0]).min(pc[0]), self.width);
+        let min_y = to_pixel(pa[1].min(pb[1]).min(pc[1]), self.height);
+        let max_x = to_pixel(pa[0].max(pb[0]).max(pc[0]) + SUBPIXEL_ONE, self.width);
+        let max_y = to_pixel(pa[1].max(pb[1]).max(pc[1]) + SUBPIXEL_ONE, self.height);
+
+        let bias_bc = is_top_left(pb, pc);
+        let bias_ca = is_top_left(pc, pa);
+        let bias_ab = is_top_left(pa, pb);
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = [
+                    ((x as i64) << SUBPIXEL_BITS) + SUBPIXEL_ONE / 2,
+                    ((y as i64) << SUBPIXEL_BITS) + SUBPIXEL_ONE / 2,
+                ];
+                let wa = edge(pb, pc, p);
+                let wb = edge(pc, pa, p);
+                let wc = edge(pa, pb, p);
+
+                if !covers(wa, bias_bc) || !covers(wb, bias_ca) || !covers(wc, bias_ab) {
+                    continue;
+                }
+
+                let (wa, wb, wc) = (wa as f32 / area, wb as f32 / area, wc as f32 / area);
+                let mut color: [f32; 4] = std::array::from_fn(|channel| {
+                    a.color[channel] * wa + b.color[channel] * wb + c.color[channel] * wc
+                });
+                if let Some(texture) = texture {
+                    let uv = std::array::from_fn(|axis| {
+                        a.uv[axis] * wa + b.uv[axis] * wb + c.uv[axis] * wc
+                    });
+                    let texel = texture.sample(uv);
+                    color = std::array::from_fn(|channel| color[channel] * texel[channel]);
+                }
+
+                let pixel = &mut self.framebuffer[(y * self.width + x) as usize];
+                *pixel = blend_mode.blend(color, *pixel);
+            }
+        }
+    }
+}
+
+impl RenderBackend for SoftwareRenderer {
+    type Error = Infallible;
+
+    fn target_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn pixels_per_unit(&self) -> f32 {
+        self.camera.pixels_per_unit()
+    }
+
+    fn draw(&mut self, buffers: &RenderBuffers) -> Result<(), Self::Error> {
+        if let Some(color) = self.background.clear_color() {
+            self.framebuffer.fill(color.to_array());
+        }
+        if let Some((vertices, indices)) = self
+            .background
+            .geometry(self.width as f32, self.height as f32)
+        {
+            for triangle in indices.chunks_exact(3) {
+                let [a, b, c] =
+                    [triangle[0], triangle[1], triangle[2]].map(|i| vertices[i as usize]);
+                self.fill_triangle([&a, &b, &c], BlendMode::Alpha, None);
+            }
+        }
+
+        let vertices: Vec<Vertex> = buffers
+            .vertices()
+            .iter()
+            .map(|vertex| {
+                let [x, y] = vertex.position;
+                let clip = self.camera.world_to_clip(Vector2::new(x, y));
+                Vertex {
+                    position: [clip.x, clip.y],
+                    ..*vertex
+                }
+            })
+            .collect();
+        for batch in buffers.batches() {
+            let indices =
+                &buffers.indices()[batch.indices.start as usize..batch.indices.end as usize];
+            for triangle in indices.chunks_exact(3) {
+                let [a, b, c] =
+                    [triangle[0], triangle[1], triangle[2]].map(|i| vertices[i as usize]);
+                self.fill_triangle([&a, &b, &c], batch.blend_mode, batch.texture.as_ref());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Number of fractional bits of the fixed-point framebuffer coordinates.
+const SUBPIXEL_BITS: u32 = 8;
+const SUBPIXEL_ONE: i64 = 1 << SUBPIXEL_BITS;
+
+fn to_fixed(value: f32) -> i64 {
+    (value * SUBPIXEL_ONE as f32).round() as i64
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `p`, in fixed-point units.
+/// Positive when `p` lies to the right of the edge `a` -> `b` in framebuffer coordinates.
+/// Computed in `i128` so that coordinates far outside of the framebuffer cannot overflow.
+fn edge(a: [i64; 2], b: [i64; 2], p: [i64; 2]) -> i128 {
+    let [ax, ay, bx, by, px, py] = [a[0], a[1], b[0], b[1], p[0], p[1]].map(i128::from);
+    (bx - ax) * (py - ay) - (by - ay) * (px - ax)
+}
+
+/// Whether the edge `a` -> `b` of a clockwise triangle is a top or a left edge.
+/// Pixel centers lying exactly on such edges are covered, others are not.
+fn is_top_left(a: [i64; 2], b: [i64; 2]) -> bool {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    (dy == 0 && dx > 0) || dy < 0
+}
+
+fn covers(weight: i128, is_top_left: bool) -> bool {
+    weight > 0 || (weight == 0 && is_top_left)
+}
+
+/// Converts a linear color to 8-bit sRGB, like writing into an sRGB texture does.
+fn encode_pixel(color: [f32; 4]) -> [u8; 4] {
+    let to_u8 = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    [
+        to_u8(linear_to_srgb(color[0])),
+        to_u8(linear_to_srgb(color[1])),
+        to_u8(linear_to_srgb(color[2])),
+        to_u8(color[3]),
+    ]
+}
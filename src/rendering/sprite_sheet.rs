@@ -0,0 +1,344 @@
+use std::{error::Error, fmt, fs, io, path::Path, time::Duration};
+
+use serde::Deserialize;
+
+use crate::{
+    animation::{Animation, Clip, PlayMode},
+    math::Vector2,
+    rendering::{
+        shapes::Sprite,
+        texture::{Texture, TextureError, TextureRegion},
+    },
+};
+
+/// Frames of a texture described by a JSON file, as exported by Aseprite or TexturePacker.
+///
+/// Both the hash and the array layout of the `frames` are read. Frames rotated by
+/// TexturePacker are not supported, the sheet must be exported without rotation.
+#[derive(Debug, Clone)]
+pub struct SpriteSheet {
+    pub texture: Texture,
+    /// The frames in the order of the file.
+    pub frames: Vec<SheetFrame>,
+    /// Named ranges of frames, the `frameTags` of Aseprite.
+    pub tags: Vec<FrameTag>,
+}
+
+/// One image of a `SpriteSheet`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetFrame {
+    pub name: String,
+    /// Where the frame is in the texture.
+    pub region: TextureRegion,
+    /// Size of the image before transparent borders were trimmed off.
+    pub source_size: Vector2<u32>,
+    /// Position of `region` in the untrimmed image, from its top-left corner.
+    pub offset: Vector2<u32>,
+    /// How long the frame is shown, if the file says.
+    pub duration: Option<Duration>,
+}
+
+/// A named range of frames, like one animation of a character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameTag {
+    pub name: String,
+    /// Index of the first frame.
+    pub from: usize,
+    /// Index of the last frame, included in the range.
+    pub to: usize,
+    pub direction: TagDirection,
+}
+
+/// Order in which the frames of a `FrameTag` are played.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TagDirection {
+    #[default]
+    Forward,
+    Reverse,
+    /// Forward then back.
+    PingPong,
+    /// Backward then forward.
+    PingPongReverse,
+}
+
+/// Error returned when a sprite sheet can't be loaded.
+#[derive(Debug)]
+pub enum SpriteSheetError {
+    /// The JSON file can't be read.
+    Io(io::Error),
+    /// The file is not valid JSON or misses required fields.
+    Json(serde_json::Error),
+    /// The image of the sheet can't be loaded.
+    Texture(TextureError),
+    /// The file does not name the image of the sheet.
+    MissingImage,
+    /// The frame is rotated, which is not supported.
+    RotatedFrame(String),
+    /// The frame reaches outside of the texture.
+    FrameOutOfBounds(String),
+    /// The tag refers to frames that do not exist.
+    InvalidTag(String),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonFrames {
+    Array(Vec<JsonNamedFrame>),
+    Hash(serde_json::Map<String, serde_json::Value>),
+}
+
+#[derive(Deserialize)]
+struct JsonNamedFrame {
+    filename: String,
+    #[serde(flatten)]
+    frame: JsonFrame,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonFrame {
+    frame: JsonRect,
+    #[serde(default)]
+    rotated: bool,
+    sprite_source_size: Option<JsonRect>,
+    source_size: Option<JsonSize>,
+    /// In milliseconds.
+    duration: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct JsonRect {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize)]
+struct JsonSize {
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct JsonMeta {
+    image: Option<String>,
+    #[serde(default)]
+    frame_tags: Vec<JsonTag>,
+}
+
+#[derive(Deserialize)]
+struct JsonTag {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: JsonDirection,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum JsonDirection {
+    #[default]
+    Forward,
+    Reverse,
+    Pingpong,
+    PingpongReverse,
+}
+
+#[derive(Deserialize)]
+struct JsonSheet {
+    frames: JsonFrames,
+    #[serde(default)]
+    meta: JsonMeta,
+}
+
+impl SpriteSheet {
+    /// Reads the JSON file at `path` and the image it names, relative to the JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SpriteSheetError> {
+        let path = path.as_ref();
+        let sheet: JsonSheet = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let image = sheet
+            .meta
+            .image
+            .as_deref()
+            .ok_or(SpriteSheetError::MissingImage)?;
+        let texture = Texture::load(path.with_file_name(image))?;
+
+        Self::from_parsed(sheet, texture)
+    }
+
+    /// Reads the frames from `json`, with the image of the sheet already loaded as `texture`.
+    pub fn from_json(json: &str, texture: Texture) -> Result<Self, SpriteSheetError> {
+        Self::from_parsed(serde_json::from_str(json)?, texture)
+    }
+
+    fn from_parsed(sheet: JsonSheet, texture: Texture) -> Result<Self, SpriteSheetError> {
+        let named_frames = match sheet.frames {
+            JsonFrames::Array(frames) => frames
+                .into_iter()
+                .map(|frame| (frame.filename, frame.frame))
+                .collect(),
+            JsonFrames::Hash(frames) => frames
+                .into_iter()
+                .map(|(name, frame)| Ok((name, serde_json::from_value(frame)?)))
+                .collect::<Result<Vec<_>, serde_json::Error>>()?,
+        };
+
+        let mut frames = Vec::with_capacity(named_frames.len());
+        for (name, frame) in named_frames {
+            if frame.rotated {
+                return Err(SpriteSheetError::RotatedFrame(name));
+            }
+            let JsonRect { x, y, w, h } = frame.frame;
+            if x as u64 + w as u64 > texture.width() as u64
+                || y as u64 + h as u64 > texture.height() as u64
+            {
+                return Err(SpriteSheetError::FrameOutOfBounds(name));
+            }
+
+            let offset = frame
+                .sprite_source_size
+                .map_or(Vector2::new(0, 0), |rect| Vector2::new(rect.x, rect.y));
+            let source_size = frame
+                .source_size
+                .map_or(Vector2::new(w, h), |size| Vector2::new(size.w, size.h));
+            frames.push(SheetFrame {
+                name,
+                region: TextureRegion::new(x, y, w, h),
+                source_size,
+                offset,
+                duration: frame.duration.map(Duration::from_millis),
+            });
+        }
+
+        let tags = sheet
+            .meta
+            .frame_tags
+            .into_iter()
+            .map(|tag| {
+                if tag.from > tag.to || tag.to >= frames.len() {
+                    return Err(SpriteSheetError::InvalidTag(tag.name));
+                }
+                let direction = match tag.direction {
+                    JsonDirection::Forward => TagDirection::Forward,
+                    JsonDirection::Reverse => TagDirection::Reverse,
+                    JsonDirection::Pingpong => TagDirection::PingPong,
+                    JsonDirection::PingpongReverse => TagDirection::PingPongReverse,
+                };
+                Ok(FrameTag {
+                    name: tag.name,
+                    from: tag.from,
+                    to: tag.to,
+                    direction,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            texture,
+            frames,
+            tags,
+        })
+    }
+
+    /// Index of the frame called `name`.
+    pub fn frame_index(&self, name: &str) -> Option<usize> {
+        self.frames.iter().position(|frame| frame.name == name)
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&FrameTag> {
+        self.tags.iter().find(|tag| tag.name == name)
+    }
+
+    /// An animation of frame indices with one clip per tag, played in the direction of the tag.
+    /// Frames without a duration are shown for 100 milliseconds, the default of Aseprite.
+    pub fn animation(&self) -> Animation<usize> {
+        self.tags.iter().fold(Animation::new(), |animation, tag| {
+            let clip = (tag.from..=tag.to).fold(Clip::new(&tag.name), |clip, index| {
+                let duration = self.frames[index]
+                    .duration
+                    .unwrap_or(Duration::from_millis(100));
+                clip.with_frame(index, duration)
+            });
+            animation.with_clip(match tag.direction {
+                TagDirection::Forward => clip,
+                TagDirection::Reverse => clip.reversed(),
+                TagDirection::PingPong => clip.with_mode(PlayMode::PingPong),
+                TagDirection::PingPongReverse => clip.reversed().with_mode(PlayMode::PingPong),
+            })
+        })
+    }
+
+    /// A sprite showing frame `index` with one world unit per pixel.
+    /// `center` is the center of the untrimmed image, so trimmed frames stay in place.
+    ///
+    /// # Panics
+    /// Panics if there is no frame `index`.
+    pub fn sprite(&self, index: usize, center: Vector2<f32>) -> Sprite {
+        let frame = &self.frames[index];
+        Sprite::new(self.texture.clone(), center + frame.center_offset()).with_region(frame.region)
+    }
+}
+
+impl SheetFrame {
+    /// Where the center of the region is relative to the center of the untrimmed image,
+    /// in world units with one unit per pixel.
+    pub fn center_offset(&self) -> Vector2<f32> {
+        let x =
+            self.offset.x as f32 + self.region.width as f32 * 0.5 - self.source_size.x as f32 * 0.5;
+        let y = self.offset.y as f32 + self.region.height as f32 * 0.5
+            - self.source_size.y as f32 * 0.5;
+        // The y axis points down in the image
+        Vector2::new(x, -y)
+    }
+}
+
+impl fmt::Display for SpriteSheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read sprite sheet: {error}"),
+            Self::Json(error) => write!(f, "invalid sprite sheet: {error}"),
+            Self::Texture(error) => write!(f, "failed to load sprite sheet image: {error}"),
+            Self::MissingImage => write!(f, "sprite sheet does not name its image"),
+            Self::RotatedFrame(name) => write!(f, "frame {name:?} is rotated"),
+            Self::FrameOutOfBounds(name) => {
+                write!(f, "frame {name:?} reaches outside of the image")
+            }
+            Self::InvalidTag(name) => write!(f, "tag {name:?} refers to missing frames"),
+        }
+    }
+}
+
+impl Error for SpriteSheetError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Json(error) => Some(error),
+            Self::Texture(error) => Some(error),
+            Self::MissingImage
+            | Self::RotatedFrame(_)
+            | Self::FrameOutOfBounds(_)
+            | Self::InvalidTag(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for SpriteSheetError {
+    fn from(error: io::Error) -> Self {
+        SpriteSheetError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for SpriteSheetError {
+    fn from(error: serde_json::Error) -> Self {
+        SpriteSheetError::Json(error)
+    }
+}
+
+impl From<TextureError> for SpriteSheetError {
+    fn from(error: TextureError) -> Self {
+        SpriteSheetError::Texture(error)
+    }
+}
@@ -1,30 +1,103 @@
-use std::{mem, num::NonZeroU64, sync::Arc};
+use std::{
+    collections::HashMap,
+    mem,
+    ops::Range,
+    sync::{Arc, Weak},
+};
 
+use anyhow::anyhow;
 use wgpu::util::DeviceExt;
 use winit::window::Window;
 
-use crate::rendering::{
-    index::Index,
-    queue::{RenderBuffers, RenderQueue},
-    vertex::Vertex,
+use crate::{
+    math::Vector2,
+    rendering::{
+        backend::RenderBackend,
+        background::{self, Background},
+        blend::BlendMode,
+        camera::Camera2D,
+        index::{INDEX_FORMAT, Index},
+        queue::{DrawBatch, RenderBuffers, RenderQueue},
+        texture::{Texture, TextureData, TextureFilter},
+        vertex::Vertex,
+    },
 };
 
 const INITIAL_VERTEX_BUFFER_SIZE: usize = mem::size_of::<Vertex>() * 32;
 const INITIAL_INDEX_BUFFER_SIZE: usize = mem::size_of::<Index>() * 32;
 
+/// Texture format used by headless renderers.
+/// It is an sRGB format so the output matches what a window surface would show.
+const OFFSCREEN_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// Where the frames of a `RendererState` end up.
+enum RenderTarget {
+    /// Frames are presented to the surface of a window.
+    Window {
+        surface: wgpu::Surface<'static>,
+        config: wgpu::SurfaceConfiguration,
+        is_surface_configured: bool,
+        window: Arc<Window>,
+        /// Frames are drawn into this texture and then copied to the surface when the background
+        /// is `Background::Keep`, because the surface textures are not preserved between frames.
+        /// It starts out transparent and is dropped as soon as the background changes, so frames
+        /// from before a switch to another background never come back.
+        canvas: Option<wgpu::Texture>,
+    },
+    /// Frames are drawn into a texture which can be read back with `RendererState::read_pixels`.
+    Offscreen { texture: wgpu::Texture },
+}
+
+impl RenderTarget {
+    fn size(&self) -> (u32, u32) {
+        match self {
+            RenderTarget::Window { config, .. } => (config.width, config.height),
+            RenderTarget::Offscreen { texture } => (texture.width(), texture.height()),
+        }
+    }
+}
+
+/// A `Texture` uploaded to the GPU.
+struct GpuTexture {
+    /// Binds the texture and the sampler of its filter.
+    bind_group: wgpu::BindGroup,
+    /// The GPU texture is freed once the `Texture` is dropped.
+    source: Weak<TextureData>,
+}
+
 pub struct RendererState {
     pub render_queue: RenderQueue,
+    pub background: Background,
+    /// Camera used to draw the shapes of `render_queue`.
+    /// Its viewport is updated by `RendererState::resize`.
+    pub camera: Camera2D,
 
-    surface: wgpu::Surface<'static>,
+    target: RenderTarget,
     device: wgpu::Device,
     queue: wgpu::Queue,
-    config: wgpu::SurfaceConfiguration,
-    is_surface_configured: bool,
-    render_pipeline: wgpu::RenderPipeline,
+    /// One pipeline for each `BlendMode`.
+    render_pipelines: HashMap<BlendMode, wgpu::RenderPipeline>,
     vertex_buffer: wgpu::Buffer,
     index_buffer: wgpu::Buffer,
-    indices_count: u32,
-    window: Arc<Window>,
+    /// Batches of the last uploaded buffers.
+    batches: Vec<DrawBatch>,
+    /// Revision of the `RenderBuffers` in `vertex_buffer` and `index_buffer`.
+    uploaded_revision: Option<u64>,
+    background_vertex_buffer: wgpu::Buffer,
+    background_index_buffer: wgpu::Buffer,
+    background_indices_count: u32,
+    camera_buffer: wgpu::Buffer,
+    /// Binds `camera_buffer`, used for the shapes.
+    camera_bind_group: wgpu::BindGroup,
+    /// Binds an identity matrix, used for the background which is already in clip space.
+    screen_bind_group: wgpu::BindGroup,
+    texture_bind_group_layout: wgpu::BindGroupLayout,
+    linear_sampler: wgpu::Sampler,
+    nearest_sampler: wgpu::Sampler,
+    /// Binds a white pixel, used for shapes without a texture.
+    white_bind_group: wgpu::BindGroup,
+    /// Textures drawn so far, by the id of their `Texture`.
+    textures: HashMap<u64, GpuTexture>,
 }
 
 impl RendererState {
@@ -48,15 +121,7 @@ impl RendererState {
             })
             .await?;
 
-        let (device, queue) = adapter
-            .request_device(&wgpu::DeviceDescriptor {
-                label: None,
-                required_features: wgpu::Features::empty(),
-                required_limits: wgpu::Limits::default(),
-                memory_hints: Default::default(),
-                trace: wgpu::Trace::Off,
-            })
-            .await?;
+        let (device, queue) = Self::request_device(&adapter).await?;
 
         let surface_caps = surface.get_capabilities(&adapter);
         let surface_format = surface_caps
@@ -66,7 +131,9 @@ impl RendererState {
             .copied()
             .unwrap_or(surface_caps.formats[0]);
         let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            // Copying into the surface is needed by `Background::Keep`
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | (surface_caps.usages & wgpu::TextureUsages::COPY_DST),
             format: surface_format,
             width: size.width,
             height: size.height,
@@ -76,28 +143,329 @@ impl RendererState {
             desired_maximum_frame_latency: 2,
         };
 
+        let target = RenderTarget::Window {
+            surface,
+            config,
+            is_surface_configured: false,
+            window,
+            canvas: None,
+        };
+
+        Ok(Self::with_target(device, queue, surface_format, target))
+    }
+
+    /// Creates a renderer without any window.
+    /// Frames are drawn into an offscreen texture of size `width` x `height`
+    /// and can be read back with `RendererState::read_pixels`.
+    ///
+    /// The wgpu fallback (software) adapter is used, so this works even on machines without a GPU.
+    pub async fn new_headless(width: u32, height: u32) -> anyhow::Result<Self> {
+        if width == 0 || height == 0 {
+            return Err(anyhow!(
+                "headless renderer size must be non-zero, got {width}x{height}"
+            ));
+        }
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        });
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: None,
+                force_fallback_adapter: true,
+            })
+            .await?;
+
+        let (device, queue) = Self::request_device(&adapter).await?;
+
+        let texture = Self::create_offscreen_texture(&device, width, height);
+        let target = RenderTarget::Offscreen { texture };
+
+        Ok(Self::with_target(device, queue, OFFSCREEN_FORMAT, target))
+    }
+
+    async fn request_device(
+        adapter: &wgpu::Adapter,
+    ) -> anyhow::Result<(wgpu::Device, wgpu::Queue)> {
+        let device_and_queue = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: None,
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::default(),
+                memory_hints: Default::default(),
+                trace: wgpu::Trace::Off,
+            })
+            .await?;
+
+        Ok(device_and_queue)
+    }
+
+    fn create_offscreen_texture(device: &wgpu::Device, width: u32, height: u32) -> wgpu::Texture {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Offscreen Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: OFFSCREEN_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        })
+    }
+
+    /// Creates the pipeline and buffers shared by all render targets.
+    fn with_target(
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        format: wgpu::TextureFormat,
+        target: RenderTarget,
+    ) -> Self {
+        let (width, height) = target.size();
+        let camera = Camera2D::new(width as f32, height as f32);
+
+        let camera_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Camera Bind Group Layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }],
+            });
+        let create_camera_bind_group = |label, buffer: &wgpu::Buffer| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &camera_bind_group_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                }],
+            })
+        };
+
+        let camera_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Camera Buffer"),
+            contents: bytemuck::cast_slice(&camera.view_projection()),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let camera_bind_group = create_camera_bind_group("Camera Bind Group", &camera_buffer);
+
+        let identity: [[f32; 4]; 4] = [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        let screen_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Screen Camera Buffer"),
+            contents: bytemuck::cast_slice(&identity),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let screen_bind_group = create_camera_bind_group("Screen Bind Group", &screen_buffer);
+
+        let texture_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Texture Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+            });
+        let create_sampler = |label, filter| {
+            device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some(label),
+                address_mode_u: wgpu::AddressMode::ClampToEdge,
+                address_mode_v: wgpu::AddressMode::ClampToEdge,
+                mag_filter: filter,
+                min_filter: filter,
+                ..Default::default()
+            })
+        };
+        let linear_sampler = create_sampler("Linear Sampler", wgpu::FilterMode::Linear);
+        let nearest_sampler = create_sampler("Nearest Sampler", wgpu::FilterMode::Nearest);
+        let white_bind_group = Self::create_texture_bind_group(
+            &device,
+            &queue,
+            &texture_bind_group_layout,
+            &nearest_sampler,
+            (1, 1),
+            &[255; 4],
+        );
+
         let shader = device.create_shader_module(wgpu::include_wgsl!("shaders/shader.wgsl"));
         let render_pipeline_layout =
             device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                 label: Some("Render Pipeline Layout"),
-                bind_group_layouts: &[],
+                bind_group_layouts: &[&camera_bind_group_layout, &texture_bind_group_layout],
                 push_constant_ranges: &[],
             });
-        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        let render_pipelines = BlendMode::ALL
+            .into_iter()
+            .map(|blend_mode| {
+                let pipeline = Self::create_render_pipeline(
+                    &device,
+                    &render_pipeline_layout,
+                    &shader,
+                    format,
+                    blend_mode,
+                );
+                (blend_mode, pipeline)
+            })
+            .collect();
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Main Vertex Buffer"),
+            contents: &bytemuck::zeroed_vec(INITIAL_VERTEX_BUFFER_SIZE),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Main Index Buffer"),
+            contents: &bytemuck::zeroed_vec(INITIAL_INDEX_BUFFER_SIZE),
+            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let background_vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Background Vertex Buffer"),
+            size: (background::MAX_VERTICES * mem::size_of::<Vertex>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let background_index_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Background Index Buffer"),
+            size: (background::MAX_INDICES * mem::size_of::<Index>()).next_multiple_of(4) as u64,
+            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            render_queue: RenderQueue::default(),
+            background: Background::default(),
+            camera,
+
+            target,
+            device,
+            queue,
+            render_pipelines,
+            vertex_buffer,
+            index_buffer,
+            batches: Vec::new(),
+            uploaded_revision: None,
+            background_vertex_buffer,
+            background_index_buffer,
+            background_indices_count: 0,
+            camera_buffer,
+            camera_bind_group,
+            screen_bind_group,
+            texture_bind_group_layout,
+            linear_sampler,
+            nearest_sampler,
+            white_bind_group,
+            textures: HashMap::new(),
+        }
+    }
+
+    /// Uploads sRGB RGBA `pixels` into a new GPU texture and binds it with `sampler`.
+    fn create_texture_bind_group(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        layout: &wgpu::BindGroupLayout,
+        sampler: &wgpu::Sampler,
+        (width, height): (u32, u32),
+        pixels: &[u8],
+    ) -> wgpu::BindGroup {
+        let texture = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("Shape Texture"),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            pixels,
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Texture Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        })
+    }
+
+    fn create_render_pipeline(
+        device: &wgpu::Device,
+        layout: &wgpu::PipelineLayout,
+        shader: &wgpu::ShaderModule,
+        format: wgpu::TextureFormat,
+        blend_mode: BlendMode,
+    ) -> wgpu::RenderPipeline {
+        let fragment_entry_point = if blend_mode.premultiplies_source() {
+            "fs_premultiplied"
+        } else {
+            "fs_main"
+        };
+
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
             label: Some("Render Pipeline"),
-            layout: Some(&render_pipeline_layout),
+            layout: Some(layout),
             vertex: wgpu::VertexState {
-                module: &shader,
+                module: shader,
                 entry_point: Some("vs_main"),
                 buffers: &[Vertex::desc()],
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
             },
             fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
+                module: shader,
+                entry_point: Some(fragment_entry_point),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::REPLACE),
+                    format,
+                    blend: Some(blend_mode.to_wgpu()),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
@@ -119,160 +487,435 @@ impl RendererState {
             },
             multiview: None,
             cache: None,
-        });
-
-        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Main Vertex Buffer"),
-            contents: &bytemuck::zeroed_vec(INITIAL_VERTEX_BUFFER_SIZE),
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-        });
-
-        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Main Index Buffer"),
-            contents: &bytemuck::zeroed_vec(INITIAL_INDEX_BUFFER_SIZE),
-            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
-        });
-
-        Ok(Self {
-            render_queue: RenderQueue::default(),
-
-            surface,
-            device,
-            queue,
-            config,
-            is_surface_configured: false,
-            render_pipeline,
-            vertex_buffer,
-            index_buffer,
-            indices_count: 0,
-            window,
         })
     }
 
     pub fn resize(&mut self, width: u32, height: u32) {
-        if width > 0 && height > 0 {
-            self.config.width = width;
-            self.config.height = height;
-            self.surface.configure(&self.device, &self.config);
-            self.is_surface_configured = true;
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        self.camera.viewport = Vector2::new(width as f32, height as f32);
+
+        match &mut self.target {
+            RenderTarget::Window {
+                surface,
+                config,
+                is_surface_configured,
+                canvas,
+                ..
+            } => {
+                config.width = width;
+                config.height = height;
+                surface.configure(&self.device, config);
+                *is_surface_configured = true;
+                *canvas = None;
+            }
+            RenderTarget::Offscreen { texture } => {
+                *texture = Self::create_offscreen_texture(&self.device, width, height);
+            }
         }
     }
 
     pub fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
-        self.window.request_redraw();
+        if let RenderTarget::Window {
+            window,
+            is_surface_configured,
+            ..
+        } = &self.target
+        {
+            window.request_redraw();
 
-        if !self.is_surface_configured {
-            return Ok(());
+            if !is_surface_configured {
+                return Ok(());
+            }
         }
 
-        // Take buffers from `RenderQueue`
-        let RenderBuffers { vertices, indices } = self
-            .render_queue
-            .buffers(self.config.width as f32, self.config.height as f32);
-        let vertices_bytes_size = vertices.len() * mem::size_of::<Vertex>();
-        let indices_bytes_size = indices.len() * mem::size_of::<Index>();
+        // The queue is taken out for the draw, because drawing borrows the whole renderer
+        let mut queue = mem::take(&mut self.render_queue);
+        let result = self.draw_queue(&mut queue);
+        self.render_queue = queue;
+        result
+    }
+
+    /// Writes the vertices and indices into the GPU buffers, growing them if needed.
+    /// Only the dirty ranges are written if the GPU buffers hold the previous revision.
+    fn upload_buffers(&mut self, buffers: &RenderBuffers) {
+        if self.uploaded_revision == Some(buffers.revision()) {
+            return;
+        }
+        let vertices = buffers.vertices();
+        let indices = buffers.indices();
+        let vertices_bytes_size = mem::size_of_val(vertices);
+        let indices_bytes_size = mem::size_of_val(indices);
+        let mut full_upload = self.uploaded_revision.is_none()
+            || self.uploaded_revision != buffers.previous_revision();
 
-        // Grow the GPU buffers if needed
+        // Grow the GPU buffers if needed, their old contents are lost
         let vertex_buffer_size = self.vertex_buffer.size() as usize;
         if vertices_bytes_size > vertex_buffer_size {
-            self.vertex_buffer =
-                self.device
-                    .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                        label: Some("Main Vertex Buffer"),
-                        contents: &bytemuck::zeroed_vec(vertex_buffer_size * Self::BUFFER_GROW),
-                        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                    });
+            self.vertex_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Main Vertex Buffer"),
+                size: Self::grown_size(vertex_buffer_size, vertices_bytes_size) as u64,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            full_upload = true;
         }
 
         let index_buffer_size = self.index_buffer.size() as usize;
         if indices_bytes_size > index_buffer_size {
-            self.index_buffer = self
-                .device
-                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                    label: Some("Main Index Buffer"),
-                    contents: &bytemuck::zeroed_vec(index_buffer_size * Self::BUFFER_GROW),
-                    usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
-                });
+            self.index_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Main Index Buffer"),
+                size: Self::grown_size(index_buffer_size, indices_bytes_size) as u64,
+                usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            full_upload = true;
         }
 
-        let nearest_4 = |mut val: usize| {
-            while val % 4 != 0 {
-                val += 1;
+        // Both types are a multiple of 4 bytes, as `write_buffer` requires
+        if full_upload {
+            Self::write_range(
+                &self.queue,
+                &self.vertex_buffer,
+                vertices,
+                0..vertices.len() as u32,
+            );
+            Self::write_range(
+                &self.queue,
+                &self.index_buffer,
+                indices,
+                0..indices.len() as u32,
+            );
+        } else {
+            for range in buffers.dirty_vertices() {
+                Self::write_range(&self.queue, &self.vertex_buffer, vertices, range.clone());
             }
-            val
+            for range in buffers.dirty_indices() {
+                Self::write_range(&self.queue, &self.index_buffer, indices, range.clone());
+            }
+        }
+
+        self.batches.clone_from(&buffers.batches);
+        self.uploaded_revision = Some(buffers.revision());
+    }
+
+    /// Writes `range` of `data` to the same place in `buffer`.
+    fn write_range<T: bytemuck::Pod>(
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        data: &[T],
+        range: Range<u32>,
+    ) {
+        if range.is_empty() {
+            return;
+        }
+        let offset = (range.start as usize * mem::size_of::<T>()) as u64;
+        let data = &data[range.start as usize..range.end as usize];
+        queue.write_buffer(buffer, offset, bytemuck::cast_slice(data));
+    }
+
+    /// Uploads the textures of the batches that are not on the GPU yet
+    /// and frees the ones whose `Texture` was dropped.
+    fn upload_textures(&mut self) {
+        let missing: Vec<Texture> = self
+            .batches
+            .iter()
+            .filter_map(|batch| batch.texture.as_ref())
+            .filter(|texture| !self.textures.contains_key(&texture.id()))
+            .cloned()
+            .collect();
+
+        for texture in missing {
+            let sampler = match texture.filter() {
+                TextureFilter::Linear => &self.linear_sampler,
+                TextureFilter::Nearest => &self.nearest_sampler,
+            };
+            let bind_group = Self::create_texture_bind_group(
+                &self.device,
+                &self.queue,
+                &self.texture_bind_group_layout,
+                sampler,
+                (texture.width(), texture.height()),
+                texture.pixels(),
+            );
+            self.textures.insert(
+                texture.id(),
+                GpuTexture {
+                    bind_group,
+                    source: texture.downgrade(),
+                },
+            );
+        }
+
+        self.textures
+            .retain(|_, texture| texture.source.strong_count() > 0);
+    }
+
+    /// Size a GPU buffer of `current` bytes has to grow to, to hold `required` bytes.
+    fn grown_size(current: usize, required: usize) -> usize {
+        let mut size = current.max(1);
+        while size < required {
+            size *= Self::BUFFER_GROW;
+        }
+        size
+    }
+
+    /// Creates the canvas for `Background::Keep` and records clearing it into `encoder`,
+    /// so the first frame is not drawn over whatever the new texture happened to hold.
+    fn create_canvas(
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> wgpu::Texture {
+        let canvas = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Canvas Texture"),
+            size: wgpu::Extent3d {
+                width: config.width,
+                height: config.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        let view = canvas.create_view(&wgpu::TextureViewDescriptor::default());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Canvas Clear Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+
+        canvas
+    }
+
+    /// Writes the geometry of the background into its GPU buffers.
+    fn upload_background(&mut self) {
+        let (width, height) = self.window_size();
+        let Some((vertices, mut indices)) = self.background.geometry(width as f32, height as f32)
+        else {
+            self.background_indices_count = 0;
+            return;
         };
 
-        // Write data into the GPU buffers
-        if let Some(size) = NonZeroU64::new(nearest_4(vertices_bytes_size) as u64) {
-            if let Some(mut write_view) = self.queue.write_buffer_with(&self.vertex_buffer, 0, size)
-            {
-                for (buffer_chunk, vertex) in write_view
-                    .chunks_mut(mem::size_of::<Vertex>())
-                    .zip(vertices.into_iter())
-                {
-                    buffer_chunk.copy_from_slice(bytemuck::bytes_of(&vertex));
-                }
-            }
+        self.background_indices_count = indices.len() as u32;
+        // `write_buffer` needs a size aligned to 4 bytes
+        while !(indices.len() * mem::size_of::<Index>()).is_multiple_of(4) {
+            indices.push(0);
         }
-        if let Some(size) = NonZeroU64::new(nearest_4(indices_bytes_size) as u64) {
-            self.indices_count = indices.len() as u32;
-            if let Some(mut write_view) = self.queue.write_buffer_with(&self.index_buffer, 0, size)
-            {
-                for (buffer_chunk, index) in write_view
-                    .chunks_mut(mem::size_of::<Index>())
-                    .zip(indices.into_iter())
-                {
-                    buffer_chunk.copy_from_slice(bytemuck::bytes_of(&index));
-                }
-            }
+
+        self.queue.write_buffer(
+            &self.background_vertex_buffer,
+            0,
+            bytemuck::cast_slice(&vertices),
+        );
+        self.queue.write_buffer(
+            &self.background_index_buffer,
+            0,
+            bytemuck::cast_slice(&indices),
+        );
+    }
+
+    /// Records the render pass drawing the background and the uploaded buffers into `view`.
+    fn draw_to_view(&self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
+        let load = match self.background.clear_color() {
+            Some(color) => wgpu::LoadOp::Clear(wgpu::Color {
+                r: color.r as f64,
+                g: color.g as f64,
+                b: color.b as f64,
+                a: color.a as f64,
+            }),
+            None => wgpu::LoadOp::Load,
+        };
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+
+        if self.background_indices_count > 0 {
+            render_pass.set_pipeline(&self.render_pipelines[&BlendMode::Alpha]);
+            render_pass.set_bind_group(0, &self.screen_bind_group, &[]);
+            render_pass.set_bind_group(1, &self.white_bind_group, &[]);
+            render_pass.set_vertex_buffer(0, self.background_vertex_buffer.slice(..));
+            render_pass.set_index_buffer(self.background_index_buffer.slice(..), INDEX_FORMAT);
+            render_pass.draw_indexed(0..self.background_indices_count, 0, 0..1);
         }
-        self.queue.submit([]);
 
-        let output = self.surface.get_current_texture()?;
-        let view = output
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
+        render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(self.index_buffer.slice(..), INDEX_FORMAT);
+        for batch in &self.batches {
+            let texture_bind_group = match &batch.texture {
+                Some(texture) => &self.textures[&texture.id()].bind_group,
+                None => &self.white_bind_group,
+            };
+            render_pass.set_pipeline(&self.render_pipelines[&batch.blend_mode]);
+            render_pass.set_bind_group(1, texture_bind_group, &[]);
+            render_pass.draw_indexed(batch.indices.clone(), 0, 0..1);
+        }
+    }
+
+    /// Reads back the last rendered frame of a headless renderer.
+    /// The pixels are returned row by row from the top-left corner, 4 bytes (RGBA) per pixel.
+    ///
+    /// Fails if the renderer draws to a window.
+    pub fn read_pixels(&self) -> anyhow::Result<Vec<u8>> {
+        let RenderTarget::Offscreen { texture } = &self.target else {
+            return Err(anyhow!("only headless renderers can read back pixels"));
+        };
+
+        let (width, height) = (texture.width(), texture.height());
+        let unpadded_bytes_per_row = width * 4;
+        let padded_bytes_per_row =
+            unpadded_bytes_per_row.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+
+        let readback_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Readback Buffer"),
+            size: (padded_bytes_per_row * height) as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
 
         let mut encoder = self
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Render Encoder"),
-            });
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.2,
-                            b: 0.3,
-                            a: 1.0,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                occlusion_query_set: None,
-                timestamp_writes: None,
+                label: Some("Readback Encoder"),
             });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &readback_buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture.size(),
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
 
-            render_pass.set_pipeline(&self.render_pipeline);
-            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-            render_pass.draw_indexed(0..self.indices_count, 0, 0..1);
-        }
+        let (sender, receiver) = std::sync::mpsc::channel();
+        readback_buffer.map_async(wgpu::MapMode::Read, .., move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::PollType::Wait)?;
+        receiver.recv()??;
 
-        self.queue.submit(std::iter::once(encoder.finish()));
-        output.present();
+        // Strip the row padding required by wgpu
+        let pixels = readback_buffer
+            .get_mapped_range(..)
+            .chunks(padded_bytes_per_row as usize)
+            .flat_map(|row| &row[..unpadded_bytes_per_row as usize])
+            .copied()
+            .collect();
+        readback_buffer.unmap();
 
-        Ok(())
+        Ok(pixels)
     }
 
+    /// Size of the render target in pixels.
+    /// For headless renderers this is the size of the offscreen texture.
     pub fn window_size(&self) -> (u32, u32) {
-        (self.config.width, self.config.height)
+        self.target.size()
+    }
+}
+
+impl RenderBackend for RendererState {
+    type Error = wgpu::SurfaceError;
+
+    fn target_size(&self) -> (u32, u32) {
+        self.window_size()
+    }
+
+    fn pixels_per_unit(&self) -> f32 {
+        self.camera.pixels_per_unit()
+    }
+
+    fn draw(&mut self, buffers: &RenderBuffers) -> Result<(), Self::Error> {
+        self.upload_buffers(buffers);
+        self.upload_textures();
+        self.upload_background();
+        self.queue.write_buffer(
+            &self.camera_buffer,
+            0,
+            bytemuck::cast_slice(&self.camera.view_projection()),
+        );
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Render Encoder"),
+            });
+
+        if let RenderTarget::Window { config, canvas, .. } = &mut self.target {
+            if self.background != Background::Keep {
+                *canvas = None;
+            } else if canvas.is_none() && config.usage.contains(wgpu::TextureUsages::COPY_DST) {
+                *canvas = Some(Self::create_canvas(&self.device, config, &mut encoder));
+            }
+        }
+
+        match &self.target {
+            RenderTarget::Window {
+                surface, canvas, ..
+            } => {
+                let output = surface.get_current_texture()?;
+
+                if let Some(canvas) = canvas
+                    && self.background == Background::Keep
+                {
+                    let view = canvas.create_view(&wgpu::TextureViewDescriptor::default());
+                    self.draw_to_view(&mut encoder, &view);
+                    encoder.copy_texture_to_texture(
+                        canvas.as_image_copy(),
+                        output.texture.as_image_copy(),
+                        canvas.size(),
+                    );
+                } else {
+                    let view = output
+                        .texture
+                        .create_view(&wgpu::TextureViewDescriptor::default());
+                    self.draw_to_view(&mut encoder, &view);
+                }
+
+                self.queue.submit(std::iter::once(encoder.finish()));
+                output.present();
+            }
+            RenderTarget::Offscreen { texture } => {
+                let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+                self.draw_to_view(&mut encoder, &view);
+                self.queue.submit(std::iter::once(encoder.finish()));
+            }
+        }
+
+        Ok(())
     }
 }
@@ -0,0 +1,122 @@
+use std::fmt::Write;
+
+use crate::rendering::{
+    blend::BlendMode,
+    color::Color,
+    queue::{RenderBuffers, RenderQueue},
+    vertex::Vertex,
+};
+
+/// Writes the triangles of `buffers` as an SVG document.
+///
+/// Vertex positions are used as they are, one world unit becomes one user unit, with the y axis
+/// flipped to point down like SVG expects. `pixels_per_unit` only sets the size of the document.
+/// Runs of triangles with the same color are merged into one `path`, so no seams show between
+/// them. Triangles whose vertices differ in color are filled with their average color.
+/// Textures are not exported, textured triangles are filled with their tint.
+pub fn export(buffers: &RenderBuffers, pixels_per_unit: f32) -> String {
+    let point = |vertex: &Vertex| {
+        // Adding zero turns -0 into 0
+        let [x, y] = vertex.position;
+        (x + 0.0, -y + 0.0)
+    };
+
+    let mut min = (f32::INFINITY, f32::INFINITY);
+    let mut max = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+    for index in &buffers.indices {
+        let (x, y) = point(&buffers.vertices[*index as usize]);
+        min = (min.0.min(x), min.1.min(y));
+        max = (max.0.max(x), max.1.max(y));
+    }
+    if min.0 > max.0 {
+        min = (0.0, 0.0);
+        max = (0.0, 0.0);
+    }
+    let size = (max.0 - min.0, max.1 - min.1);
+
+    let mut document = String::new();
+    // Writing into a `String` can't fail
+    let _ = writeln!(
+        document,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+        size.0 * pixels_per_unit,
+        size.1 * pixels_per_unit,
+        min.0,
+        min.1,
+        size.0,
+        size.1
+    );
+
+    for batch in &buffers.batches {
+        let indices = &buffers.indices[batch.indices.start as usize..batch.indices.end as usize];
+        let blend = match batch.blend_mode {
+            BlendMode::Alpha | BlendMode::PremultipliedAlpha => None,
+            BlendMode::Additive => Some("plus-lighter"),
+            BlendMode::Multiply => Some("multiply"),
+        };
+        let indent = if let Some(blend) = blend {
+            let _ = writeln!(document, r#"  <g style="mix-blend-mode:{blend}">"#);
+            "    "
+        } else {
+            "  "
+        };
+
+        let mut run: Option<(String, String)> = None;
+        for triangle in indices.chunks_exact(3) {
+            let corners = [0, 1, 2].map(|i| &buffers.vertices[triangle[i] as usize]);
+            let fill = fill(corners, batch.blend_mode);
+            let [a, b, c] = corners.map(point);
+            let outline = format!("M{} {}L{} {}L{} {}Z", a.0, a.1, b.0, b.1, c.0, c.1);
+
+            match &mut run {
+                Some((run_fill, data)) if *run_fill == fill => data.push_str(&outline),
+                _ => {
+                    if let Some((fill, data)) = run.replace((fill, outline)) {
+                        let _ = writeln!(document, r#"{indent}<path {fill} d="{data}"/>"#);
+                    }
+                }
+            }
+        }
+        if let Some((fill, data)) = run {
+            let _ = writeln!(document, r#"{indent}<path {fill} d="{data}"/>"#);
+        }
+
+        if blend.is_some() {
+            let _ = writeln!(document, "  </g>");
+        }
+    }
+
+    document.push_str("</svg>\n");
+    document
+}
+
+/// The `fill` and, if needed, `fill-opacity` attributes of a triangle.
+fn fill(corners: [&Vertex; 3], blend_mode: BlendMode) -> String {
+    let [r, g, b, a] = [0, 1, 2, 3].map(|channel| {
+        corners
+            .iter()
+            .map(|vertex| vertex.color[channel])
+            .sum::<f32>()
+            / 3.0
+    });
+    let mut color = Color::rgba(r, g, b, a);
+    if blend_mode == BlendMode::PremultipliedAlpha && a > 0.0 {
+        color = Color::rgba(r / a, g / a, b / a, a);
+    }
+
+    let [r, g, b, a] = color.to_rgba8();
+    let fill = format!(r##"fill="#{r:02x}{g:02x}{b:02x}""##);
+    if a == 255 {
+        fill
+    } else {
+        format!(r#"{fill} fill-opacity="{}""#, a as f32 / 255.0)
+    }
+}
+
+impl RenderQueue {
+    /// Tessellates the visible shapes for `pixels_per_unit` and writes them as an SVG document,
+    /// see `svg::export`.
+    pub fn to_svg(&mut self, pixels_per_unit: f32) -> String {
+        export(self.buffers(pixels_per_unit), pixels_per_unit)
+    }
+}
@@ -0,0 +1,617 @@
+use std::{error::Error, fmt};
+
+use roxmltree::{Document, Node};
+
+use crate::{
+    math::{Affine2, Matrix2, Vector2},
+    rendering::{
+        color::Color,
+        queue::{RenderQueue, ShapeHandle},
+        shapes::{FillRule, LineCap, LineJoin, ParsePathError, Path, Shape, Stroke},
+    },
+};
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// Reads the shapes of an SVG document.
+///
+/// Supports `rect`, `circle`, `ellipse`, `line`, `polyline`, `polygon` and `path` elements
+/// in nested groups with transforms, filled and stroked with solid colors. Each of them becomes
+/// a `Shape::Path`. One user unit of the document is one world unit, and the y axis is flipped
+/// to point up, so the drawing keeps its orientation around the origin.
+/// Other elements, like text, images and anything inside of `defs`, are skipped.
+/// Like in browsers, presentation attributes and style properties with values that are not
+/// supported are ignored, keeping the value inherited from the parent element.
+pub fn import(svg: &str) -> Result<Vec<Shape>, SvgError> {
+    let document = Document::parse(svg)?;
+    let root = document.root_element();
+    if !is_svg_element(root, "svg") {
+        return Err(SvgError::NotSvg);
+    }
+
+    let mut shapes = Vec::new();
+    let style = Style::default().with_node(root);
+    if style.display {
+        let transform = Affine2::from_scale(Vector2::new(1.0, -1.0)) * node_transform(root)?;
+        add_children(root, &style, transform, &mut shapes)?;
+    }
+    Ok(shapes)
+}
+
+impl RenderQueue {
+    /// Adds the shapes of an SVG document, see `svg::import`.
+    pub fn add_svg(&mut self, svg: &str) -> Result<Vec<ShapeHandle>, SvgError> {
+        let shapes = import(svg)?;
+        Ok(shapes.into_iter().map(|shape| self.add(shape)).collect())
+    }
+}
+
+/// Error returned when importing an SVG document fails.
+#[derive(Debug)]
+pub enum SvgError {
+    /// The document is not well-formed XML.
+    Xml(roxmltree::Error),
+    /// The root element is not an `svg` element.
+    NotSvg,
+    /// The `d` of a path, or the `points` of a polyline or polygon, can't be parsed.
+    InvalidPath(ParsePathError),
+    /// A geometry or `transform` attribute has a value that can't be parsed.
+    InvalidValue { name: String, value: String },
+}
+
+impl SvgError {
+    fn invalid_value(name: &str, value: &str) -> Self {
+        SvgError::InvalidValue {
+            name: name.to_owned(),
+            value: value.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Xml(error) => write!(f, "invalid SVG document: {error}"),
+            Self::NotSvg => write!(f, "root element is not an SVG element"),
+            Self::InvalidPath(error) => write!(f, "invalid SVG path: {error}"),
+            Self::InvalidValue { name, value } => {
+                write!(f, "unsupported value {value:?} of SVG attribute `{name}`")
+            }
+        }
+    }
+}
+
+impl Error for SvgError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Xml(error) => Some(error),
+            Self::InvalidPath(error) => Some(error),
+            Self::NotSvg | Self::InvalidValue { .. } => None,
+        }
+    }
+}
+
+impl From<roxmltree::Error> for SvgError {
+    fn from(error: roxmltree::Error) -> Self {
+        SvgError::Xml(error)
+    }
+}
+
+impl From<ParsePathError> for SvgError {
+    fn from(error: ParsePathError) -> Self {
+        SvgError::InvalidPath(error)
+    }
+}
+
+/// Presentation properties, inherited from the parent elements.
+#[derive(Debug, Clone)]
+struct Style {
+    fill: Option<Color>,
+    fill_opacity: f32,
+    fill_rule: FillRule,
+    stroke: Option<Color>,
+    stroke_opacity: f32,
+    stroke_width: f32,
+    line_cap: LineCap,
+    line_join: LineJoin,
+    miter_limit: f32,
+    dash_pattern: Vec<f32>,
+    dash_offset: f32,
+    /// Used by `currentColor`.
+    color: Color,
+    /// Multiplied into the opacity of the children, which approximates group opacity
+    /// without blending the group as a whole.
+    opacity: f32,
+    display: bool,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style {
+            fill: Some(Color::BLACK),
+            fill_opacity: 1.0,
+            fill_rule: FillRule::NonZero,
+            stroke: None,
+            stroke_opacity: 1.0,
+            stroke_width: 1.0,
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+            miter_limit: 4.0,
+            dash_pattern: Vec::new(),
+            dash_offset: 0.0,
+            color: Color::BLACK,
+            opacity: 1.0,
+            display: true,
+        }
+    }
+}
+
+impl Style {
+    /// Applies the presentation attributes of `node`, then its `style` attribute.
+    fn with_node(mut self, node: Node) -> Self {
+        // `color` first, so `currentColor` refers to the color of this element
+        let color = node.attribute("color").into_iter().chain(
+            declarations(node.attribute("style").unwrap_or_default())
+                .filter(|(name, _)| *name == "color")
+                .map(|(_, value)| value),
+        );
+        for value in color {
+            self.set("color", value);
+        }
+
+        let parent_opacity = self.opacity;
+        self.opacity = 1.0;
+        for attribute in node.attributes() {
+            if attribute.namespace().is_none() {
+                self.set(attribute.name(), attribute.value());
+            }
+        }
+        for (name, value) in declarations(node.attribute("style").unwrap_or_default()) {
+            self.set(name, value);
+        }
+        self.opacity *= parent_opacity;
+
+        self
+    }
+
+    /// Applies one property, leaving it unchanged if `value` is not supported.
+    fn set(&mut self, name: &str, value: &str) {
+        let value = value.trim();
+        match name {
+            "fill" => {
+                let fill = self.parse_paint(value);
+                set(&mut self.fill, fill)
+            }
+            "fill-opacity" => set(&mut self.fill_opacity, parse_opacity(value)),
+            "fill-rule" => set(
+                &mut self.fill_rule,
+                match value {
+                    "nonzero" => Some(FillRule::NonZero),
+                    "evenodd" => Some(FillRule::EvenOdd),
+                    _ => None,
+                },
+            ),
+            "stroke" => {
+                let stroke = self.parse_paint(value);
+                set(&mut self.stroke, stroke)
+            }
+            "stroke-opacity" => set(&mut self.stroke_opacity, parse_opacity(value)),
+            "stroke-width" => set(&mut self.stroke_width, parse_length(value)),
+            "stroke-linecap" => set(
+                &mut self.line_cap,
+                match value {
+                    "butt" => Some(LineCap::Butt),
+                    "round" => Some(LineCap::Round),
+                    "square" => Some(LineCap::Square),
+                    _ => None,
+                },
+            ),
+            "stroke-linejoin" => set(
+                &mut self.line_join,
+                match value {
+                    "miter" | "miter-clip" | "arcs" => Some(LineJoin::Miter),
+                    "round" => Some(LineJoin::Round),
+                    "bevel" => Some(LineJoin::Bevel),
+                    _ => None,
+                },
+            ),
+            "stroke-miterlimit" => set(&mut self.miter_limit, parse_length(value)),
+            "stroke-dasharray" => set(
+                &mut self.dash_pattern,
+                if value == "none" {
+                    Some(Vec::new())
+                } else {
+                    parse_list(value)
+                },
+            ),
+            "stroke-dashoffset" => set(&mut self.dash_offset, parse_length(value)),
+            "color" => {
+                let color = self.parse_paint(value).flatten();
+                set(&mut self.color, color)
+            }
+            "opacity" => set(&mut self.opacity, parse_opacity(value)),
+            "display" => self.display = value != "none",
+            // Everything else is either geometry or not supported
+            _ => {}
+        }
+    }
+
+    /// Parses a fill or stroke, `Some(None)` for `none`.
+    fn parse_paint(&self, value: &str) -> Option<Option<Color>> {
+        if value == "none" {
+            return Some(None);
+        }
+        if value == "currentColor" {
+            return Some(Some(self.color));
+        }
+        // Gradients and patterns are not supported, their fallback color is used instead
+        if let Some(reference) = value.strip_prefix("url(") {
+            let fallback = reference.split_once(')')?.1.trim();
+            return if fallback.is_empty() {
+                Some(None)
+            } else {
+                self.parse_paint(fallback)
+            };
+        }
+        parse_color(value).map(Some)
+    }
+
+    fn fill(&self) -> Option<Color> {
+        self.fill.map(|color| Color {
+            a: color.a * self.fill_opacity * self.opacity,
+            ..color
+        })
+    }
+
+    fn stroke(&self) -> Option<Stroke> {
+        let color = self.stroke?;
+        if self.stroke_width <= 0.0 {
+            return None;
+        }
+        let color = Color {
+            a: color.a * self.stroke_opacity * self.opacity,
+            ..color
+        };
+
+        Some(
+            Stroke::new(self.stroke_width)
+                .with_color(color)
+                .with_cap(self.line_cap)
+                .with_join(self.line_join)
+                .with_miter_limit(self.miter_limit)
+                .with_dashes(self.dash_pattern.clone(), self.dash_offset),
+        )
+    }
+}
+
+/// Replaces `property` with `value`, or keeps it, which is the inherited value, if the
+/// value could not be parsed. This also covers `inherit`.
+fn set<T>(property: &mut T, value: Option<T>) {
+    if let Some(value) = value {
+        *property = value;
+    }
+}
+
+fn is_svg_element(node: Node, name: &str) -> bool {
+    let tag = node.tag_name();
+    tag.name() == name && matches!(tag.namespace(), None | Some(SVG_NAMESPACE))
+}
+
+/// Splits a `style` attribute into its property names and values.
+fn declarations(style: &str) -> impl Iterator<Item = (&str, &str)> {
+    style
+        .split(';')
+        .filter_map(|declaration| declaration.split_once(':'))
+        .map(|(name, value)| (name.trim(), value.trim()))
+}
+
+fn add_children(
+    parent: Node,
+    style: &Style,
+    transform: Affine2<f32>,
+    shapes: &mut Vec<Shape>,
+) -> Result<(), SvgError> {
+    for node in parent.children().filter(Node::is_element) {
+        let tag = node.tag_name();
+        if !matches!(tag.namespace(), None | Some(SVG_NAMESPACE)) {
+            continue;
+        }
+
+        let geometry = match tag.name() {
+            "g" | "svg" => None,
+            "rect" => Some(rect(node)?),
+            "circle" => {
+                let radius = length(node, "r")?;
+                Some(ellipse(node, Vector2::new(radius, radius))?)
+            }
+            "ellipse" => {
+                let radii = Vector2::new(length(node, "rx")?, length(node, "ry")?);
+                Some(ellipse(node, radii)?)
+            }
+            "line" => Some(Some(
+                Path::new()
+                    .move_to(point(node, "x1", "y1")?)
+                    .line_to(point(node, "x2", "y2")?),
+            )),
+            "polyline" => Some(points(node, false)?),
+            "polygon" => Some(points(node, true)?),
+            "path" => Some(match node.attribute("d") {
+                Some(data) if !data.trim().is_empty() => Some(Path::from_svg(data)?),
+                _ => None,
+            }),
+            // Definitions, text, images and metadata
+            _ => continue,
+        };
+
+        let style = style.clone().with_node(node);
+        if !style.display {
+            continue;
+        }
+        let mut transform = transform * node_transform(node)?;
+        if tag.name() == "svg" {
+            transform *=
+                Affine2::from_translation(Vector2::new(length(node, "x")?, length(node, "y")?));
+        }
+
+        match geometry {
+            None => add_children(node, &style, transform, shapes)?,
+            Some(None) => {}
+            Some(Some(path)) => {
+                let mut path = path.with_fill_rule(style.fill_rule);
+                path.fill = style.fill();
+                path.stroke = style.stroke();
+                if path.fill.is_some() || path.stroke.is_some() {
+                    shapes.push(Shape::Path(path.transformed(&transform)));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A rectangle, `None` if it has no area.
+fn rect(node: Node) -> Result<Option<Path>, SvgError> {
+    let min = point(node, "x", "y")?;
+    let size = Vector2::new(length(node, "width")?, length(node, "height")?);
+    if size.x <= 0.0 || size.y <= 0.0 {
+        return Ok(None);
+    }
+    let max = min + size;
+
+    // A missing radius is the same as the other one
+    let rx = node
+        .attribute("rx")
+        .map(|_| length(node, "rx"))
+        .transpose()?;
+    let ry = node
+        .attribute("ry")
+        .map(|_| length(node, "ry"))
+        .transpose()?;
+    let radii = Vector2::new(
+        rx.or(ry).unwrap_or_default().clamp(0.0, size.x * 0.5),
+        ry.or(rx).unwrap_or_default().clamp(0.0, size.y * 0.5),
+    );
+
+    if radii.x == 0.0 || radii.y == 0.0 {
+        return Ok(Some(
+            Path::new()
+                .move_to(min)
+                .line_to(Vector2::new(max.x, min.y))
+                .line_to(max)
+                .line_to(Vector2::new(min.x, max.y))
+                .close(),
+        ));
+    }
+
+    let corner = |path: Path, to: Vector2<f32>| path.arc_to(radii, 0.0, false, true, to);
+    let path = Path::new().move_to(Vector2::new(min.x + radii.x, min.y));
+    let path = corner(
+        path.line_to(Vector2::new(max.x - radii.x, min.y)),
+        Vector2::new(max.x, min.y + radii.y),
+    );
+    let path = corner(
+        path.line_to(Vector2::new(max.x, max.y - radii.y)),
+        Vector2::new(max.x - radii.x, max.y),
+    );
+    let path = corner(
+        path.line_to(Vector2::new(min.x + radii.x, max.y)),
+        Vector2::new(min.x, max.y - radii.y),
+    );
+    let path = corner(
+        path.line_to(Vector2::new(min.x, min.y + radii.y)),
+        Vector2::new(min.x + radii.x, min.y),
+    );
+    Ok(Some(path.close()))
+}
+
+/// A circle or ellipse around `cx` and `cy`, `None` if it has no area.
+fn ellipse(node: Node, radii: Vector2<f32>) -> Result<Option<Path>, SvgError> {
+    if radii.x <= 0.0 || radii.y <= 0.0 {
+        return Ok(None);
+    }
+    let center = point(node, "cx", "cy")?;
+    let right = center + Vector2::new(radii.x, 0.0);
+    let left = center - Vector2::new(radii.x, 0.0);
+
+    Ok(Some(
+        Path::new()
+            .move_to(right)
+            .arc_to(radii, 0.0, false, true, left)
+            .arc_to(radii, 0.0, false, true, right)
+            .close(),
+    ))
+}
+
+/// A polyline or polygon from the `points` attribute, `None` if there are none.
+fn points(node: Node, closed: bool) -> Result<Option<Path>, SvgError> {
+    let points = node.attribute("points").unwrap_or_default().trim();
+    if points.is_empty() {
+        return Ok(None);
+    }
+
+    // The points are the coordinates of a move command followed by implicit lines
+    let path = Path::from_svg(&format!("M{points}"))?;
+    Ok(Some(if closed { path.close() } else { path }))
+}
+
+fn point(node: Node, x: &str, y: &str) -> Result<Vector2<f32>, SvgError> {
+    Ok(Vector2::new(length(node, x)?, length(node, y)?))
+}
+
+/// A length in user units, 0.0 if the attribute is missing.
+fn length(node: Node, name: &str) -> Result<f32, SvgError> {
+    match node.attribute(name) {
+        Some(value) => parse_length(value).ok_or_else(|| SvgError::invalid_value(name, value)),
+        None => Ok(0.0),
+    }
+}
+
+/// Parses a number, which may be given in pixels. Other units are not supported.
+fn parse_length(value: &str) -> Option<f32> {
+    let value = value.trim();
+    value
+        .strip_suffix("px")
+        .unwrap_or(value)
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Parses an opacity given as a number or a percentage, clamped to 0.0..=1.0.
+fn parse_opacity(value: &str) -> Option<f32> {
+    let opacity = match value.trim().strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f32>().ok()? / 100.0,
+        None => value.trim().parse::<f32>().ok()?,
+    };
+    Some(opacity.clamp(0.0, 1.0))
+}
+
+fn parse_list(value: &str) -> Option<Vec<f32>> {
+    value
+        .split([',', ' ', '\t', '\n', '\r'])
+        .filter(|item| !item.is_empty())
+        .map(parse_length)
+        .collect()
+}
+
+/// Parses a color as `#rgb`, `#rrggbb`, `rgb(r, g, b)`, `rgba(r, g, b, a)` or one of the
+/// basic color keywords.
+fn parse_color(value: &str) -> Option<Color> {
+    if value.starts_with('#') {
+        return Color::from_hex(value).ok();
+    }
+
+    if let Some(arguments) = value
+        .strip_prefix("rgba(")
+        .or_else(|| value.strip_prefix("rgb("))
+    {
+        let arguments: Vec<&str> = arguments
+            .strip_suffix(')')?
+            .split([',', ' ', '/'])
+            .filter(|argument| !argument.is_empty())
+            .collect();
+        let channel = |argument: &str| -> Option<u8> {
+            let value = match argument.strip_suffix('%') {
+                Some(percent) => percent.parse::<f32>().ok()? * 2.55,
+                None => argument.parse::<f32>().ok()?,
+            };
+            Some(value.round().clamp(0.0, 255.0) as u8)
+        };
+        let alpha = |argument: &str| -> Option<f32> {
+            let value = match argument.strip_suffix('%') {
+                Some(percent) => percent.parse::<f32>().ok()? / 100.0,
+                None => argument.parse::<f32>().ok()?,
+            };
+            Some(value.clamp(0.0, 1.0))
+        };
+
+        return match arguments[..] {
+            [r, g, b] => Some(Color::from_rgba8(
+                channel(r)?,
+                channel(g)?,
+                channel(b)?,
+                255,
+            )),
+            [r, g, b, a] => Some(Color {
+                a: alpha(a)?,
+                ..Color::from_rgba8(channel(r)?, channel(g)?, channel(b)?, 255)
+            }),
+            _ => None,
+        };
+    }
+
+    let [r, g, b] = match value.to_ascii_lowercase().as_str() {
+        "transparent" => return Some(Color::TRANSPARENT),
+        "black" => [0, 0, 0],
+        "silver" => [192, 192, 192],
+        "gray" | "grey" => [128, 128, 128],
+        "white" => [255, 255, 255],
+        "maroon" => [128, 0, 0],
+        "red" => [255, 0, 0],
+        "purple" => [128, 0, 128],
+        "fuchsia" | "magenta" => [255, 0, 255],
+        "green" => [0, 128, 0],
+        "lime" => [0, 255, 0],
+        "olive" => [128, 128, 0],
+        "yellow" => [255, 255, 0],
+        "navy" => [0, 0, 128],
+        "blue" => [0, 0, 255],
+        "teal" => [0, 128, 128],
+        "aqua" | "cyan" => [0, 255, 255],
+        "orange" => [255, 165, 0],
+        _ => return None,
+    };
+    Some(Color::from_rgba8(r, g, b, 255))
+}
+
+/// The `transform` attribute of `node`, the identity if it has none.
+fn node_transform(node: Node) -> Result<Affine2<f32>, SvgError> {
+    match node.attribute("transform") {
+        Some(value) => {
+            parse_transform(value).ok_or_else(|| SvgError::invalid_value("transform", value))
+        }
+        None => Ok(Affine2::identity()),
+    }
+}
+
+/// Parses a list of transform functions like `translate(10, 20) rotate(45)`.
+fn parse_transform(value: &str) -> Option<Affine2<f32>> {
+    let mut transform = Affine2::identity();
+    let mut rest = value.trim();
+
+    while !rest.is_empty() {
+        let (name, after_name) = rest.split_once('(')?;
+        let (arguments, after_arguments) = after_name.split_once(')')?;
+        let arguments = parse_list(arguments)?;
+
+        let function = match (name.trim(), arguments.as_slice()) {
+            ("matrix", &[a, b, c, d, e, f]) => matrix(a, b, c, d, e, f),
+            ("translate", &[x]) => Affine2::from_translation(Vector2::new(x, 0.0)),
+            ("translate", &[x, y]) => Affine2::from_translation(Vector2::new(x, y)),
+            ("scale", &[scale]) => Affine2::from_scale(Vector2::new(scale, scale)),
+            ("scale", &[x, y]) => Affine2::from_scale(Vector2::new(x, y)),
+            ("rotate", &[angle]) => Affine2::from_angle(angle.to_radians()),
+            ("rotate", &[angle, x, y]) => {
+                Affine2::from_translation(Vector2::new(x, y))
+                    * Affine2::from_angle(angle.to_radians())
+                    * Affine2::from_translation(Vector2::new(-x, -y))
+            }
+            ("skewX", &[angle]) => matrix(1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0),
+            ("skewY", &[angle]) => matrix(1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0),
+            _ => return None,
+        };
+        transform *= function;
+        rest = after_arguments.trim_start_matches([',', ' ', '\t', '\n', '\r']);
+    }
+
+    Some(transform)
+}
+
+/// The transform SVG writes as `matrix(a, b, c, d, e, f)`, mapping `(x, y)` to
+/// `(a * x + c * y + e, b * x + d * y + f)`.
+fn matrix(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Affine2<f32> {
+    Affine2::new(
+        Matrix2::from_columns(Vector2::new(a, b), Vector2::new(c, d)),
+        Vector2::new(e, f),
+    )
+}
@@ -0,0 +1,7 @@
+//! Conversion between SVG documents and shapes.
+
+mod export;
+mod import;
+
+pub use export::export;
+pub use import::{SvgError, import};
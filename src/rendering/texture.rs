@@ -0,0 +1,335 @@
+use std::{
+    error::Error,
+    fmt, fs,
+    io::{self, Cursor},
+    path::Path,
+    sync::{
+        Arc, LazyLock, Weak,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use crate::rendering::color::srgb_to_linear;
+
+/// An RGBA image with 8 bits per channel that shapes can be drawn with.
+///
+/// The pixels are sRGB encoded like the ones of PNG files, with alpha that is not premultiplied.
+/// Cloning a texture is cheap, the clones share the pixels. Renderers upload a texture
+/// to the GPU the first time it is drawn and free it once all of its clones are dropped.
+#[derive(Clone)]
+pub struct Texture {
+    data: Arc<TextureData>,
+}
+
+#[derive(Clone)]
+pub(super) struct TextureData {
+    /// Unique among all textures, a texture with other pixels or filter gets a new one.
+    id: u64,
+    width: u32,
+    height: u32,
+    /// sRGB RGBA pixels, row by row from the top-left corner.
+    pixels: Vec<u8>,
+    filter: TextureFilter,
+}
+
+/// How a texture is sampled between the centers of its pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TextureFilter {
+    /// Blends the four nearest pixels, smooth when the texture is scaled.
+    #[default]
+    Linear,
+    /// Takes the nearest pixel, keeps the edges of pixel art sharp.
+    Nearest,
+}
+
+/// A rectangle of a texture in pixels, with `x` and `y` being its top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextureRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Error returned when a texture can't be created.
+#[derive(Debug)]
+pub enum TextureError {
+    /// The file can't be read.
+    Io(io::Error),
+    /// The data is not a valid PNG image.
+    Png(png::DecodingError),
+    /// The data is not a valid QOI image.
+    Qoi(qoi::Error),
+    /// The data is neither a PNG nor a QOI image.
+    UnknownFormat,
+    /// The pixels do not fill a non-empty image of the given size.
+    InvalidSize {
+        width: u32,
+        height: u32,
+        bytes: usize,
+    },
+}
+
+/// Source of the ids of all textures.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Linear value of each 8-bit sRGB value.
+static SRGB_TO_LINEAR: LazyLock<[f32; 256]> =
+    LazyLock::new(|| std::array::from_fn(|value| srgb_to_linear(value as f32 / 255.0)));
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+const QOI_MAGIC: &[u8] = b"qoif";
+
+impl Texture {
+    /// Creates a texture from sRGB RGBA pixels, row by row from the top-left corner.
+    pub fn from_rgba8(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self, TextureError> {
+        if width == 0 || height == 0 || pixels.len() != width as usize * height as usize * 4 {
+            return Err(TextureError::InvalidSize {
+                width,
+                height,
+                bytes: pixels.len(),
+            });
+        }
+
+        Ok(Self {
+            data: Arc::new(TextureData {
+                id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+                width,
+                height,
+                pixels,
+                filter: TextureFilter::default(),
+            }),
+        })
+    }
+
+    /// Decodes a PNG or QOI image, the format is detected from the data.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TextureError> {
+        if bytes.starts_with(PNG_SIGNATURE) {
+            Self::from_png(bytes)
+        } else if bytes.starts_with(QOI_MAGIC) {
+            Self::from_qoi(bytes)
+        } else {
+            Err(TextureError::UnknownFormat)
+        }
+    }
+
+    /// Reads a PNG or QOI image from a file, see `Texture::from_bytes`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TextureError> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// Decodes a PNG image of any color type and bit depth.
+    pub fn from_png(bytes: &[u8]) -> Result<Self, TextureError> {
+        let mut decoder = png::Decoder::new(Cursor::new(bytes));
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+        let mut buffer = vec![0; reader.output_buffer_size().unwrap_or_default()];
+        let info = reader.next_frame(&mut buffer)?;
+        buffer.truncate(info.buffer_size());
+
+        let pixels = match info.color_type {
+            png::ColorType::Rgba => buffer,
+            png::ColorType::Rgb => buffer
+                .chunks_exact(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => buffer
+                .chunks_exact(2)
+                .flat_map(|gray| [gray[0], gray[0], gray[0], gray[1]])
+                .collect(),
+            // Indexed images are expanded to RGB or RGBA by the transformations
+            png::ColorType::Grayscale | png::ColorType::Indexed => buffer
+                .iter()
+                .flat_map(|&gray| [gray, gray, gray, 255])
+                .collect(),
+        };
+
+        Self::from_rgba8(info.width, info.height, pixels)
+    }
+
+    /// Decodes a QOI image. Its pixels are treated as sRGB whatever color space the header states.
+    pub fn from_qoi(bytes: &[u8]) -> Result<Self, TextureError> {
+        let mut decoder = qoi::Decoder::new(bytes)?.with_channels(qoi::Channels::Rgba);
+        let header = *decoder.header();
+        let pixels = decoder.decode_to_vec()?;
+
+        Self::from_rgba8(header.width, header.height, pixels)
+    }
+
+    /// Sets how the texture is sampled. The result is a new texture for the renderers,
+    /// other clones keep the old filter.
+    pub fn with_filter(self, filter: TextureFilter) -> Self {
+        let mut data = Arc::unwrap_or_clone(self.data);
+        data.id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        data.filter = filter;
+
+        Self {
+            data: Arc::new(data),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.data.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.data.height
+    }
+
+    pub fn filter(&self) -> TextureFilter {
+        self.data.filter
+    }
+
+    /// The sRGB RGBA pixels, row by row from the top-left corner.
+    pub fn pixels(&self) -> &[u8] {
+        &self.data.pixels
+    }
+
+    /// The region covering the whole texture.
+    pub fn region(&self) -> TextureRegion {
+        TextureRegion::new(0, 0, self.width(), self.height())
+    }
+
+    pub(super) fn id(&self) -> u64 {
+        self.data.id
+    }
+
+    pub(super) fn downgrade(&self) -> Weak<TextureData> {
+        Arc::downgrade(&self.data)
+    }
+
+    /// Texture coordinates of the top-left and bottom-right corners of `region`.
+    pub(super) fn uv_bounds(&self, region: TextureRegion) -> ([f32; 2], [f32; 2]) {
+        let (width, height) = (self.width() as f32, self.height() as f32);
+        (
+            [region.x as f32 / width, region.y as f32 / height],
+            [
+                (region.x + region.width) as f32 / width,
+                (region.y + region.height) as f32 / height,
+            ],
+        )
+    }
+
+    /// Linear color of the texture at `uv`, filtered the same way the GPU does.
+    /// Coordinates outside of 0.0..1.0 are clamped to the edge pixels.
+    pub(super) fn sample(&self, uv: [f32; 2]) -> [f32; 4] {
+        let x = uv[0] * self.width() as f32;
+        let y = uv[1] * self.height() as f32;
+
+        match self.filter() {
+            TextureFilter::Nearest => self.texel(x.floor() as i64, y.floor() as i64),
+            TextureFilter::Linear => {
+                // Pixel centers are at half coordinates
+                let (x, y) = (x - 0.5, y - 0.5);
+                let (left, top) = (x.floor(), y.floor());
+                let (tx, ty) = (x - left, y - top);
+                let (left, top) = (left as i64, top as i64);
+
+                let top_left = self.texel(left, top);
+                let top_right = self.texel(left + 1, top);
+                let bottom_left = self.texel(left, top + 1);
+                let bottom_right = self.texel(left + 1, top + 1);
+                std::array::from_fn(|channel| {
+                    let upper = top_left[channel] + (top_right[channel] - top_left[channel]) * tx;
+                    let lower =
+                        bottom_left[channel] + (bottom_right[channel] - bottom_left[channel]) * tx;
+                    upper + (lower - upper) * ty
+                })
+            }
+        }
+    }
+
+    /// Linear color of the pixel at `x`, `y`, clamped to the edges.
+    fn texel(&self, x: i64, y: i64) -> [f32; 4] {
+        let x = x.clamp(0, self.width() as i64 - 1) as usize;
+        let y = y.clamp(0, self.height() as i64 - 1) as usize;
+        let start = (y * self.width() as usize + x) * 4;
+        let [r, g, b, a] = [0, 1, 2, 3].map(|channel| self.data.pixels[start + channel]);
+
+        [
+            SRGB_TO_LINEAR[r as usize],
+            SRGB_TO_LINEAR[g as usize],
+            SRGB_TO_LINEAR[b as usize],
+            a as f32 / 255.0,
+        ]
+    }
+}
+
+/// Textures are equal when they are clones of each other.
+impl PartialEq for Texture {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl Eq for Texture {}
+
+impl fmt::Debug for Texture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Texture")
+            .field("id", &self.id())
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("filter", &self.filter())
+            .finish_non_exhaustive()
+    }
+}
+
+impl TextureRegion {
+    pub const fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+impl fmt::Display for TextureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read image: {error}"),
+            Self::Png(error) => write!(f, "invalid PNG image: {error}"),
+            Self::Qoi(error) => write!(f, "invalid QOI image: {error}"),
+            Self::UnknownFormat => write!(f, "image is neither PNG nor QOI"),
+            Self::InvalidSize {
+                width,
+                height,
+                bytes,
+            } => write!(
+                f,
+                "{bytes} bytes do not make a non-empty {width}x{height} RGBA image"
+            ),
+        }
+    }
+}
+
+impl Error for TextureError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Png(error) => Some(error),
+            Self::Qoi(error) => Some(error),
+            Self::UnknownFormat | Self::InvalidSize { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for TextureError {
+    fn from(error: io::Error) -> Self {
+        TextureError::Io(error)
+    }
+}
+
+impl From<png::DecodingError> for TextureError {
+    fn from(error: png::DecodingError) -> Self {
+        TextureError::Png(error)
+    }
+}
+
+impl From<qoi::Error> for TextureError {
+    fn from(error: qoi::Error) -> Self {
+        TextureError::Qoi(error)
+    }
+}
@@ -1,18 +1,22 @@
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-/// A position on the screen as seen by the GPU.
-/// The position has values in range -1.0..1.0.
-/// The color is in RGB format with values in range 0.0..1.0.
-pub(super) struct Vertex {
-    /// Position on the screen with values in the range -1.0..1.0.
-    pub(super) position: [f32; 2],
-    /// Color in the RGB color format where each value is in range 0.0..1.0.
-    pub(super) color: [f32; 3],
+/// A point of a shape as seen by the GPU.
+/// The position is in world units, the `Camera2D` maps it onto the screen.
+/// The color is in linear RGBA format with values in range 0.0..1.0.
+/// Textured shapes multiply the color with the texture sampled at `uv`.
+pub struct Vertex {
+    /// Position in the world.
+    pub position: [f32; 2],
+    /// Color in the linear RGBA color format where each value is in range 0.0..1.0.
+    pub color: [f32; 4],
+    /// Texture coordinates, (0.0, 0.0) is the top-left corner of the texture and (1.0, 1.0)
+    /// its bottom-right corner. Shapes without a texture leave them at zero.
+    pub uv: [f32; 2],
 }
 
 impl Vertex {
-    const ATTRIBS: [wgpu::VertexAttribute; 2] =
-        wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x3];
+    const ATTRIBS: [wgpu::VertexAttribute; 3] =
+        wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x4, 2 => Float32x2];
 
     pub(super) fn desc() -> wgpu::VertexBufferLayout<'static> {
         wgpu::VertexBufferLayout {
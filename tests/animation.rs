@@ -0,0 +1,198 @@
+use std::time::Duration;
+
+use stellar_engine::{
+    animation::{Animation, Clip, Frame, PlayMode},
+    rendering::{Color, SpriteSheet, Texture},
+};
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+/// A clip of four frames `0..4` shown for 100 milliseconds each.
+fn counting(mode: PlayMode) -> Clip<usize> {
+    Clip::uniform("count", 0..4, ms(100)).with_mode(mode)
+}
+
+/// The frame shown after each of `steps` updates of 100 milliseconds.
+fn frames(animation: &mut Animation<usize>, steps: usize) -> Vec<usize> {
+    (0..steps)
+        .map(|_| {
+            animation.update(ms(100));
+            *animation.frame().unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn plays_in_each_mode() {
+    let mut animation = Animation::new().with_clip(counting(PlayMode::Loop));
+    assert!(animation.play("count"));
+    assert_eq!(animation.frame(), Some(&0));
+    assert_eq!(frames(&mut animation, 6), [1, 2, 3, 0, 1, 2]);
+
+    let mut animation = Animation::new().with_clip(counting(PlayMode::PingPong));
+    animation.play("count");
+    assert_eq!(frames(&mut animation, 8), [1, 2, 3, 2, 1, 0, 1, 2]);
+
+    let mut animation = Animation::new().with_clip(counting(PlayMode::Once));
+    animation.play("count");
+    assert_eq!(frames(&mut animation, 3), [1, 2, 3]);
+    assert!(!animation.is_finished());
+    assert_eq!(frames(&mut animation, 2), [3, 3]);
+    assert!(animation.is_finished());
+}
+
+#[test]
+fn carries_time_over_between_frames() {
+    let clip = Clip::new("uneven")
+        .with_frame('a', ms(50))
+        .with_frame('b', ms(200))
+        .with_frame('c', ms(25));
+    let mut animation = Animation::new().with_clip(clip);
+    animation.play("uneven");
+
+    animation.update(ms(40));
+    assert_eq!(animation.frame(), Some(&'a'));
+    animation.update(ms(20));
+    assert_eq!(animation.frame(), Some(&'b'));
+    // 10 milliseconds into b, a long update skips over c and a
+    animation.update(ms(190 + 25 + 50 + 5));
+    assert_eq!(animation.frame_index(), Some(1));
+    animation.update(ms(195 + 3 * 275));
+    assert_eq!(animation.frame(), Some(&'c'));
+}
+
+#[test]
+fn fires_events_when_frames_are_entered() {
+    let clip = counting(PlayMode::Loop)
+        .with_event(0, "start")
+        .with_event(2, "step")
+        .with_event(2, "dust");
+    let mut animation = Animation::new().with_clip(clip);
+    animation.play("count");
+
+    assert_eq!(animation.update(ms(0)), ["start"]);
+    assert!(animation.update(ms(150)).is_empty());
+    assert_eq!(animation.update(ms(50)), ["step", "dust"]);
+    // Looping around enters the first frame again
+    assert_eq!(animation.update(ms(400)), ["start", "step", "dust"]);
+}
+
+#[test]
+fn long_updates_play_one_lap() {
+    let mut animation = Animation::new().with_clip(counting(PlayMode::Loop).with_event(0, "start"));
+    animation.play("count");
+    assert_eq!(animation.update(ms(0)), ["start"]);
+
+    // 41 frames later, after starting over ten times
+    assert_eq!(animation.update(ms(1000 * 400 + 150)), ["start"]);
+    assert_eq!(animation.frame(), Some(&1));
+    // The time into the frame is kept
+    animation.update(ms(49));
+    assert_eq!(animation.frame(), Some(&1));
+    animation.update(ms(1));
+    assert_eq!(animation.frame(), Some(&2));
+
+    // A lap of 0, 1, 2, 3, 2, 1 takes 600 milliseconds
+    let mut animation =
+        Animation::new().with_clip(counting(PlayMode::PingPong).with_event(3, "turn"));
+    animation.play("count");
+    assert_eq!(animation.update(ms(1000 * 600 + 250)), ["turn"]);
+    assert_eq!(animation.frame(), Some(&2));
+    assert_eq!(animation.update(ms(100)), ["turn"]);
+}
+
+#[test]
+fn switches_between_named_clips() {
+    let red = Color::rgb(1.0, 0.0, 0.0);
+    let blue = Color::rgb(0.0, 0.0, 1.0);
+    let mut animation = Animation::new()
+        .with_clip(Clip::uniform("idle", [red], ms(100)))
+        .with_clip(
+            Clip::new("hit")
+                .with(Frame::new(blue, ms(80)).with_event("flash"))
+                .with_frame(red, ms(80))
+                .with_mode(PlayMode::Once),
+        );
+    assert_eq!(animation.frame(), None);
+    assert!(!animation.play("jump"));
+    assert_eq!(animation.playing(), None);
+
+    animation.play("hit");
+    assert_eq!(animation.update(ms(100)), ["flash"]);
+    assert_eq!(animation.frame(), Some(&red));
+    // Playing the same clip again does not restart it
+    animation.play("hit");
+    assert_eq!(animation.frame_index(), Some(1));
+    animation.restart();
+    assert_eq!(animation.frame(), Some(&blue));
+
+    animation.play("idle");
+    assert_eq!(animation.playing(), Some("idle"));
+    animation.update(ms(1000));
+    assert_eq!(animation.frame(), Some(&red));
+    animation.stop();
+    assert_eq!(animation.frame(), None);
+
+    animation.add_clip(Clip::uniform("idle", [blue], ms(100)));
+    assert_eq!(animation.clips().len(), 2);
+    assert_eq!(animation.clip("idle").unwrap().frames[0].value, blue);
+}
+
+#[test]
+fn clips_without_duration_stay_in_place() {
+    let mut animation = Animation::new()
+        .with_clip(Clip::uniform("still", [1, 2], Duration::ZERO))
+        .with_clip(Clip::new("empty"));
+    animation.play("still");
+    animation.update(ms(100));
+    assert_eq!(animation.frame(), Some(&1));
+
+    animation.play("empty");
+    assert!(animation.update(ms(100)).is_empty());
+    assert_eq!(animation.frame(), None);
+}
+
+#[test]
+fn imports_aseprite_tags_as_clips() {
+    let json = r#"{
+        "frames": [
+            { "filename": "0", "frame": { "x": 0, "y": 0, "w": 8, "h": 8 }, "duration": 100 },
+            { "filename": "1", "frame": { "x": 8, "y": 0, "w": 8, "h": 8 }, "duration": 200 },
+            { "filename": "2", "frame": { "x": 16, "y": 0, "w": 8, "h": 8 } },
+            { "filename": "3", "frame": { "x": 24, "y": 0, "w": 8, "h": 8 }, "duration": 50 }
+        ],
+        "meta": {
+            "frameTags": [
+                { "name": "walk", "from": 0, "to": 2, "direction": "forward" },
+                { "name": "back", "from": 1, "to": 3, "direction": "reverse" },
+                { "name": "bob", "from": 0, "to": 1, "direction": "pingpong" },
+                { "name": "sway", "from": 2, "to": 3, "direction": "pingpong_reverse" }
+            ]
+        }
+    }"#;
+    let texture = Texture::from_rgba8(32, 8, vec![255; 32 * 8 * 4]).unwrap();
+    let sheet = SpriteSheet::from_json(json, texture).unwrap();
+    let animation = sheet.animation();
+
+    let indices = |name: &str| -> Vec<usize> {
+        let clip = animation.clip(name).unwrap();
+        clip.frames.iter().map(|frame| frame.value).collect()
+    };
+    assert_eq!(indices("walk"), [0, 1, 2]);
+    assert_eq!(indices("back"), [3, 2, 1]);
+    assert_eq!(indices("sway"), [3, 2]);
+
+    let walk = animation.clip("walk").unwrap();
+    assert_eq!(walk.mode, PlayMode::Loop);
+    assert_eq!(walk.duration(), ms(100 + 200 + 100));
+    assert_eq!(animation.clip("bob").unwrap().mode, PlayMode::PingPong);
+    assert_eq!(animation.clip("sway").unwrap().mode, PlayMode::PingPong);
+
+    let mut animation = animation;
+    animation.play("walk");
+    animation.update(ms(150));
+    let sprite = sheet.sprite(*animation.frame().unwrap(), Default::default());
+    assert_eq!(sprite.region, Some(sheet.frames[1].region));
+}
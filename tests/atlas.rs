@@ -0,0 +1,207 @@
+mod common;
+
+use common::render_software;
+use stellar_engine::{
+    math::Vector2,
+    rendering::{
+        AtlasBuilder, AtlasError, RenderBackend, RenderQueue, Texture, TextureAtlas, TextureFilter,
+        TextureRegion,
+        shapes::{Shape, Sprite},
+    },
+};
+
+/// An image filled with one color.
+fn image(width: u32, height: u32, color: [u8; 4]) -> Texture {
+    Texture::from_rgba8(width, height, color.repeat((width * height) as usize)).unwrap()
+}
+
+fn pixel(texture: &Texture, x: u32, y: u32) -> [u8; 4] {
+    let start = ((y * texture.width() + x) * 4) as usize;
+    texture.pixels()[start..start + 4].try_into().unwrap()
+}
+
+fn overlaps(a: TextureRegion, b: TextureRegion) -> bool {
+    a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+}
+
+#[test]
+fn packs_images_without_overlap() {
+    let mut builder = AtlasBuilder::new().with_padding(2).with_bleed(1);
+    let mut rng = fastrand::Rng::with_seed(7);
+    for i in 0..60 {
+        let color = [i as u8 * 4, 255 - i as u8, 128, 255];
+        builder.add(
+            format!("image {i}"),
+            image(rng.u32(1..40), rng.u32(1..40), color),
+        );
+    }
+    let atlas = builder.build().unwrap();
+    let texture = atlas.texture();
+    assert!(texture.width().is_power_of_two() && texture.height().is_power_of_two());
+
+    let regions: Vec<_> = atlas.regions().collect();
+    assert_eq!(regions.len(), 60);
+    for (i, &(name, region)) in regions.iter().enumerate() {
+        // The bleed and padding must stay inside of the texture too
+        assert!(region.x >= 3 && region.y >= 3, "{name} at {region:?}");
+        assert!(region.x + region.width + 3 <= texture.width());
+        assert!(region.y + region.height + 3 <= texture.height());
+
+        // Grown by the bleed and half of the padding on each side
+        let grow = |region: TextureRegion| {
+            TextureRegion::new(
+                region.x - 2,
+                region.y - 2,
+                region.width + 4,
+                region.height + 4,
+            )
+        };
+        for &(other_name, other) in &regions[i + 1..] {
+            assert!(
+                !overlaps(grow(region), grow(other)),
+                "{name} at {region:?} overlaps {other_name} at {other:?}"
+            );
+        }
+
+        let number: u8 = name["image ".len()..].parse().unwrap();
+        assert_eq!(
+            pixel(texture, region.x, region.y),
+            [number * 4, 255 - number, 128, 255]
+        );
+    }
+}
+
+#[test]
+fn surrounds_images_with_bleed_and_padding() {
+    let mut builder = AtlasBuilder::new().with_padding(1).with_bleed(2);
+    builder.add("red", image(3, 2, [255, 0, 0, 255]));
+    let atlas = builder.build().unwrap();
+    let texture = atlas.texture();
+
+    let region = atlas.region("red").unwrap();
+    assert_eq!(
+        (region.x, region.y, region.width, region.height),
+        (3, 3, 3, 2)
+    );
+    // Bleed repeats the edges up to two pixels out, the padding stays transparent
+    assert_eq!(pixel(texture, 1, 1), [255, 0, 0, 255]);
+    assert_eq!(pixel(texture, 7, 6), [255, 0, 0, 255]);
+    assert_eq!(pixel(texture, 0, 0), [0; 4]);
+    assert_eq!(pixel(texture, 3, 0), [0; 4]);
+    assert_eq!(pixel(texture, 8, 3), [0; 4]);
+}
+
+#[test]
+fn grows_until_the_images_fit() {
+    let mut builder = AtlasBuilder::new().with_padding(0).with_bleed(0);
+    builder.add("wide", image(100, 10, [255; 4]));
+    builder.add("tall", image(10, 100, [255; 4]));
+    let atlas = builder.build().unwrap();
+    assert_eq!(
+        (atlas.texture().width(), atlas.texture().height()),
+        (128, 128)
+    );
+}
+
+#[test]
+fn reports_images_that_do_not_fit() {
+    let mut builder = AtlasBuilder::new().with_max_size(16);
+    builder.add("large", image(15, 15, [255; 4]));
+    assert!(matches!(builder.build(), Err(AtlasError::ImageTooLarge(name)) if name == "large"));
+
+    let mut builder = AtlasBuilder::new().with_max_size(16);
+    for i in 0..5 {
+        builder.add(format!("{i}"), image(6, 6, [255; 4]));
+    }
+    assert!(matches!(
+        builder.build(),
+        Err(AtlasError::OutOfSpace { max_size: 16 })
+    ));
+
+    let mut builder = AtlasBuilder::new();
+    builder.add("twice", image(1, 1, [255; 4]));
+    builder.add("twice", image(1, 1, [255; 4]));
+    assert!(matches!(builder.build(), Err(AtlasError::DuplicateName(_))));
+}
+
+#[test]
+fn builds_empty_atlases() {
+    let atlas = AtlasBuilder::new().build().unwrap();
+    assert_eq!(atlas.regions().count(), 0);
+    assert!(atlas.region("missing").is_none());
+}
+
+fn icons() -> Vec<(&'static str, Texture)> {
+    let checker = (0..16)
+        .flat_map(|i| {
+            if (i % 4 + i / 4) % 2 == 0 {
+                [255, 255, 255, 255]
+            } else {
+                [40, 40, 200, 255]
+            }
+        })
+        .collect();
+    vec![
+        ("checker", Texture::from_rgba8(4, 4, checker).unwrap()),
+        ("red", image(3, 5, [220, 30, 30, 255])),
+        ("green", image(6, 2, [30, 220, 30, 160])),
+    ]
+}
+
+#[test]
+fn sprites_from_an_atlas_share_one_batch() {
+    let mut builder = AtlasBuilder::new();
+    for (name, icon) in icons() {
+        builder.add(name, icon);
+    }
+    let atlas: TextureAtlas = builder.build().unwrap();
+
+    let mut queue = RenderQueue::default();
+    for (i, (name, _)) in icons().into_iter().enumerate() {
+        queue.add(Shape::Sprite(
+            atlas
+                .sprite(name, Vector2::new(i as f32 * 10.0, 0.0))
+                .unwrap(),
+        ));
+    }
+    let mut backend = common::recording::RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+    assert_eq!(backend.batches.len(), 1);
+}
+
+#[test]
+fn atlas_sprites_look_like_separate_textures() {
+    let scale = 4.0;
+    let scene = |queue: &mut RenderQueue, sprite: &dyn Fn(&str, Texture) -> Sprite| {
+        for (i, (name, icon)) in icons().into_iter().enumerate() {
+            let size = Vector2::new(icon.width() as f32, icon.height() as f32) * scale;
+            let center = Vector2::new(i as f32 * 24.0 - 24.0, 0.0);
+            let mut sprite = sprite(name, icon).with_size(size);
+            sprite.center = center;
+            queue.add(Shape::Sprite(sprite));
+        }
+    };
+
+    // The bleed keeps linear filtering from blending in the padding at the edges
+    for filter in [TextureFilter::Nearest, TextureFilter::Linear] {
+        let mut builder = AtlasBuilder::new().with_filter(filter);
+        for (name, icon) in icons() {
+            builder.add(name, icon);
+        }
+        let atlas = builder.build().unwrap();
+
+        let separate = render_software(64, 64, |renderer| {
+            scene(&mut renderer.render_queue, &|_, icon| {
+                Sprite::new(icon.with_filter(filter), Vector2::default())
+            })
+        });
+        let packed = render_software(64, 64, |renderer| {
+            scene(&mut renderer.render_queue, &|name, _| {
+                atlas.sprite(name, Vector2::default()).unwrap()
+            })
+        });
+        for (separate, packed) in separate.pixels.iter().zip(&packed.pixels) {
+            assert!(separate.abs_diff(*packed) <= 1, "{filter:?} atlas differs");
+        }
+    }
+}
@@ -0,0 +1,242 @@
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use common::recording::RecordingBackend;
+use stellar_engine::{
+    math::Vector2,
+    rendering::{
+        BmFontError, GlyphAtlas, RenderBackend, RenderQueue, Texture, TextureFilter, TextureRegion,
+        shapes::{Shape, Text},
+    },
+};
+
+/// A font with a line height of 10 and its baseline 8 pixels below the top of the line.
+/// `A` and `B` are on the first page, `C` on the second.
+const FONT: &str = r#"info face="Pixel Sans" size=10 bold=0 italic=0 charset="" unicode=1 stretchH=100 smooth=0 aa=1 padding=0,0,0,0 spacing=1,1
+common lineHeight=10 base=8 scaleW=32 scaleH=16 pages=2 packed=0
+page id=0 file="pixel_0.png"
+page id=1 file="pixel_1.png"
+chars count=4
+char id=65   x=0     y=0     width=6     height=8     xoffset=0     yoffset=0     xadvance=7     page=0  chnl=15
+char id=66   x=8     y=0     width=5     height=8     xoffset=1     yoffset=0     xadvance=7     page=0  chnl=15
+char id=67   x=0     y=0     width=6     height=6     xoffset=0     yoffset=2     xadvance=7     page=1  chnl=15
+char id=32   x=0     y=0     width=0     height=0     xoffset=0     yoffset=0     xadvance=4     page=0  chnl=15
+kernings count=1
+kerning first=65  second=66  amount=-2
+"#;
+
+fn page(color: [u8; 4]) -> Texture {
+    Texture::from_rgba8(32, 16, color.repeat(32 * 16)).unwrap()
+}
+
+fn pages() -> Vec<Texture> {
+    vec![page([255, 0, 0, 255]), page([0, 0, 255, 255])]
+}
+
+/// `FONT` in the binary format.
+fn binary_font() -> Vec<u8> {
+    fn block(bytes: &mut Vec<u8>, kind: u8, content: &[u8]) {
+        bytes.push(kind);
+        bytes.extend((content.len() as u32).to_le_bytes());
+        bytes.extend(content);
+    }
+
+    let mut bytes = b"BMF\x03".to_vec();
+    let mut info = vec![0; 14];
+    info[0..2].copy_from_slice(&10i16.to_le_bytes());
+    info.extend(b"Pixel Sans\0");
+    block(&mut bytes, 1, &info);
+
+    let mut common = Vec::new();
+    for value in [10u16, 8, 32, 16, 2] {
+        common.extend(value.to_le_bytes());
+    }
+    common.extend([0, 0, 4, 4, 4]);
+    block(&mut bytes, 2, &common);
+    block(&mut bytes, 3, b"pixel_0.png\0pixel_1.png\0");
+
+    let mut chars = Vec::new();
+    for (id, x, y, width, height, xoffset, yoffset, xadvance, page) in [
+        (65u32, 0u16, 0u16, 6u16, 8u16, 0i16, 0i16, 7i16, 0u8),
+        (66, 8, 0, 5, 8, 1, 0, 7, 0),
+        (67, 0, 0, 6, 6, 0, 2, 7, 1),
+        (32, 0, 0, 0, 0, 0, 0, 4, 0),
+    ] {
+        chars.extend(id.to_le_bytes());
+        for value in [x, y, width, height] {
+            chars.extend(value.to_le_bytes());
+        }
+        for value in [xoffset, yoffset, xadvance] {
+            chars.extend(value.to_le_bytes());
+        }
+        chars.extend([page, 15]);
+    }
+    block(&mut bytes, 4, &chars);
+
+    let mut kernings = Vec::new();
+    kernings.extend(65u32.to_le_bytes());
+    kernings.extend(66u32.to_le_bytes());
+    kernings.extend((-2i16).to_le_bytes());
+    block(&mut bytes, 5, &kernings);
+    bytes
+}
+
+fn pixel(texture: &Texture, x: u32, y: u32) -> [u8; 4] {
+    let start = ((y * texture.width() + x) * 4) as usize;
+    texture.pixels()[start..start + 4].try_into().unwrap()
+}
+
+#[test]
+fn reads_text_fonts() {
+    let font = GlyphAtlas::from_bmfont(FONT.as_bytes(), pages()).unwrap();
+    assert_eq!(font.size(), 10.0);
+    assert_eq!(font.line_height(), 10.0);
+    assert_eq!((font.ascent(), font.descent()), (8.0, -2.0));
+
+    // Offsets are from the baseline, not the top of the line
+    let b = font.glyph('B').unwrap();
+    assert_eq!(b.offset, Vector2::new(1.0, -8.0));
+    assert_eq!(b.advance, 7.0);
+    assert_eq!(font.glyph('C').unwrap().offset, Vector2::new(0.0, -6.0));
+    assert_eq!(font.glyph(' ').unwrap().region, None);
+    assert!(font.glyph('D').is_none());
+
+    assert_eq!(font.kerning('A', 'B'), -2.0);
+    assert_eq!(font.kerning('B', 'A'), 0.0);
+}
+
+#[test]
+fn combines_pages_into_one_texture() {
+    let font = GlyphAtlas::from_bmfont(FONT.as_bytes(), pages()).unwrap();
+    let texture = font.texture();
+
+    let a = font.glyph('A').unwrap().region.unwrap();
+    let c = font.glyph('C').unwrap().region.unwrap();
+    assert_eq!((a.width, a.height, c.width, c.height), (6, 8, 6, 6));
+    assert_eq!(pixel(texture, a.x, a.y), [255, 0, 0, 255]);
+    assert_eq!(pixel(texture, c.x + 5, c.y + 5), [0, 0, 255, 255]);
+
+    // A single page is used without copying
+    let single = format!(
+        "{}\nchar id=68 x=0 y=0 width=2 height=2 xoffset=0 yoffset=0 xadvance=3 page=0 chnl=15",
+        FONT.replace("page=1", "page=0")
+    );
+    let only_page = page([255; 4]).with_filter(TextureFilter::Nearest);
+    let font = GlyphAtlas::from_bmfont(single.as_bytes(), vec![only_page.clone()]).unwrap();
+    assert_eq!(font.texture(), &only_page);
+    assert_eq!(
+        font.glyph('D').unwrap().region,
+        Some(TextureRegion::new(0, 0, 2, 2))
+    );
+}
+
+#[test]
+fn binary_fonts_match_text_fonts() {
+    let text = GlyphAtlas::from_bmfont(FONT.as_bytes(), pages()).unwrap();
+    let binary = GlyphAtlas::from_bmfont(&binary_font(), pages()).unwrap();
+
+    assert_eq!(binary.size(), text.size());
+    assert_eq!(binary.ascent(), text.ascent());
+    for character in ['A', 'B', 'C', ' '] {
+        assert_eq!(binary.glyph(character), text.glyph(character));
+    }
+    assert_eq!(binary.kerning('A', 'B'), -2.0);
+}
+
+#[test]
+fn lays_out_strings_into_quads() {
+    let font = GlyphAtlas::from_bmfont(FONT.as_bytes(), pages())
+        .unwrap()
+        .with_filter(TextureFilter::Nearest);
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Text(Text::new(
+        "AB C\nA",
+        font.clone(),
+        Vector2::new(0.0, 0.0),
+    )));
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    assert_eq!(backend.vertices.len(), 4 * 4);
+    assert_eq!(backend.batches.len(), 1);
+    assert_eq!(backend.batches[0].texture.as_ref(), Some(font.texture()));
+
+    // Top-left corners: B moves back by the kerning, C sits 2 pixels lower,
+    // the second line starts one line height down
+    let corners: Vec<_> = backend
+        .vertices
+        .chunks_exact(4)
+        .map(|quad| quad[0].position)
+        .collect();
+    assert_eq!(
+        corners,
+        [[0.0, 0.0], [6.0, 0.0], [16.0, -2.0], [0.0, -10.0]]
+    );
+}
+
+#[test]
+fn loads_pages_next_to_the_file() {
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("bmfont");
+    fs::create_dir_all(&directory).unwrap();
+    for name in ["pixel_0.png", "pixel_1.png"] {
+        let mut image = Vec::new();
+        let mut encoder = png::Encoder::new(&mut image, 32, 16);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder
+            .write_header()
+            .unwrap()
+            .write_image_data(&[255; 32 * 16 * 4])
+            .unwrap();
+        fs::write(directory.join(name), image).unwrap();
+    }
+    fs::write(directory.join("pixel.fnt"), FONT).unwrap();
+    fs::write(directory.join("pixel_binary.fnt"), binary_font()).unwrap();
+
+    for name in ["pixel.fnt", "pixel_binary.fnt"] {
+        let font = GlyphAtlas::load_bmfont(directory.join(name)).unwrap();
+        assert_eq!(font.line_height(), 10.0);
+    }
+
+    fs::remove_file(directory.join("pixel_1.png")).unwrap();
+    assert!(matches!(
+        GlyphAtlas::load_bmfont(directory.join("pixel.fnt")),
+        Err(BmFontError::Texture(_))
+    ));
+}
+
+#[test]
+fn reports_invalid_fonts() {
+    let load = |bytes: &[u8]| GlyphAtlas::from_bmfont(bytes, pages());
+
+    assert!(matches!(
+        load(b"info face=\"x\"\n"),
+        Err(BmFontError::MissingCommon)
+    ));
+    assert!(matches!(
+        load(b"common lineHeight=10 base=8\nchar id=65 x=0"),
+        Err(BmFontError::InvalidLine(2))
+    ));
+    assert!(matches!(
+        load(b"common lineHeight=ten base=8"),
+        Err(BmFontError::InvalidLine(1))
+    ));
+    assert!(matches!(
+        load(FONT.replace("page=1", "page=2").as_bytes()),
+        Err(BmFontError::MissingPage(67))
+    ));
+    assert!(matches!(
+        load(FONT.replace("x=8 ", "x=30").as_bytes()),
+        Err(BmFontError::CharOutOfBounds(66))
+    ));
+
+    let binary = binary_font();
+    assert!(matches!(
+        load(&binary[..binary.len() - 3]),
+        Err(BmFontError::Truncated)
+    ));
+    assert!(matches!(
+        load(b"BMF\x02"),
+        Err(BmFontError::UnsupportedVersion(2))
+    ));
+}
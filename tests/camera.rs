@@ -0,0 +1,99 @@
+use std::f32::consts::FRAC_PI_2;
+
+use stellar_engine::{math::Vector2, rendering::Camera2D};
+
+fn assert_close(actual: Vector2<f32>, expected: Vector2<f32>) {
+    assert!(
+        (actual - expected).length() < 1e-3,
+        "{actual:?} != {expected:?}"
+    );
+}
+
+#[test]
+fn default_camera_maps_pixels_to_world_units() {
+    let camera = Camera2D::new(800.0, 600.0);
+
+    assert_close(
+        camera.screen_to_world(Vector2::new(400.0, 300.0)),
+        Vector2::new(0.0, 0.0),
+    );
+    assert_close(
+        camera.screen_to_world(Vector2::new(0.0, 0.0)),
+        Vector2::new(-400.0, 300.0),
+    );
+    assert_close(
+        camera.screen_to_world(Vector2::new(500.0, 400.0)),
+        Vector2::new(100.0, -100.0),
+    );
+}
+
+#[test]
+fn pan_zoom_and_rotation() {
+    let camera = Camera2D {
+        position: Vector2::new(10.0, 20.0),
+        zoom: 2.0,
+        rotation: FRAC_PI_2,
+        ..Camera2D::new(800.0, 600.0)
+    };
+
+    assert_close(
+        camera.world_to_screen(Vector2::new(10.0, 20.0)),
+        Vector2::new(400.0, 300.0),
+    );
+    // Rotating the camera counter-clockwise makes world +y point to the screen right
+    assert_close(
+        camera.world_to_screen(Vector2::new(10.0, 30.0)),
+        Vector2::new(420.0, 300.0),
+    );
+}
+
+#[test]
+fn screen_to_world_inverts_world_to_screen() {
+    let camera = Camera2D {
+        position: Vector2::new(-35.0, 12.5),
+        zoom: 0.75,
+        rotation: 0.4,
+        ..Camera2D::new(640.0, 480.0)
+    };
+
+    for world in [
+        Vector2::new(0.0, 0.0),
+        Vector2::new(-120.0, 33.0),
+        Vector2::new(250.0, -80.0),
+    ] {
+        assert_close(camera.screen_to_world(camera.world_to_screen(world)), world);
+    }
+}
+
+#[test]
+fn view_projection_matches_world_to_screen() {
+    let camera = Camera2D {
+        position: Vector2::new(5.0, -7.0),
+        zoom: 1.5,
+        rotation: -0.8,
+        ..Camera2D::new(300.0, 200.0)
+    };
+    let matrix = camera.view_projection();
+    let world = Vector2::new(42.0, 17.0);
+
+    let clip_x = matrix[0][0] * world.x + matrix[1][0] * world.y + matrix[3][0];
+    let clip_y = matrix[0][1] * world.x + matrix[1][1] * world.y + matrix[3][1];
+    let screen = Vector2::new((clip_x + 1.0) * 150.0, (1.0 - clip_y) * 100.0);
+
+    assert_close(screen, camera.world_to_screen(world));
+}
+
+#[test]
+fn zero_zoom_keeps_conversions_finite() {
+    for zoom in [0.0, -0.0, f32::NAN] {
+        let camera = Camera2D {
+            zoom,
+            ..Camera2D::new(800.0, 600.0)
+        };
+
+        let world = camera.screen_to_world(Vector2::new(500.0, 400.0));
+        assert!(world.x.is_finite() && world.y.is_finite(), "zoom {zoom}");
+        assert_eq!(camera.pixels_per_unit(), Camera2D::MIN_ZOOM);
+        assert_close(camera.world_to_screen(world), Vector2::new(500.0, 400.0));
+    }
+}
@@ -0,0 +1,79 @@
+use stellar_engine::rendering::{Color, ParseColorError};
+
+fn assert_close(actual: [f32; 3], expected: [f32; 3]) {
+    for (a, e) in actual.iter().zip(expected) {
+        assert!(
+            (a - e).abs() < 0.01 * e.abs().max(1.0),
+            "{actual:?} != {expected:?}"
+        );
+    }
+}
+
+#[test]
+fn hex_round_trip() {
+    for hex in ["#000000", "#ffffff", "#3cb371", "#12345678"] {
+        assert_eq!(Color::from_hex(hex).unwrap().to_hex(), hex);
+    }
+}
+
+#[test]
+fn hex_short_forms() {
+    assert_eq!(
+        Color::from_hex("#f80").unwrap(),
+        Color::from_hex("#ff8800").unwrap()
+    );
+    assert_eq!(
+        Color::from_hex("f808").unwrap(),
+        Color::from_hex("ff880088").unwrap()
+    );
+}
+
+#[test]
+fn hex_is_srgb_encoded() {
+    assert_eq!(
+        Color::from_hex("#808080").unwrap().to_rgba8(),
+        [128, 128, 128, 255]
+    );
+    assert!((Color::from_hex("#808080").unwrap().r - Color::GRAY.r).abs() < 1e-6);
+}
+
+#[test]
+fn invalid_hex() {
+    assert_eq!(
+        Color::from_hex("#12345"),
+        Err(ParseColorError::InvalidLength(5))
+    );
+    assert_eq!(Color::from_hex("#12g"), Err(ParseColorError::InvalidDigit));
+    assert_eq!(Color::from_hex("+1f"), Err(ParseColorError::InvalidDigit));
+}
+
+#[test]
+fn hsv_and_hsl() {
+    assert_eq!(Color::from_hsv(0.0, 1.0, 1.0).to_rgba8(), [255, 0, 0, 255]);
+    assert_eq!(
+        Color::from_hsv(120.0, 1.0, 1.0).to_rgba8(),
+        [0, 255, 0, 255]
+    );
+    assert_eq!(
+        Color::from_hsl(240.0, 1.0, 0.5).to_rgba8(),
+        [0, 0, 255, 255]
+    );
+    assert_eq!(
+        Color::from_hsl(-60.0, 1.0, 0.5),
+        Color::from_hsl(300.0, 1.0, 0.5)
+    );
+
+    let color = Color::from_hex("#3cb371").unwrap();
+    let [h, s, v] = color.to_hsv();
+    assert_eq!(Color::from_hsv(h, s, v).to_hex(), "#3cb371");
+    let [h, s, l] = color.to_hsl();
+    assert_eq!(Color::from_hsl(h, s, l).to_hex(), "#3cb371");
+    assert_close(color.to_hsl(), [146.7, 0.498, 0.469]);
+}
+
+#[test]
+fn named_constants_match_their_srgb_codes() {
+    assert_eq!(Color::ORANGE.to_hex(), "#ffa500");
+    assert_eq!(Color::PURPLE.to_hex(), "#800080");
+    assert_eq!(Color::TRANSPARENT.to_rgba8()[3], 0);
+}
@@ -0,0 +1,12 @@
+//! Comparison of results that carry floating point error.
+
+use stellar_engine::math::Vector2;
+
+/// Checks that neither coordinate of `actual` is further than `tolerance` from `expected`.
+#[track_caller]
+pub fn assert_vector_close(actual: Vector2<f32>, expected: Vector2<f32>, tolerance: f32) {
+    assert!(
+        actual.approx_eq(expected, tolerance),
+        "{actual:?} is not {expected:?}"
+    );
+}
@@ -0,0 +1,195 @@
+//! Golden-image testing.
+//!
+//! A scene is rendered into an `Image` and compared against a reference PNG stored in `tests/golden`.
+//! Run the tests with `UPDATE_GOLDEN=1` to (re)write the references from the current output.
+//! On a mismatch the actual image and a diff image are written to the cargo target tmp directory.
+//!
+//! Tests of the geometry itself can draw into `recording::RecordingBackend` instead,
+//! and compare points with `approx::assert_vector_close`.
+
+#![allow(dead_code)]
+
+pub mod approx;
+pub mod recording;
+
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use stellar_engine::rendering::{RendererState, SoftwareRenderer};
+
+/// An RGBA image with 8 bits per channel, stored row by row from the top-left corner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let start = ((y * self.width + x) * 4) as usize;
+        self.pixels[start..start + 4].try_into().unwrap()
+    }
+
+    pub fn load_png(path: &Path) -> anyhow::Result<Self> {
+        let decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+        let mut reader = decoder.read_info()?;
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap_or_default()];
+        let info = reader.next_frame(&mut pixels)?;
+
+        anyhow::ensure!(
+            info.color_type == png::ColorType::Rgba && info.bit_depth == png::BitDepth::Eight,
+            "{} is not an 8-bit RGBA image",
+            path.display()
+        );
+        pixels.truncate(info.buffer_size());
+
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+
+    pub fn save_png(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut encoder =
+            png::Encoder::new(BufWriter::new(File::create(path)?), self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&self.pixels)?;
+
+        Ok(())
+    }
+}
+
+/// Renders a frame with the CPU rasterizer after letting `setup` fill the renderer.
+pub fn render_software(
+    width: u32,
+    height: u32,
+    setup: impl FnOnce(&mut SoftwareRenderer),
+) -> Image {
+    let mut renderer = SoftwareRenderer::new(width, height);
+    setup(&mut renderer);
+    renderer.render();
+
+    Image {
+        width,
+        height,
+        pixels: renderer.read_pixels(),
+    }
+}
+
+/// Renders a frame with a headless wgpu renderer after letting `setup` fill the renderer.
+///
+/// Returns `None` when wgpu finds no adapter at all, so tests can be skipped on machines
+/// without one. Any other failure panics, so a broken pipeline still fails the test.
+pub fn render_headless(
+    width: u32,
+    height: u32,
+    setup: impl FnOnce(&mut RendererState),
+) -> Option<Image> {
+    let mut renderer = match pollster::block_on(RendererState::new_headless(width, height)) {
+        Ok(renderer) => renderer,
+        Err(error) if error.downcast_ref::<wgpu::RequestAdapterError>().is_some() => {
+            eprintln!("skipping, no fallback adapter available: {error}");
+            return None;
+        }
+        Err(error) => panic!("creating the headless renderer failed: {error:?}"),
+    };
+    setup(&mut renderer);
+    renderer.render().unwrap();
+
+    Some(Image {
+        width,
+        height,
+        pixels: renderer.read_pixels().unwrap(),
+    })
+}
+
+/// Compares `actual` against the reference image `tests/golden/<name>.png`.
+///
+/// Two pixels match when none of their channels differ by more than `tolerance`.
+///
+/// # Panics
+/// Panics if the reference is missing, has a different size or any pixel does not match.
+pub fn assert_golden(name: &str, actual: &Image, tolerance: u8) {
+    let reference_path = golden_dir().join(format!("{name}.png"));
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        actual.save_png(&reference_path).unwrap();
+        return;
+    }
+
+    let expected = Image::load_png(&reference_path).unwrap_or_else(|error| {
+        panic!(
+            "failed to load reference image {}: {error}\nrun the tests with UPDATE_GOLDEN=1 to create it",
+            reference_path.display()
+        )
+    });
+    assert_eq!(
+        (expected.width, expected.height),
+        (actual.width, actual.height),
+        "size of `{name}` differs from the reference"
+    );
+
+    let (diff, mismatched) = diff_images(&expected, actual, tolerance);
+    if mismatched > 0 {
+        let output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("golden");
+        let actual_path = output_dir.join(format!("{name}.actual.png"));
+        let diff_path = output_dir.join(format!("{name}.diff.png"));
+        actual.save_png(&actual_path).unwrap();
+        diff.save_png(&diff_path).unwrap();
+
+        panic!(
+            "{mismatched} pixels of `{name}` differ from the reference by more than {tolerance}\n\
+             actual: {}\ndiff: {}",
+            actual_path.display(),
+            diff_path.display()
+        );
+    }
+}
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+/// Builds an image with mismatched pixels in red and matching pixels as a dimmed grayscale
+/// of the expected image. Also returns the number of mismatched pixels.
+fn diff_images(expected: &Image, actual: &Image, tolerance: u8) -> (Image, usize) {
+    let mut mismatched = 0;
+    let pixels = expected
+        .pixels
+        .chunks_exact(4)
+        .zip(actual.pixels.chunks_exact(4))
+        .flat_map(|(expected, actual)| {
+            let matches = expected
+                .iter()
+                .zip(actual)
+                .all(|(a, b)| a.abs_diff(*b) <= tolerance);
+
+            if matches {
+                let gray =
+                    ((expected[0] as u32 + expected[1] as u32 + expected[2] as u32) / 12) as u8;
+                [gray, gray, gray, 255]
+            } else {
+                mismatched += 1;
+                [255, 0, 0, 255]
+            }
+        })
+        .collect();
+
+    let diff = Image {
+        width: expected.width,
+        height: expected.height,
+        pixels,
+    };
+
+    (diff, mismatched)
+}
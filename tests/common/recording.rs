@@ -0,0 +1,90 @@
+use std::{convert::Infallible, ops::Range};
+
+use stellar_engine::{
+    math::Vector2,
+    rendering::{DrawBatch, RenderBackend, RenderBuffers, RenderQueue, Vertex},
+};
+
+/// Backend that only records what it was asked to draw.
+#[derive(Default)]
+pub struct RecordingBackend {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+    pub batches: Vec<DrawBatch>,
+    pub revision: u64,
+    pub previous_revision: Option<u64>,
+    pub dirty_vertices: Vec<Range<u32>>,
+    pub dirty_indices: Vec<Range<u32>>,
+    /// Scale reported to the queue, 1.0 when left at zero.
+    pub zoom: f32,
+}
+
+impl RecordingBackend {
+    /// x of the first vertex drawn.
+    pub fn first_x(&self) -> Option<f32> {
+        self.vertices.first().map(|vertex| vertex.position[0])
+    }
+
+    /// Corners of each drawn triangle.
+    pub fn triangles(&self) -> impl Iterator<Item = [[f32; 2]; 3]> {
+        self.indices
+            .chunks_exact(3)
+            .map(|triangle| [0, 1, 2].map(|i| self.vertices[triangle[i] as usize].position))
+    }
+
+    /// Smallest and largest corner of the box around all drawn vertices.
+    pub fn bounds(&self) -> (Vector2<f32>, Vector2<f32>) {
+        self.vertices
+            .iter()
+            .map(|vertex| Vector2::from(vertex.position))
+            .fold(
+                (
+                    Vector2::new(f32::MAX, f32::MAX),
+                    Vector2::new(f32::MIN, f32::MIN),
+                ),
+                |(min, max), point| (min.min(point), max.max(point)),
+            )
+    }
+
+    #[track_caller]
+    pub fn assert_counter_clockwise(&self) {
+        for [a, b, c] in self.triangles() {
+            let area = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+            assert!(
+                area > 0.0,
+                "triangle {:?} is not counter-clockwise",
+                [a, b, c]
+            );
+        }
+    }
+}
+
+/// Draws `queue` into a new backend and returns what it recorded.
+pub fn draw(queue: &mut RenderQueue) -> RecordingBackend {
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(queue);
+    backend
+}
+
+impl RenderBackend for RecordingBackend {
+    type Error = Infallible;
+
+    fn target_size(&self) -> (u32, u32) {
+        (1, 1)
+    }
+
+    fn pixels_per_unit(&self) -> f32 {
+        if self.zoom == 0.0 { 1.0 } else { self.zoom }
+    }
+
+    fn draw(&mut self, buffers: &RenderBuffers) -> Result<(), Self::Error> {
+        self.vertices = buffers.vertices().to_vec();
+        self.indices = buffers.indices().to_vec();
+        self.batches = buffers.batches().to_vec();
+        self.revision = buffers.revision();
+        self.previous_revision = buffers.previous_revision();
+        self.dirty_vertices = buffers.dirty_vertices().to_vec();
+        self.dirty_indices = buffers.dirty_indices().to_vec();
+        Ok(())
+    }
+}
@@ -0,0 +1,623 @@
+mod common;
+
+use common::{assert_golden, render_headless, render_software};
+use stellar_engine::{
+    math::Vector2,
+    rendering::{
+        Background, BlendMode, Camera2D, Color, DrawParams, Font, RenderQueue, Texture,
+        TextureFilter, TextureRegion,
+        shapes::{
+            Arc, Circle, Ellipse, FillRule, Line, LineCap, LineJoin, Path, Polygon, Polyline, Quad,
+            Sector, Shape, Sprite, Stroke, Tessellation, Text, TextAlign, Triangle,
+        },
+    },
+};
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 64;
+
+#[test]
+fn quad_square() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        renderer
+            .render_queue
+            .add(Shape::Quad(Quad::square(Vector2::new(0.0, 0.0), 32.0)));
+    });
+
+    assert_golden("quad_square", &image, 0);
+}
+
+#[test]
+fn quad_square_off_center() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        renderer
+            .render_queue
+            .add(Shape::Quad(Quad::square(Vector2::new(-10.5, 7.25), 21.0)));
+    });
+
+    assert_golden("quad_square_off_center", &image, 0);
+}
+
+#[test]
+fn triangle() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        renderer.render_queue.add(Shape::Triangle(Triangle::new([
+            Vector2::new(-25.0, -20.0),
+            Vector2::new(25.0, -20.0),
+            Vector2::new(0.0, 25.0),
+        ])));
+    });
+
+    assert_golden("triangle", &image, 0);
+}
+
+#[test]
+fn clockwise_triangle_is_culled() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        renderer.render_queue.add(Shape::Triangle(Triangle::new([
+            Vector2::new(-25.0, -20.0),
+            Vector2::new(0.0, 25.0),
+            Vector2::new(25.0, -20.0),
+        ])));
+    });
+
+    assert_golden("empty", &image, 0);
+}
+
+#[test]
+fn quad_with_color() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        renderer.render_queue.add(Shape::Quad(
+            Quad::square(Vector2::new(0.0, 0.0), 40.0)
+                .with_color(Color::from_hex("#3cb371").unwrap()),
+        ));
+    });
+
+    assert_golden("quad_with_color", &image, 0);
+}
+
+#[test]
+fn triangle_with_vertex_colors() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        renderer.render_queue.add(Shape::Triangle(
+            Triangle::new([
+                Vector2::new(-25.0, -20.0),
+                Vector2::new(25.0, -20.0),
+                Vector2::new(0.0, 25.0),
+            ])
+            .with_vertex_colors([Color::RED, Color::GREEN, Color::BLUE]),
+        ));
+    });
+
+    assert_golden("triangle_with_vertex_colors", &image, 0);
+}
+
+/// Draws a white and a black stripe and a translucent shape over them with each blend mode.
+fn blend_modes_scene(queue: &mut RenderQueue) {
+    queue.add(Shape::Quad(
+        Quad::new([
+            Vector2::new(-32.0, 32.0),
+            Vector2::new(-32.0, 0.0),
+            Vector2::new(32.0, 0.0),
+            Vector2::new(32.0, 32.0),
+        ])
+        .with_color(Color::WHITE),
+    ));
+
+    let color = Color::from_hex("#ff800080").unwrap();
+    for (i, blend_mode) in BlendMode::ALL.into_iter().enumerate() {
+        let x = -24.0 + i as f32 * 16.0;
+        let color = match blend_mode {
+            BlendMode::PremultipliedAlpha => Color::rgba(
+                color.r * color.a,
+                color.g * color.a,
+                color.b * color.a,
+                color.a,
+            ),
+            _ => color,
+        };
+        queue.add_with(
+            Shape::Quad(Quad::square(Vector2::new(x, 0.0), 12.0).with_color(color)),
+            DrawParams::default().with_blend_mode(blend_mode),
+        );
+    }
+}
+
+#[test]
+fn blend_modes() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        blend_modes_scene(&mut renderer.render_queue)
+    });
+
+    assert_golden("blend_modes", &image, 0);
+}
+
+#[test]
+fn layers_are_drawn_from_lowest() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        let queue = &mut renderer.render_queue;
+        queue.add_with(
+            Shape::Quad(Quad::square(Vector2::new(6.0, 6.0), 30.0).with_color(Color::BLUE)),
+            DrawParams::default().with_layer(1),
+        );
+        queue.add(Shape::Quad(
+            Quad::square(Vector2::new(-6.0, -6.0), 30.0).with_color(Color::RED),
+        ));
+        queue.add(Shape::Quad(
+            Quad::square(Vector2::new(0.0, 0.0), 10.0).with_color(Color::GREEN.with_alpha(0.5)),
+        ));
+    });
+
+    assert_golden("layers", &image, 0);
+}
+
+#[test]
+fn headless_matches_software() {
+    let background = Background::RadialGradient {
+        center: Color::WHITE,
+        edge: Color::BLACK,
+    };
+    let camera = Camera2D {
+        position: Vector2::new(3.0, -2.0),
+        zoom: 1.25,
+        rotation: 0.2,
+        ..Camera2D::new(WIDTH as f32, HEIGHT as f32)
+    };
+    let Some(gpu) = render_headless(WIDTH, HEIGHT, |renderer| {
+        renderer.background = background;
+        renderer.camera = camera;
+        blend_modes_scene(&mut renderer.render_queue)
+    }) else {
+        return;
+    };
+    let cpu = render_software(WIDTH, HEIGHT, |renderer| {
+        renderer.background = background;
+        renderer.camera = camera;
+        blend_modes_scene(&mut renderer.render_queue)
+    });
+
+    // The GPU blends against the 8-bit target, so allow a small rounding difference
+    for (gpu, cpu) in gpu.pixels.iter().zip(&cpu.pixels) {
+        assert!(gpu.abs_diff(*cpu) <= 2, "GPU and CPU output differ");
+    }
+}
+
+#[test]
+fn vertical_gradient_background() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        renderer.background = Background::VerticalGradient {
+            top: Color::from_hex("#87ceeb").unwrap(),
+            bottom: Color::from_hex("#191970").unwrap(),
+        };
+        renderer
+            .render_queue
+            .add(Shape::Quad(Quad::square(Vector2::new(0.0, 0.0), 16.0)));
+    });
+
+    assert_golden("vertical_gradient_background", &image, 0);
+}
+
+#[test]
+fn radial_gradient_background() {
+    let image = render_software(WIDTH, HEIGHT / 2, |renderer| {
+        renderer.background = Background::RadialGradient {
+            center: Color::WHITE,
+            edge: Color::BLACK,
+        };
+    });
+
+    assert_golden("radial_gradient_background", &image, 0);
+}
+
+#[test]
+fn kept_background_shows_previous_frame() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        renderer
+            .render_queue
+            .add(Shape::Quad(Quad::square(Vector2::new(-12.0, 0.0), 16.0)));
+        renderer.render();
+
+        renderer.background = Background::Keep;
+        renderer.render_queue.clear();
+        renderer.render_queue.add(Shape::Quad(
+            Quad::square(Vector2::new(12.0, 0.0), 16.0).with_color(Color::YELLOW),
+        ));
+    });
+
+    assert_golden("kept_background", &image, 0);
+}
+
+#[test]
+fn camera_pan_zoom_rotation() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        renderer.camera.position = Vector2::new(100.0, 0.0);
+        renderer.camera.zoom = 2.0;
+        renderer.camera.rotation = 0.3;
+        renderer.render_queue.add(Shape::Quad(
+            Quad::square(Vector2::new(100.0, 0.0), 12.0).with_color(Color::CYAN),
+        ));
+        renderer
+            .render_queue
+            .add(Shape::Quad(Quad::square(Vector2::new(110.0, 8.0), 6.0)));
+    });
+
+    assert_golden("camera", &image, 0);
+}
+
+#[test]
+fn headless_draws_past_u16_vertices() {
+    // 20,000 quads need 80,000 vertices, more than a 16-bit index can address
+    let scene = |queue: &mut RenderQueue| {
+        for i in 0..20_000 {
+            let x = (i % 64) as f32 - 31.5;
+            let y = (i / 64 % 64) as f32 - 31.5;
+            let color = Color::from_hsv(i as f32 * 0.05, 0.8, 1.0);
+            queue.add(Shape::Quad(
+                Quad::square(Vector2::new(x, y), 1.0).with_color(color),
+            ));
+        }
+    };
+    let Some(gpu) = render_headless(WIDTH, HEIGHT, |renderer| scene(&mut renderer.render_queue))
+    else {
+        return;
+    };
+    let cpu = render_software(WIDTH, HEIGHT, |renderer| scene(&mut renderer.render_queue));
+
+    for (gpu, cpu) in gpu.pixels.iter().zip(&cpu.pixels) {
+        assert!(gpu.abs_diff(*cpu) <= 2, "GPU and CPU output differ");
+    }
+}
+
+#[test]
+fn headless_uploads_changed_shapes() {
+    let Some(gpu) = render_headless(WIDTH, HEIGHT, |renderer| {
+        let queue = &mut renderer.render_queue;
+        queue.add(Shape::Quad(Quad::square(Vector2::new(-16.0, 0.0), 16.0)));
+        let moved = queue.add(Shape::Quad(Quad::square(Vector2::new(16.0, 0.0), 16.0)));
+        renderer.render().unwrap();
+
+        // Only the vertices of `moved` are written for the second frame
+        if let Some(Shape::Quad(quad)) = renderer.render_queue.get_mut(moved) {
+            *quad = Quad::square(Vector2::new(16.0, 12.0), 16.0).with_color(Color::GREEN);
+        }
+    }) else {
+        return;
+    };
+    let cpu = render_software(WIDTH, HEIGHT, |renderer| {
+        let queue = &mut renderer.render_queue;
+        queue.add(Shape::Quad(Quad::square(Vector2::new(-16.0, 0.0), 16.0)));
+        queue.add(Shape::Quad(
+            Quad::square(Vector2::new(16.0, 12.0), 16.0).with_color(Color::GREEN),
+        ));
+    });
+
+    assert_eq!(gpu.pixels, cpu.pixels, "GPU and CPU output differ");
+}
+
+#[test]
+fn curved_shapes() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        let queue = &mut renderer.render_queue;
+        queue.add(Shape::Circle(
+            Circle::new(Vector2::new(-16.0, 16.0), 12.0).with_gradient(Color::WHITE, Color::BLUE),
+        ));
+        queue.add(Shape::Ellipse(
+            Ellipse::new(Vector2::new(16.0, 16.0), Vector2::new(14.0, 6.0))
+                .rotated(0.5)
+                .with_color(Color::GREEN),
+        ));
+        queue.add(Shape::Arc(
+            Arc::new(Vector2::new(-16.0, -16.0), 10.0, 4.0, 0.0, 4.5)
+                .with_gradient(Color::YELLOW, Color::RED),
+        ));
+        // Clockwise sweep and a coarse fixed tessellation
+        queue.add(Shape::Sector(
+            Sector::new(Vector2::new(16.0, -16.0), 12.0, 1.0, -2.0)
+                .with_color(Color::CYAN)
+                .with_tessellation(Tessellation::Segments(8)),
+        ));
+    });
+
+    assert_golden("curved_shapes", &image, 0);
+}
+
+#[test]
+fn transformed_shapes() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        let queue = &mut renderer.render_queue;
+        queue.add(Shape::Quad(
+            Quad::rect(Vector2::new(-14.0, 14.0), Vector2::new(20.0, 8.0))
+                .rotated(0.6)
+                .with_color(Color::GREEN),
+        ));
+        // Scaled unevenly into an ellipse and moved
+        let circle = Circle::new(Vector2::new(0.0, 0.0), 6.0).with_color(Color::BLUE);
+        let transform = circle
+            .transform
+            .with_scale(Vector2::new(2.0, 1.0))
+            .with_position(Vector2::new(14.0, 14.0));
+        queue.add(Shape::Circle(circle.with_transform(transform)));
+        // Mirrored, yet still facing the camera
+        let triangle = Triangle::new([
+            Vector2::new(-24.0, -24.0),
+            Vector2::new(-4.0, -24.0),
+            Vector2::new(-4.0, -4.0),
+        ])
+        .with_vertex_colors([Color::RED, Color::YELLOW, Color::WHITE]);
+        let transform = triangle.transform.with_scale(Vector2::new(-1.0, 1.0));
+        queue.add(Shape::Triangle(triangle.with_transform(transform)));
+        queue.add(Shape::Quad(
+            Quad::square(Vector2::new(16.0, -16.0), 12.0)
+                .rotated(std::f32::consts::FRAC_PI_4)
+                .with_color(Color::CYAN),
+        ));
+    });
+
+    assert_golden("transformed_shapes", &image, 0);
+}
+
+#[test]
+fn concave_polygon_with_hole() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        let outline = [
+            [-28.0, -24.0],
+            [28.0, -24.0],
+            [28.0, 26.0],
+            [0.0, 4.0],
+            [-28.0, 26.0],
+        ];
+        let hole = [[-6.0, -16.0], [-14.0, -8.0], [-6.0, 0.0], [2.0, -8.0]];
+        let to_points =
+            |points: &[[f32; 2]]| points.iter().map(|&[x, y]| Vector2::new(x, y)).collect();
+
+        renderer.render_queue.add(Shape::Polygon(
+            Polygon::new(to_points(&outline))
+                .with_hole(to_points(&hole))
+                .with_color(Color::MAGENTA),
+        ));
+    });
+
+    assert_golden("concave_polygon_with_hole", &image, 0);
+}
+
+#[test]
+fn strokes() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        let queue = &mut renderer.render_queue;
+        let zigzag = |y: f32| {
+            vec![
+                Vector2::new(-26.0, y),
+                Vector2::new(-16.0, y + 10.0),
+                Vector2::new(-6.0, y),
+            ]
+        };
+
+        for (i, join) in [LineJoin::Miter, LineJoin::Bevel, LineJoin::Round]
+            .into_iter()
+            .enumerate()
+        {
+            let cap = [LineCap::Butt, LineCap::Square, LineCap::Round][i];
+            queue.add(Shape::Polyline(
+                Polyline::new(zigzag(14.0 - i as f32 * 18.0)).with_stroke(
+                    Stroke::new(4.0)
+                        .with_join(join)
+                        .with_cap(cap)
+                        .with_color(Color::YELLOW),
+                ),
+            ));
+        }
+
+        queue.add(Shape::Line(
+            Line::new(Vector2::new(4.0, 26.0), Vector2::new(28.0, 10.0)).with_stroke(
+                Stroke::new(2.0)
+                    .with_dashes(vec![4.0, 2.0], 0.0)
+                    .with_color(Color::CYAN),
+            ),
+        ));
+        queue.add(Shape::Polyline(
+            Quad::square(Vector2::new(16.0, -12.0), 16.0).outline(
+                Stroke::new(2.0)
+                    .with_join(LineJoin::Miter)
+                    .with_color(Color::WHITE),
+            ),
+        ));
+    });
+
+    assert_golden("strokes", &image, 0);
+}
+
+#[test]
+fn paths() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        let queue = &mut renderer.render_queue;
+
+        // A heart from cubic curves, filled and outlined
+        let heart = Path::from_svg(
+            "M -14 2 C -20 8 -27 12 -23 19 C -20 23 -15 21 -14 17 \
+             C -13 21 -8 23 -5 19 C -1 12 -8 8 -14 2 Z",
+        )
+        .unwrap();
+        queue.add(Shape::Path(
+            heart
+                .with_fill(Color::RED)
+                .with_stroke(Stroke::new(1.0).with_color(Color::WHITE)),
+        ));
+
+        // A self-intersecting star, with and without its center
+        for (x, rule) in [(-14.0, FillRule::NonZero), (14.0, FillRule::EvenOdd)] {
+            let corner = |i: usize| {
+                let angle = std::f32::consts::FRAC_PI_2 + i as f32 * 0.8 * std::f32::consts::PI;
+                Vector2::new(x + angle.cos() * 12.0, -16.0 + angle.sin() * 12.0)
+            };
+            let star = (1..5)
+                .fold(Path::new().move_to(corner(0)), |path, i| {
+                    path.line_to(corner(i))
+                })
+                .close();
+            queue.add(Shape::Path(
+                star.with_fill(Color::YELLOW).with_fill_rule(rule),
+            ));
+        }
+
+        // An open stroke with a quadratic curve and an arc
+        queue.add(Shape::Path(
+            Path::new()
+                .move_to(Vector2::new(4.0, 8.0))
+                .quad_to(Vector2::new(14.0, 28.0), Vector2::new(20.0, 8.0))
+                .arc_to(
+                    Vector2::new(5.0, 5.0),
+                    0.0,
+                    true,
+                    false,
+                    Vector2::new(28.0, 14.0),
+                )
+                .without_fill()
+                .with_stroke(
+                    Stroke::new(2.0)
+                        .with_join(LineJoin::Round)
+                        .with_cap(LineCap::Round)
+                        .with_color(Color::CYAN),
+                ),
+        ));
+    });
+
+    assert_golden("paths", &image, 0);
+}
+
+#[test]
+fn svg_import() {
+    const ICON: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="64" height="64">
+        <g transform="translate(-32 -32)">
+            <rect x="4" y="4" width="56" height="56" rx="8" fill="#2a4d69"/>
+            <circle cx="22" cy="22" r="9" fill="#fed766" stroke="white" stroke-width="2"/>
+            <path d="M8 54 L24 34 L34 44 L44 30 L58 54 Z" fill="#4b86b4"/>
+            <polyline points="36,12 44,16 52,12" fill="none" stroke="#fe4a49"
+                stroke-width="3" stroke-linecap="round" stroke-linejoin="round"/>
+        </g>
+    </svg>"##;
+
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        renderer.render_queue.add_svg(ICON).unwrap();
+    });
+
+    assert_golden("svg_import", &image, 0);
+}
+
+/// An 8x8 texture with a colored quadrant in each corner and a transparent center.
+fn quadrants_texture() -> Texture {
+    let pixels = (0..64)
+        .flat_map(|i| {
+            let (x, y) = (i % 8, i / 8);
+            match (x < 4, y < 4) {
+                _ if (3..5).contains(&x) && (3..5).contains(&y) => [0, 0, 0, 0],
+                (true, true) => [230, 60, 60, 255],
+                (false, true) => [60, 200, 80, 255],
+                (true, false) => [50, 90, 220, 255],
+                (false, false) => [240, 220, 70, 255],
+            }
+        })
+        .collect();
+    Texture::from_rgba8(8, 8, pixels).unwrap()
+}
+
+fn sprites_scene(queue: &mut RenderQueue) {
+    let texture = quadrants_texture();
+    let nearest = texture.clone().with_filter(TextureFilter::Nearest);
+    let size = Vector2::new(24.0, 24.0);
+
+    queue.add(Shape::Sprite(
+        Sprite::new(nearest.clone(), Vector2::new(-16.0, 16.0)).with_size(size),
+    ));
+    queue.add(Shape::Sprite(
+        Sprite::new(texture, Vector2::new(16.0, 16.0)).with_size(size),
+    ));
+    queue.add(Shape::Sprite(
+        Sprite::new(nearest.clone(), Vector2::new(-16.0, -16.0))
+            .with_region(TextureRegion::new(0, 0, 4, 8))
+            .with_size(Vector2::new(12.0, 24.0))
+            .with_tint(Color::rgb(1.0, 0.5, 1.0)),
+    ));
+    queue.add(Shape::Sprite(
+        Sprite::new(nearest, Vector2::new(16.0, -16.0))
+            .with_size(size)
+            .with_flip(true, true)
+            .with_tint(Color::WHITE.with_alpha(0.5)),
+    ));
+}
+
+#[test]
+fn sprites() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        sprites_scene(&mut renderer.render_queue)
+    });
+
+    assert_golden("sprites", &image, 0);
+}
+
+#[test]
+fn headless_draws_textures() {
+    let Some(gpu) = render_headless(WIDTH, HEIGHT, |renderer| {
+        sprites_scene(&mut renderer.render_queue)
+    }) else {
+        return;
+    };
+    let cpu = render_software(WIDTH, HEIGHT, |renderer| {
+        sprites_scene(&mut renderer.render_queue)
+    });
+
+    for (gpu, cpu) in gpu.pixels.iter().zip(&cpu.pixels) {
+        assert!(gpu.abs_diff(*cpu) <= 2, "GPU and CPU output differ");
+    }
+}
+
+fn text_scene(queue: &mut RenderQueue) {
+    let font = Font::load(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fonts/Cantarell-Regular.ttf"
+    ))
+    .unwrap();
+    let atlas = font.glyph_atlas(16.0, ' '..='~').unwrap();
+
+    queue.add(Shape::Text(Text::new(
+        "Hi 42",
+        atlas.clone(),
+        Vector2::new(-30.0, 30.0),
+    )));
+    queue.add(Shape::Text(
+        Text::new("wrapped right", atlas.clone(), Vector2::new(30.0, 10.0))
+            .with_align(TextAlign::Right)
+            .with_max_width(60.0)
+            .with_color(Color::rgb(1.0, 0.8, 0.2)),
+    ));
+    queue.add(Shape::Text(
+        Text::new("Big", atlas, Vector2::new(0.0, -8.0))
+            .with_align(TextAlign::Center)
+            .with_size(24.0)
+            .with_color(Color::rgb(0.3, 0.6, 1.0)),
+    ));
+}
+
+#[test]
+fn text() {
+    let image = render_software(WIDTH, HEIGHT, |renderer| {
+        text_scene(&mut renderer.render_queue)
+    });
+
+    assert_golden("text", &image, 0);
+}
+
+#[test]
+fn headless_draws_text() {
+    let Some(gpu) = render_headless(WIDTH, HEIGHT, |renderer| {
+        text_scene(&mut renderer.render_queue)
+    }) else {
+        return;
+    };
+    let cpu = render_software(WIDTH, HEIGHT, |renderer| {
+        text_scene(&mut renderer.render_queue)
+    });
+
+    for (gpu, cpu) in gpu.pixels.iter().zip(&cpu.pixels) {
+        assert!(gpu.abs_diff(*cpu) <= 2, "GPU and CPU output differ");
+    }
+}
@@ -0,0 +1,161 @@
+use std::f32::consts::FRAC_PI_2;
+
+use stellar_engine::math::{Affine2, Matrix2, Matrix3, Vector2, Vector3};
+
+fn assert_close(actual: Vector2<f32>, expected: Vector2<f32>) {
+    assert!(
+        actual.approx_eq(expected, 1e-5),
+        "{actual:?} is not {expected:?}"
+    );
+}
+
+#[test]
+fn matrix2_operations() {
+    let m = Matrix2::from_rows(Vector2::new(1, 2), Vector2::new(3, 4));
+    assert_eq!(m.x_axis, Vector2::new(1, 3));
+    assert_eq!(m.y_axis, Vector2::new(2, 4));
+    assert_eq!(m * Vector2::new(1, 1), Vector2::new(3, 7));
+    assert_eq!(m.determinant(), -2);
+    assert_eq!(
+        m.transpose(),
+        Matrix2::from_columns(Vector2::new(1, 2), Vector2::new(3, 4))
+    );
+    assert_eq!(m * Matrix2::identity(), m);
+    assert_eq!(Matrix2::<i32>::default(), Matrix2::identity());
+
+    let m = Matrix2::from_rows(Vector2::new(1.0, 2.0), Vector2::new(3.0, 4.0));
+    let inverse = m.inverse().unwrap();
+    assert_eq!(m * inverse, Matrix2::identity());
+    assert_eq!(inverse * m, Matrix2::identity());
+
+    let singular = Matrix2::from_rows(Vector2::new(1.0, 2.0), Vector2::new(2.0, 4.0));
+    assert_eq!(singular.inverse(), None);
+}
+
+#[test]
+fn composition_applies_right_hand_side_first() {
+    let scale = Matrix2::from_scale(Vector2::new(2.0, 1.0));
+    let rotate = Matrix2::from_angle(FRAC_PI_2);
+    let point = Vector2::new(1.0, 0.0);
+
+    assert_close((rotate * scale) * point, Vector2::new(0.0, 2.0));
+    assert_close((scale * rotate) * point, Vector2::new(0.0, 1.0));
+
+    let mut m = rotate;
+    m *= scale;
+    assert_eq!(m, rotate * scale);
+}
+
+#[test]
+fn affine2_transforms_points_and_vectors() {
+    let transform = Affine2::from_scale_angle_translation(
+        Vector2::new(2.0, 2.0),
+        FRAC_PI_2,
+        Vector2::new(10.0, 0.0),
+    );
+    assert_close(transform * Vector2::new(1.0, 0.0), Vector2::new(10.0, 2.0));
+    assert_close(
+        transform.transform_vector(Vector2::new(1.0, 0.0)),
+        Vector2::new(0.0, 2.0),
+    );
+    assert!((transform.determinant() - 4.0).abs() < 1e-5);
+
+    let composed = Affine2::from_translation(Vector2::new(10.0, 0.0))
+        * Affine2::from_angle(FRAC_PI_2)
+        * Affine2::from_scale(Vector2::new(2.0, 2.0));
+    assert_close(composed.translation, transform.translation);
+    assert_close(
+        composed * Vector2::new(3.0, -1.0),
+        transform * Vector2::new(3.0, -1.0),
+    );
+}
+
+#[test]
+fn affine2_inverse() {
+    let transform = Affine2::from_scale_angle_translation(
+        Vector2::new(3.0, -0.5),
+        0.7,
+        Vector2::new(-4.0, 9.0),
+    );
+    let inverse = transform.inverse().unwrap();
+    let point = Vector2::new(5.0, -2.0);
+    assert_close(inverse * (transform * point), point);
+    assert_close((transform * inverse) * point, point);
+
+    assert_eq!(Affine2::from_scale(Vector2::new(0.0, 1.0)).inverse(), None);
+    assert_eq!(
+        Affine2::from_scale(Vector2::new(f32::NAN, 1.0)).inverse(),
+        None
+    );
+}
+
+#[test]
+fn matrix3_matches_affine2() {
+    let affine =
+        Affine2::from_scale_angle_translation(Vector2::new(1.5, 2.0), -1.2, Vector2::new(3.0, 4.0));
+    let matrix = Matrix3::from(affine);
+    let point = Vector2::new(-2.0, 7.0);
+    assert_close(matrix * point, affine * point);
+    assert_close(
+        matrix.transform_vector(point),
+        affine.transform_vector(point),
+    );
+    assert!((matrix.determinant() - affine.determinant()).abs() < 1e-5);
+
+    let composed = Matrix3::from_translation(Vector2::new(3.0, 4.0))
+        * Matrix3::from_angle(-1.2)
+        * Matrix3::from_scale(Vector2::new(1.5, 2.0));
+    assert_close(composed * point, matrix * point);
+
+    let inverse = matrix.inverse().unwrap();
+    assert_close(inverse * (matrix * point), point);
+}
+
+#[test]
+fn matrix3_operations() {
+    let m = Matrix3::from_rows(
+        Vector3::new(2, 0, 1),
+        Vector3::new(1, 3, 0),
+        Vector3::new(0, 1, 4),
+    );
+    assert_eq!(m.determinant(), 25);
+    assert_eq!(m * Vector3::new(1, 1, 1), Vector3::new(3, 4, 5));
+    assert_eq!(m.transpose().transpose(), m);
+    assert_eq!(m.transpose().determinant(), m.determinant());
+    assert_eq!(Matrix3::<i32>::default() * m, m);
+
+    let singular = Matrix3::from_rows(
+        Vector3::new(1.0, 2.0, 3.0),
+        Vector3::new(2.0, 4.0, 6.0),
+        Vector3::new(0.0, 1.0, 1.0),
+    );
+    assert_eq!(singular.inverse(), None);
+
+    // Positions divide by their third component after a projective transform
+    let projective = Matrix3::from_rows(
+        Vector3::new(1.0, 0.0, 0.0),
+        Vector3::new(0.0, 1.0, 0.0),
+        Vector3::new(0.0, 0.0, 2.0),
+    );
+    assert_eq!(projective * Vector2::new(4.0, 6.0), Vector2::new(2.0, 3.0));
+}
+
+#[test]
+fn converts_to_column_major_gpu_layout() {
+    let affine = Affine2::new(
+        Matrix2::from_columns(Vector2::new(1.0, 2.0), Vector2::new(3.0, 4.0)),
+        Vector2::new(5.0, 6.0),
+    );
+    let expected = [
+        [1.0, 2.0, 0.0, 0.0],
+        [3.0, 4.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [5.0, 6.0, 0.0, 1.0],
+    ];
+    assert_eq!(<[[f32; 4]; 4]>::from(affine), expected);
+    assert_eq!(<[[f32; 4]; 4]>::from(Matrix3::from(affine)), expected);
+
+    let mut without_translation = expected;
+    without_translation[3] = [0.0, 0.0, 0.0, 1.0];
+    assert_eq!(<[[f32; 4]; 4]>::from(affine.matrix), without_translation);
+}
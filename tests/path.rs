@@ -0,0 +1,305 @@
+mod common;
+
+use std::f32::consts::PI;
+
+use common::recording::RecordingBackend;
+use stellar_engine::{
+    math::Vector2,
+    rendering::{
+        RenderBackend, RenderQueue,
+        shapes::{FillRule, ParsePathError, Path, Shape, Stroke, Tessellation},
+    },
+};
+
+/// Draws the path at the given scale, checks that all triangles face the camera
+/// and returns their total area.
+fn drawn_area_at(path: Path, zoom: f32) -> f32 {
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Path(path));
+
+    let mut backend = RecordingBackend {
+        zoom,
+        ..Default::default()
+    };
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    backend
+        .triangles()
+        .map(|[a, b, c]| {
+            let area = ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])) * 0.5;
+            assert!(
+                area > 0.0,
+                "triangle {:?} is not counter-clockwise",
+                [a, b, c]
+            );
+            area
+        })
+        .sum()
+}
+
+fn drawn_area(path: Path) -> f32 {
+    drawn_area_at(path, 1.0)
+}
+
+fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "{actual} is not within {tolerance} of {expected}"
+    );
+}
+
+fn square(path: Path, min: Vector2<f32>, size: f32, counter_clockwise: bool) -> Path {
+    let mut corners = [
+        min,
+        min + Vector2::new(size, 0.0),
+        min + Vector2::new(size, size),
+        min + Vector2::new(0.0, size),
+    ];
+    if !counter_clockwise {
+        corners.reverse();
+    }
+    let [a, b, c, d] = corners;
+    path.move_to(a).line_to(b).line_to(c).line_to(d).close()
+}
+
+#[test]
+fn fills_a_square() {
+    let path = square(Path::new(), Vector2::new(0.0, 0.0), 10.0, true);
+    assert_close(drawn_area(path), 100.0, 1e-3);
+}
+
+#[test]
+fn overlapping_subpaths_follow_the_fill_rule() {
+    let squares = |rule| {
+        let path = square(Path::new(), Vector2::new(0.0, 0.0), 10.0, true);
+        square(path, Vector2::new(5.0, 5.0), 10.0, true).with_fill_rule(rule)
+    };
+
+    // The union, and the union without the 5 by 5 overlap
+    assert_close(drawn_area(squares(FillRule::NonZero)), 175.0, 1e-3);
+    assert_close(drawn_area(squares(FillRule::EvenOdd)), 150.0, 1e-3);
+}
+
+#[test]
+fn opposite_winding_cuts_holes_with_both_rules() {
+    for rule in [FillRule::NonZero, FillRule::EvenOdd] {
+        let path = square(Path::new(), Vector2::new(0.0, 0.0), 10.0, true);
+        let path = square(path, Vector2::new(2.0, 2.0), 4.0, false).with_fill_rule(rule);
+        assert_close(drawn_area(path), 84.0, 1e-3);
+    }
+}
+
+#[test]
+fn self_intersecting_star_center_depends_on_the_fill_rule() {
+    let star = |rule| {
+        let corner = |i: usize| {
+            let angle = PI / 2.0 + i as f32 * 4.0 * PI / 5.0;
+            Vector2::new(angle.cos(), angle.sin()) * 10.0
+        };
+        (1..5)
+            .fold(Path::new().move_to(corner(0)), |path, i| {
+                path.line_to(corner(i))
+            })
+            .close()
+            .with_fill_rule(rule)
+    };
+
+    let non_zero = drawn_area(star(FillRule::NonZero));
+    let even_odd = drawn_area(star(FillRule::EvenOdd));
+    // The inner pentagon of a star with an outer radius of 10
+    let inner_radius = 10.0 * (2.0 * PI / 5.0).cos() / (PI / 5.0).cos();
+    let pentagon = 2.5 * inner_radius * inner_radius * (2.0 * PI / 5.0).sin();
+    assert_close(non_zero - even_odd, pentagon, 0.05);
+}
+
+#[test]
+fn curves_are_flattened_within_the_tolerance() {
+    // Parabolic segment of width 10 and height 5
+    let path = Path::new()
+        .move_to(Vector2::new(0.0, 0.0))
+        .quad_to(Vector2::new(5.0, 10.0), Vector2::new(10.0, 0.0))
+        .close();
+    let exact = 2.0 / 3.0 * 10.0 * 5.0;
+    assert_close(drawn_area_at(path.clone(), 1.0), exact, 2.0);
+    assert_close(drawn_area_at(path, 100.0), exact, 0.02);
+}
+
+#[test]
+fn arcs_make_a_circle() {
+    let path = Path::new()
+        .move_to(Vector2::new(10.0, 0.0))
+        .arc_to(
+            Vector2::new(10.0, 10.0),
+            0.0,
+            false,
+            true,
+            Vector2::new(-10.0, 0.0),
+        )
+        .arc_to(
+            Vector2::new(10.0, 10.0),
+            0.0,
+            false,
+            true,
+            Vector2::new(10.0, 0.0),
+        )
+        .close();
+
+    let contours = path.flatten(10.0);
+    assert_eq!(contours.len(), 1);
+    let (points, closed) = &contours[0];
+    assert!(closed);
+    for point in points {
+        assert_close(point.length(), 10.0, 1e-3);
+    }
+    // Going counter-clockwise, the first arc passes the top
+    assert!(points.iter().any(|point| point.y > 9.9));
+    assert_close(drawn_area_at(path, 10.0), PI * 100.0, 1.0);
+}
+
+#[test]
+fn arcs_with_too_small_radii_are_scaled_up() {
+    let contours = Path::new()
+        .move_to(Vector2::new(0.0, 0.0))
+        .arc_to(
+            Vector2::new(1.0, 1.0),
+            0.0,
+            false,
+            false,
+            Vector2::new(10.0, 0.0),
+        )
+        .flatten(1.0);
+
+    // A clockwise half circle of radius 5, above the chord
+    let (points, _) = &contours[0];
+    for point in points {
+        assert_close((*point - Vector2::new(5.0, 0.0)).length(), 5.0, 1e-3);
+        assert!(point.y >= -1e-3);
+    }
+}
+
+#[test]
+fn open_paths_are_stroked_without_closing() {
+    let path = Path::new()
+        .move_to(Vector2::new(0.0, 0.0))
+        .line_to(Vector2::new(10.0, 0.0))
+        .line_to(Vector2::new(10.0, 10.0))
+        .without_fill()
+        .with_stroke(Stroke::new(2.0));
+
+    // Two 10 by 2 segments, overlapping by a unit square inside the corner,
+    // and the miter square outside of it
+    assert_close(drawn_area(path), 40.0, 1e-3);
+}
+
+#[test]
+fn fill_and_stroke_are_drawn_together() {
+    let path = square(Path::new(), Vector2::new(0.0, 0.0), 10.0, true);
+    let stroked = path.clone().with_stroke(Stroke::new(2.0));
+    // A 12 by 12 square without its 8 by 8 inside, on top of the fill
+    assert_close(drawn_area(stroked) - drawn_area(path), 80.0, 1e-3);
+}
+
+#[test]
+fn parses_svg_path_data() {
+    let parsed: Path = "M 0 0 L 10 0 L 10 10 L 0 10 Z".parse().unwrap();
+    let built = square(Path::new(), Vector2::new(0.0, 0.0), 10.0, true);
+    assert_eq!(parsed.flatten(1.0), built.flatten(1.0));
+
+    // Relative commands, implicit line commands and numbers without separators
+    let compact = Path::from_svg("m0,0h10v10h-10z").unwrap();
+    assert_eq!(compact.flatten(1.0), built.flatten(1.0));
+    let compact = Path::from_svg("M0 0l10-0 0 10-10 0z").unwrap();
+    assert_eq!(compact.flatten(1.0), built.flatten(1.0));
+
+    let contours = Path::from_svg("M1.5.5 1e1-2E-1").unwrap().flatten(1.0);
+    assert_eq!(
+        contours[0].0,
+        vec![Vector2::new(1.5, 0.5), Vector2::new(10.0, -0.2)]
+    );
+}
+
+#[test]
+fn parses_arc_flags_without_separators() {
+    let separated = Path::from_svg("M0 0 A 5 5 0 1 1 10 0").unwrap();
+    let compact = Path::from_svg("M0 0a5 5 0 1110 0").unwrap();
+    assert_eq!(separated.flatten(1.0), compact.flatten(1.0));
+
+    // Sweeping with increasing angles from the left passes below the chord
+    let (points, _) = &compact.flatten(1.0)[0];
+    assert!(points.iter().all(|point| point.y <= 1e-3));
+}
+
+#[test]
+fn smooth_curves_mirror_the_previous_control_point() {
+    let smooth = Path::from_svg("M0 0 C0 10 10 10 10 0 S20 -10 20 0").unwrap();
+    let explicit = Path::from_svg("M0 0 C0 10 10 10 10 0 C10 -10 20 -10 20 0").unwrap();
+    assert_eq!(smooth.flatten(1.0), explicit.flatten(1.0));
+
+    let smooth = Path::from_svg("M0 0 Q5 10 10 0 T20 0").unwrap();
+    let explicit = Path::from_svg("M0 0 Q5 10 10 0 Q15 -10 20 0").unwrap();
+    assert_eq!(smooth.flatten(1.0), explicit.flatten(1.0));
+}
+
+#[test]
+fn subpaths_continue_from_the_start_after_closing() {
+    let contours = Path::from_svg("M0 0 L10 0 L10 10 Z L0 10 L-10 10 Z")
+        .unwrap()
+        .flatten(1.0);
+    assert_eq!(contours.len(), 2);
+    assert_eq!(contours[1].0[0], Vector2::new(0.0, 0.0));
+}
+
+#[test]
+fn tolerances_that_cant_be_met_split_curves_finely() {
+    for tolerance in [0.0, -1.0, f32::NAN] {
+        let path = Path::new()
+            .move_to(Vector2::new(0.0, 0.0))
+            .quad_to(Vector2::new(10.0, 20.0), Vector2::new(20.0, 0.0))
+            .with_tessellation(Tessellation::Tolerance(tolerance));
+
+        let contours = path.flatten(1.0);
+
+        assert_eq!(contours.len(), 1);
+        assert_eq!(contours[0].0.len(), 1025, "tolerance {tolerance}");
+        assert!(
+            contours[0]
+                .0
+                .iter()
+                .all(|point| point.x.is_finite() && point.y.is_finite())
+        );
+    }
+}
+
+#[test]
+fn reports_invalid_path_data() {
+    assert_eq!(
+        Path::from_svg("L 10 10").unwrap_err(),
+        ParsePathError::MissingMoveTo
+    );
+    assert_eq!(
+        Path::from_svg("10 10").unwrap_err(),
+        ParsePathError::MissingMoveTo
+    );
+    assert_eq!(
+        Path::from_svg("M 0").unwrap_err(),
+        ParsePathError::ExpectedNumber(3)
+    );
+    assert_eq!(
+        Path::from_svg("M0 0 X 1").unwrap_err(),
+        ParsePathError::UnexpectedCharacter {
+            character: 'X',
+            position: 5
+        }
+    );
+    assert_eq!(
+        Path::from_svg("M0 0 Z 1").unwrap_err(),
+        ParsePathError::UnexpectedCharacter {
+            character: '1',
+            position: 7
+        }
+    );
+    assert_eq!(
+        Path::from_svg("M0 0 A 1 1 0 2 0 1 1").unwrap_err(),
+        ParsePathError::ExpectedFlag(13)
+    );
+}
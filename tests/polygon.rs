@@ -0,0 +1,127 @@
+mod common;
+
+use std::f32::consts::TAU;
+
+use common::recording::RecordingBackend;
+use stellar_engine::{
+    math::Vector2,
+    rendering::{
+        RenderBackend, RenderQueue,
+        shapes::{Polygon, Shape},
+    },
+};
+
+/// Twice the signed area of a triangle, positive when counter-clockwise.
+fn doubled_area([a, b, c]: [[f32; 2]; 3]) -> f32 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Draws the polygon, checks that all triangles face the camera and returns their total area.
+fn triangulated_area(polygon: Polygon) -> f32 {
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Polygon(polygon));
+
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    backend
+        .triangles()
+        .map(|triangle| {
+            let area = doubled_area(triangle);
+            assert!(area > 0.0, "triangle {triangle:?} is not counter-clockwise");
+            area * 0.5
+        })
+        .sum()
+}
+
+fn points(points: &[[f32; 2]]) -> Vec<Vector2<f32>> {
+    points.iter().map(|&[x, y]| Vector2::new(x, y)).collect()
+}
+
+fn square(center: [f32; 2], side: f32) -> Vec<Vector2<f32>> {
+    let half = side * 0.5;
+    let [x, y] = center;
+    points(&[
+        [x - half, y - half],
+        [x + half, y - half],
+        [x + half, y + half],
+        [x - half, y + half],
+    ])
+}
+
+#[test]
+fn concave_polygon_is_fully_covered() {
+    // L shape: a 4x4 square without its top-right 2x2 quarter
+    let l_shape = points(&[
+        [0.0, 0.0],
+        [4.0, 0.0],
+        [4.0, 2.0],
+        [2.0, 2.0],
+        [2.0, 4.0],
+        [0.0, 4.0],
+    ]);
+
+    assert_eq!(triangulated_area(Polygon::new(l_shape)), 12.0);
+}
+
+#[test]
+fn clockwise_polygon_is_not_culled() {
+    let mut l_shape = points(&[
+        [0.0, 0.0],
+        [4.0, 0.0],
+        [4.0, 2.0],
+        [2.0, 2.0],
+        [2.0, 4.0],
+        [0.0, 4.0],
+    ]);
+    l_shape.reverse();
+
+    assert_eq!(triangulated_area(Polygon::new(l_shape)), 12.0);
+}
+
+#[test]
+fn star_is_fully_covered() {
+    let star: Vec<_> = (0..10)
+        .map(|i| {
+            let angle = i as f32 / 10.0 * TAU;
+            let radius = if i % 2 == 0 { 10.0 } else { 4.0 };
+            Vector2::new(radius * angle.cos(), radius * angle.sin())
+        })
+        .collect();
+    // Five kites, each made of two triangles between the center and neighboring points
+    let expected = 10.0 * 0.5 * 10.0 * 4.0 * (TAU / 10.0).sin();
+
+    let area = triangulated_area(Polygon::new(star));
+
+    assert!((area - expected).abs() < 1e-3, "{area} != {expected}");
+}
+
+#[test]
+fn holes_are_left_empty() {
+    let polygon = Polygon::new(square([0.0, 0.0], 10.0))
+        .with_hole(square([-2.0, 0.0], 2.0))
+        .with_hole(square([2.5, 1.0], 3.0));
+
+    assert_eq!(triangulated_area(polygon), 100.0 - 4.0 - 9.0);
+}
+
+#[test]
+fn holes_in_either_winding_order() {
+    let mut hole = square([0.0, 0.0], 4.0);
+    hole.reverse();
+    let polygon = Polygon::new(square([0.0, 0.0], 10.0)).with_hole(hole);
+
+    assert_eq!(triangulated_area(polygon), 100.0 - 16.0);
+}
+
+#[test]
+fn degenerate_polygons_draw_nothing() {
+    assert_eq!(
+        triangulated_area(Polygon::new(points(&[[0.0, 0.0], [1.0, 1.0]]))),
+        0.0
+    );
+    assert_eq!(
+        triangulated_area(Polygon::new(points(&[[0.0, 0.0], [1.0, 1.0], [2.0, 2.0]]))),
+        0.0
+    );
+}
@@ -0,0 +1,262 @@
+mod common;
+
+use std::f32::consts::TAU;
+
+use common::recording::RecordingBackend;
+use stellar_engine::{
+    math::Vector2,
+    rendering::{
+        RenderBackend, RenderQueue, ShapeHandle,
+        shapes::{Arc, Circle, Quad, Sector, Shape, Tessellation},
+    },
+};
+
+#[test]
+fn indices_do_not_wrap_past_u16() {
+    const QUADS: usize = 100_000;
+
+    let mut queue = RenderQueue::default();
+    for i in 0..QUADS {
+        queue.add(Shape::Quad(Quad::square(Vector2::new(i as f32, 0.0), 1.0)));
+    }
+
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    assert_eq!(backend.vertices.len(), QUADS * 4);
+    assert_eq!(backend.indices.len(), QUADS * 6);
+    assert_eq!(backend.batches.len(), 1);
+    for (quad, indices) in backend.indices.chunks(6).enumerate() {
+        let first = quad as u32 * 4;
+        assert!(
+            indices
+                .iter()
+                .all(|index| (first..first + 4).contains(index)),
+            "indices of quad {quad} point outside of its vertices: {indices:?}"
+        );
+    }
+    assert_eq!(backend.indices.iter().max(), Some(&(QUADS as u32 * 4 - 1)));
+}
+
+fn square(x: f32) -> Shape {
+    Shape::Quad(Quad::square(Vector2::new(x, 0.0), 1.0))
+}
+
+fn first_x(queue: &RenderQueue, handle: ShapeHandle) -> Option<f32> {
+    match queue.get(handle)? {
+        Shape::Quad(quad) => Some(quad.points[0].x),
+        _ => None,
+    }
+}
+
+#[test]
+fn handles_refer_to_their_shape() {
+    let mut queue = RenderQueue::default();
+    let a = queue.add(square(0.0));
+    let b = queue.add(square(10.0));
+
+    if let Some(Shape::Quad(quad)) = queue.get_mut(b) {
+        quad.points.iter_mut().for_each(|point| point.x += 5.0);
+    }
+
+    assert_eq!(queue.len(), 2);
+    assert_eq!(first_x(&queue, a), Some(-0.5));
+    assert_eq!(first_x(&queue, b), Some(14.5));
+}
+
+#[test]
+fn removed_handles_stay_invalid_when_the_slot_is_reused() {
+    let mut queue = RenderQueue::default();
+    let removed = queue.add(square(0.0));
+    assert!(queue.remove(removed).is_some());
+
+    let added = queue.add(square(10.0));
+
+    assert!(!queue.contains(removed));
+    assert!(queue.get(removed).is_none());
+    assert!(queue.remove(removed).is_none());
+    assert!(!queue.set_visible(removed, false));
+    assert_eq!(first_x(&queue, added), Some(9.5));
+    assert_eq!(queue.len(), 1);
+}
+
+#[test]
+fn clear_invalidates_all_handles() {
+    let mut queue = RenderQueue::default();
+    let handles = [queue.add(square(0.0)), queue.add(square(1.0))];
+
+    queue.clear();
+
+    assert!(queue.is_empty());
+    assert!(handles.iter().all(|handle| !queue.contains(*handle)));
+}
+
+#[test]
+fn hidden_shapes_are_not_drawn() {
+    let mut queue = RenderQueue::default();
+    let hidden = queue.add(square(0.0));
+    queue.add(square(10.0));
+    queue.set_visible(hidden, false);
+
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    assert_eq!(queue.is_visible(hidden), Some(false));
+    assert_eq!(queue.iter().count(), 2);
+    assert_eq!(backend.vertices.len(), 4);
+    assert_eq!(backend.indices.len(), 6);
+}
+
+#[test]
+fn reused_slots_keep_the_insertion_order() {
+    let mut queue = RenderQueue::default();
+    let first = queue.add(square(0.0));
+    queue.add(square(10.0));
+    queue.remove(first);
+    // Takes the slot of `first`, but must still be drawn after the remaining shape
+    queue.add(square(20.0));
+
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    assert_eq!(backend.first_x(), Some(9.5));
+}
+
+#[test]
+fn unchanged_queue_keeps_its_revision() {
+    let mut queue = RenderQueue::default();
+    queue.add(square(0.0));
+
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+    let revision = backend.revision;
+    assert_eq!(backend.dirty_vertices, vec![0..4]);
+    assert_eq!(backend.dirty_indices, vec![0..6]);
+
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    assert_eq!(backend.revision, revision);
+}
+
+#[test]
+fn changed_shapes_only_dirty_their_vertices() {
+    let mut queue = RenderQueue::default();
+    let handles: Vec<_> = (0..4).map(|i| queue.add(square(i as f32 * 10.0))).collect();
+
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+    let revision = backend.revision;
+
+    for handle in [handles[1], handles[3]] {
+        if let Some(Shape::Quad(quad)) = queue.get_mut(handle) {
+            quad.points.iter_mut().for_each(|point| point.y += 1.0);
+        }
+    }
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    assert_eq!(backend.previous_revision, Some(revision));
+    assert_eq!(backend.dirty_vertices, [4..8, 12..16]);
+    assert!(backend.dirty_indices.is_empty());
+}
+
+#[test]
+fn removing_a_shape_rebuilds_the_buffers() {
+    let mut queue = RenderQueue::default();
+    let removed = queue.add(square(0.0));
+    queue.add(square(10.0));
+
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    queue.remove(removed);
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    assert_eq!(backend.dirty_vertices, vec![0..4]);
+    assert_eq!(backend.dirty_indices, vec![0..6]);
+    assert_eq!(backend.indices, [0, 1, 2, 0, 2, 3]);
+    assert_eq!(backend.first_x(), Some(9.5));
+}
+
+#[test]
+fn circles_get_more_segments_when_zoomed_in() {
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Circle(Circle::new(Vector2::new(0.0, 0.0), 20.0)));
+
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+    let vertices = backend.vertices.len();
+
+    backend.zoom = 4.0;
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    assert!(
+        vertices > 8,
+        "too few vertices for a smooth circle: {vertices}"
+    );
+    assert!(backend.vertices.len() > vertices);
+}
+
+#[test]
+fn fixed_segments_ignore_the_zoom() {
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Circle(
+        Circle::new(Vector2::new(0.0, 0.0), 20.0).with_tessellation(Tessellation::Segments(16)),
+    ));
+
+    let mut backend = RecordingBackend {
+        zoom: 4.0,
+        ..Default::default()
+    };
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    // The center and the outline, whose last vertex closes the loop
+    assert_eq!(backend.vertices.len(), 18);
+    assert_eq!(backend.indices.len(), 16 * 3);
+}
+
+#[test]
+fn tolerances_that_cant_be_met_get_the_most_segments() {
+    for tolerance in [0.0, -1.0, f32::NAN] {
+        let mut queue = RenderQueue::default();
+        queue.add(Shape::Circle(
+            Circle::new(Vector2::new(0.0, 0.0), 20.0)
+                .with_tessellation(Tessellation::Tolerance(tolerance)),
+        ));
+
+        let mut backend = RecordingBackend::default();
+        let Ok(()) = backend.draw_queue(&mut queue);
+
+        assert_eq!(backend.indices.len(), 1024 * 3, "tolerance {tolerance}");
+    }
+}
+
+#[test]
+fn sweeps_past_a_full_turn_are_drawn_once() {
+    let sector = |sweep| {
+        Sector::new(Vector2::new(0.0, 0.0), 20.0, 0.0, sweep)
+            .with_tessellation(Tessellation::Segments(16))
+    };
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Sector(sector(2.0 * TAU)));
+    queue.add(Shape::Sector(sector(-2.0 * TAU)));
+    queue.add(Shape::Arc(
+        Arc::new(Vector2::new(0.0, 0.0), 20.0, 2.0, 0.0, 3.0 * TAU)
+            .with_tessellation(Tessellation::Segments(16)),
+    ));
+
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    // Each covers the full circle or ring once, with a full turn's worth of segments
+    let mut areas = [0.0; 3];
+    for (i, [a, b, c]) in backend.triangles().enumerate() {
+        let shape = if i < 32 { i / 16 } else { 2 };
+        areas[shape] += ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])) * 0.5;
+    }
+    assert_eq!(backend.indices.len(), (16 + 16 + 2 * 16) * 3);
+    let polygon = 16.0 * 0.5 * 20.0 * 20.0 * (TAU / 16.0).sin();
+    assert!((areas[0] - polygon).abs() < 1e-2, "{areas:?}");
+    assert!((areas[1] - polygon).abs() < 1e-2, "{areas:?}");
+    let ring = 16.0 * 0.5 * (21.0 * 21.0 - 19.0 * 19.0) * (TAU / 16.0).sin();
+    assert!((areas[2] - ring).abs() < 1e-2, "{areas:?}");
+}
@@ -0,0 +1,222 @@
+mod common;
+
+use common::recording::RecordingBackend;
+use stellar_engine::{
+    math::Vector2,
+    rendering::{
+        Color, RenderBackend, RenderQueue, Texture, TextureError, TextureFilter, TextureRegion,
+        shapes::{Quad, Shape, Sprite},
+    },
+};
+
+/// A 4x2 texture whose pixels all differ.
+fn texture() -> Texture {
+    let pixels = (0..8u8)
+        .flat_map(|i| [i * 30, 255 - i * 30, i, 255])
+        .collect();
+    Texture::from_rgba8(4, 2, pixels).unwrap()
+}
+
+fn encode_png(width: u32, height: u32, color: png::ColorType, data: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, width, height);
+    encoder.set_color(color);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .unwrap()
+        .write_image_data(data)
+        .unwrap();
+    bytes
+}
+
+#[test]
+fn decodes_png_color_types() {
+    let rgba = encode_png(1, 2, png::ColorType::Rgba, &[1, 2, 3, 4, 5, 6, 7, 8]);
+    let texture = Texture::from_png(&rgba).unwrap();
+    assert_eq!((texture.width(), texture.height()), (1, 2));
+    assert_eq!(texture.pixels(), [1, 2, 3, 4, 5, 6, 7, 8]);
+
+    let rgb = encode_png(2, 1, png::ColorType::Rgb, &[1, 2, 3, 4, 5, 6]);
+    assert_eq!(
+        Texture::from_png(&rgb).unwrap().pixels(),
+        [1, 2, 3, 255, 4, 5, 6, 255]
+    );
+
+    let gray = encode_png(2, 1, png::ColorType::GrayscaleAlpha, &[10, 20, 30, 40]);
+    assert_eq!(
+        Texture::from_png(&gray).unwrap().pixels(),
+        [10, 10, 10, 20, 30, 30, 30, 40]
+    );
+}
+
+#[test]
+fn decodes_qoi() {
+    let bytes = qoi::encode_to_vec([1, 2, 3, 4, 5, 6], 2, 1).unwrap();
+    let texture = Texture::from_qoi(&bytes).unwrap();
+    assert_eq!((texture.width(), texture.height()), (2, 1));
+    assert_eq!(texture.pixels(), [1, 2, 3, 255, 4, 5, 6, 255]);
+}
+
+#[test]
+fn detects_the_format_of_files() {
+    let directory = std::path::PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("sprite");
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let png_path = directory.join("pixel.png");
+    std::fs::write(&png_path, encode_png(1, 1, png::ColorType::Rgba, &[9; 4])).unwrap();
+    assert_eq!(Texture::load(&png_path).unwrap().pixels(), [9; 4]);
+
+    let qoi_path = directory.join("pixel.qoi");
+    std::fs::write(&qoi_path, qoi::encode_to_vec([9; 4], 1, 1).unwrap()).unwrap();
+    assert_eq!(Texture::load(&qoi_path).unwrap().pixels(), [9; 4]);
+
+    assert!(matches!(
+        Texture::load(directory.join("missing.png")),
+        Err(TextureError::Io(_))
+    ));
+}
+
+#[test]
+fn reports_invalid_images() {
+    assert!(matches!(
+        Texture::from_bytes(b"GIF89a"),
+        Err(TextureError::UnknownFormat)
+    ));
+    assert!(matches!(
+        Texture::from_bytes(b"\x89PNG\r\n\x1a\n"),
+        Err(TextureError::Png(_))
+    ));
+    assert!(matches!(
+        Texture::from_bytes(b"qoif"),
+        Err(TextureError::Qoi(_))
+    ));
+    assert!(matches!(
+        Texture::from_rgba8(2, 2, vec![0; 12]),
+        Err(TextureError::InvalidSize { bytes: 12, .. })
+    ));
+    assert!(Texture::from_rgba8(0, 0, Vec::new()).is_err());
+}
+
+#[test]
+fn clones_are_the_same_texture() {
+    let original = texture();
+    assert_eq!(original.clone(), original);
+    assert_ne!(texture(), original);
+
+    let nearest = original.clone().with_filter(TextureFilter::Nearest);
+    assert_ne!(nearest, original);
+    assert_eq!(nearest.filter(), TextureFilter::Nearest);
+    assert_eq!(original.filter(), TextureFilter::Linear);
+    assert_eq!(nearest.pixels(), original.pixels());
+}
+
+fn draw(queue: &mut RenderQueue) -> RecordingBackend {
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(queue);
+    backend
+}
+
+#[test]
+fn sprites_cover_one_unit_per_pixel() {
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Sprite(Sprite::new(
+        texture(),
+        Vector2::new(10.0, 20.0),
+    )));
+
+    let backend = draw(&mut queue);
+    let corners: Vec<_> = backend
+        .vertices
+        .iter()
+        .map(|vertex| (vertex.position, vertex.uv))
+        .collect();
+    assert_eq!(
+        corners,
+        [
+            ([8.0, 21.0], [0.0, 0.0]),
+            ([8.0, 19.0], [0.0, 1.0]),
+            ([12.0, 19.0], [1.0, 1.0]),
+            ([12.0, 21.0], [1.0, 0.0]),
+        ]
+    );
+    assert!(
+        backend
+            .vertices
+            .iter()
+            .all(|vertex| vertex.color == Color::WHITE.to_array())
+    );
+}
+
+#[test]
+fn regions_and_flips_change_the_texture_coordinates() {
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Sprite(
+        Sprite::new(texture(), Vector2::new(0.0, 0.0))
+            .with_region(TextureRegion::new(1, 0, 2, 1))
+            .with_flip(true, false)
+            .with_tint(Color::RED),
+    ));
+
+    let backend = draw(&mut queue);
+    let uvs: Vec<_> = backend.vertices.iter().map(|vertex| vertex.uv).collect();
+    assert_eq!(uvs, [[0.75, 0.0], [0.75, 0.5], [0.25, 0.5], [0.25, 0.0]]);
+    // The region is 2x1 pixels
+    assert_eq!(backend.vertices[2].position, [1.0, -0.5]);
+    assert_eq!(backend.vertices[0].color, Color::RED.to_array());
+
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Sprite(
+        Sprite::new(texture(), Vector2::new(0.0, 0.0))
+            .with_size(Vector2::new(1.0, 1.0))
+            .with_flip(false, true),
+    ));
+    let backend = draw(&mut queue);
+    assert_eq!(backend.vertices[0].position, [-0.5, 0.5]);
+    assert_eq!(backend.vertices[0].uv, [0.0, 1.0]);
+}
+
+#[test]
+fn batches_are_split_by_texture() {
+    let (first, second) = (texture(), texture());
+    let sprite =
+        |texture: &Texture| Shape::Sprite(Sprite::new(texture.clone(), Vector2::default()));
+
+    let mut queue = RenderQueue::default();
+    queue.add(sprite(&first));
+    queue.add(sprite(&first));
+    let quad = queue.add(Shape::Quad(Quad::square(Vector2::default(), 1.0)));
+    let last = queue.add(sprite(&second));
+
+    let backend = draw(&mut queue);
+    let textures: Vec<_> = backend
+        .batches
+        .iter()
+        .map(|batch| batch.texture.clone())
+        .collect();
+    assert_eq!(textures, [Some(first.clone()), None, Some(second)]);
+    assert_eq!(backend.batches[0].indices, 0..12);
+
+    // Switching to the texture of the batch before merges the batches
+    if let Some(Shape::Sprite(sprite)) = queue.get_mut(last) {
+        sprite.texture = first.clone();
+    }
+    queue.remove(quad);
+    let backend = draw(&mut queue);
+    assert_eq!(backend.batches.len(), 1);
+    assert_eq!(backend.batches[0].texture, Some(first));
+}
+
+#[test]
+fn changing_the_texture_rebuilds_the_batches() {
+    let mut queue = RenderQueue::default();
+    let handle = queue.add(Shape::Sprite(Sprite::new(texture(), Vector2::default())));
+    draw(&mut queue);
+
+    let other = texture();
+    if let Some(Shape::Sprite(sprite)) = queue.get_mut(handle) {
+        sprite.texture = other.clone();
+    }
+    let backend = draw(&mut queue);
+    assert_eq!(backend.batches[0].texture, Some(other));
+}
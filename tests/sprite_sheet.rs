@@ -0,0 +1,185 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use stellar_engine::{
+    math::Vector2,
+    rendering::{SpriteSheet, SpriteSheetError, TagDirection, Texture, TextureRegion},
+};
+
+fn sheet_texture() -> Texture {
+    Texture::from_rgba8(64, 32, vec![255; 64 * 32 * 4]).unwrap()
+}
+
+/// A sheet as exported by Aseprite with the hash layout.
+const ASEPRITE: &str = r##"{
+    "frames": {
+        "knight 0.aseprite": {
+            "frame": { "x": 0, "y": 0, "w": 16, "h": 16 },
+            "rotated": false,
+            "trimmed": false,
+            "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 },
+            "sourceSize": { "w": 16, "h": 16 },
+            "duration": 100
+        },
+        "knight 1.aseprite": {
+            "frame": { "x": 16, "y": 0, "w": 16, "h": 16 },
+            "rotated": false,
+            "trimmed": false,
+            "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 },
+            "sourceSize": { "w": 16, "h": 16 },
+            "duration": 150
+        },
+        "knight 2.aseprite": {
+            "frame": { "x": 32, "y": 0, "w": 16, "h": 16 },
+            "rotated": false,
+            "trimmed": false,
+            "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 },
+            "sourceSize": { "w": 16, "h": 16 },
+            "duration": 100
+        }
+    },
+    "meta": {
+        "app": "https://www.aseprite.org/",
+        "version": "1.3.7",
+        "image": "knight.png",
+        "format": "RGBA8888",
+        "size": { "w": 64, "h": 32 },
+        "scale": "1",
+        "frameTags": [
+            { "name": "idle", "from": 0, "to": 0, "direction": "forward", "color": "#000000ff" },
+            { "name": "walk", "from": 1, "to": 2, "direction": "pingpong", "color": "#000000ff" }
+        ],
+        "layers": [],
+        "slices": []
+    }
+}"##;
+
+/// A trimmed sheet as exported by TexturePacker with the array layout.
+const TEXTURE_PACKER: &str = r#"{
+    "frames": [
+        {
+            "filename": "coin.png",
+            "frame": { "x": 2, "y": 2, "w": 10, "h": 12 },
+            "rotated": false,
+            "trimmed": true,
+            "spriteSourceSize": { "x": 4, "y": 1, "w": 10, "h": 12 },
+            "sourceSize": { "w": 16, "h": 16 },
+            "pivot": { "x": 0.5, "y": 0.5 }
+        },
+        {
+            "filename": "gem.png",
+            "frame": { "x": 14, "y": 2, "w": 8, "h": 8 },
+            "rotated": false,
+            "trimmed": false,
+            "spriteSourceSize": { "x": 0, "y": 0, "w": 8, "h": 8 },
+            "sourceSize": { "w": 8, "h": 8 }
+        }
+    ],
+    "meta": { "app": "https://www.codeandweb.com/texturepacker", "image": "items.png" }
+}"#;
+
+#[test]
+fn reads_aseprite_sheets() {
+    let sheet = SpriteSheet::from_json(ASEPRITE, sheet_texture()).unwrap();
+
+    let names: Vec<_> = sheet
+        .frames
+        .iter()
+        .map(|frame| frame.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "knight 0.aseprite",
+            "knight 1.aseprite",
+            "knight 2.aseprite"
+        ]
+    );
+    assert_eq!(sheet.frames[1].region, TextureRegion::new(16, 0, 16, 16));
+    assert_eq!(sheet.frames[1].duration, Some(Duration::from_millis(150)));
+
+    let walk = sheet.tag("walk").unwrap();
+    assert_eq!((walk.from, walk.to), (1, 2));
+    assert_eq!(walk.direction, TagDirection::PingPong);
+    assert_eq!(sheet.tag("idle").unwrap().direction, TagDirection::Forward);
+    assert!(sheet.tag("run").is_none());
+}
+
+#[test]
+fn reads_texture_packer_sheets() {
+    let sheet = SpriteSheet::from_json(TEXTURE_PACKER, sheet_texture()).unwrap();
+
+    assert_eq!(sheet.frame_index("gem.png"), Some(1));
+    let coin = &sheet.frames[0];
+    assert_eq!(coin.region, TextureRegion::new(2, 2, 10, 12));
+    assert_eq!(coin.source_size, Vector2::new(16, 16));
+    assert_eq!(coin.offset, Vector2::new(4, 1));
+    assert_eq!(coin.duration, None);
+    assert!(sheet.tags.is_empty());
+}
+
+#[test]
+fn trimmed_frames_stay_in_place() {
+    let sheet = SpriteSheet::from_json(TEXTURE_PACKER, sheet_texture()).unwrap();
+
+    // The coin was cut out 4 pixels from the left and 1 from the top of a 16x16 image,
+    // so its 10x12 center is 1 pixel right of and 1 pixel above the image center
+    let coin = sheet.sprite(0, Vector2::new(100.0, 100.0));
+    assert_eq!(coin.center, Vector2::new(101.0, 101.0));
+    assert_eq!(coin.region, Some(TextureRegion::new(2, 2, 10, 12)));
+    assert_eq!(coin.drawn_size(), Vector2::new(10.0, 12.0));
+
+    let gem = sheet.sprite(1, Vector2::new(0.0, 0.0));
+    assert_eq!(gem.center, Vector2::new(0.0, 0.0));
+}
+
+#[test]
+fn loads_the_image_next_to_the_file() {
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("sprite_sheet");
+    fs::create_dir_all(&directory).unwrap();
+    let mut image = Vec::new();
+    let mut encoder = png::Encoder::new(&mut image, 64, 32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder
+        .write_header()
+        .unwrap()
+        .write_image_data(&[255; 64 * 32 * 3])
+        .unwrap();
+    fs::write(directory.join("items.png"), image).unwrap();
+    fs::write(directory.join("items.json"), TEXTURE_PACKER).unwrap();
+
+    let sheet = SpriteSheet::load(directory.join("items.json")).unwrap();
+    assert_eq!(sheet.texture.width(), 64);
+    assert_eq!(sheet.frames.len(), 2);
+
+    fs::write(directory.join("no_image.json"), r#"{ "frames": [] }"#).unwrap();
+    assert!(matches!(
+        SpriteSheet::load(directory.join("no_image.json")),
+        Err(SpriteSheetError::MissingImage)
+    ));
+}
+
+#[test]
+fn reports_invalid_sheets() {
+    let load = |json: &str| SpriteSheet::from_json(json, sheet_texture());
+
+    assert!(matches!(load("{"), Err(SpriteSheetError::Json(_))));
+    assert!(matches!(
+        load(r#"{ "frames": { "a": { "x": 0 } } }"#),
+        Err(SpriteSheetError::Json(_))
+    ));
+    assert!(matches!(
+        load(r#"{ "frames": { "a": { "frame": { "x": 0, "y": 0, "w": 8, "h": 8 }, "rotated": true } } }"#),
+        Err(SpriteSheetError::RotatedFrame(name)) if name == "a"
+    ));
+    assert!(matches!(
+        load(r#"{ "frames": { "a": { "frame": { "x": 60, "y": 0, "w": 8, "h": 8 } } } }"#),
+        Err(SpriteSheetError::FrameOutOfBounds(_))
+    ));
+    assert!(matches!(
+        load(
+            r#"{ "frames": { "a": { "frame": { "x": 0, "y": 0, "w": 8, "h": 8 } } },
+                 "meta": { "frameTags": [{ "name": "run", "from": 0, "to": 1 }] } }"#
+        ),
+        Err(SpriteSheetError::InvalidTag(name)) if name == "run"
+    ));
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use common::recording::RecordingBackend;
+use stellar_engine::{
+    math::Vector2,
+    rendering::{
+        Color, RenderBackend, RenderQueue,
+        shapes::{Line, LineCap, LineJoin, Polyline, Quad, Shape, Stroke, Tessellation},
+    },
+};
+
+/// Draws the shape, checks that all triangles face the camera and returns their total area.
+fn drawn_area(shape: Shape) -> f32 {
+    let mut queue = RenderQueue::default();
+    queue.add(shape);
+
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    backend
+        .triangles()
+        .map(|[a, b, c]| {
+            let area = ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])) * 0.5;
+            assert!(
+                area > 0.0,
+                "triangle {:?} is not counter-clockwise",
+                [a, b, c]
+            );
+            area
+        })
+        .sum()
+}
+
+fn line(stroke: Stroke) -> Shape {
+    Shape::Line(Line::new(Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0)).with_stroke(stroke))
+}
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < 1e-3,
+        "{actual} is not close to {expected}"
+    );
+}
+
+#[test]
+fn butt_caps_end_at_the_end_points() {
+    assert_close(drawn_area(line(Stroke::new(2.0))), 20.0);
+}
+
+#[test]
+fn square_caps_extend_past_the_end_points() {
+    assert_close(
+        drawn_area(line(Stroke::new(2.0).with_cap(LineCap::Square))),
+        24.0,
+    );
+}
+
+#[test]
+fn round_caps_add_half_circles() {
+    let area = drawn_area(line(
+        Stroke::new(2.0)
+            .with_cap(LineCap::Round)
+            .with_tessellation(Tessellation::Segments(64)),
+    ));
+
+    // The half circles are polygons inside of the real curve
+    assert!(area > 20.0 + std::f32::consts::PI * 0.95 && area < 20.0 + std::f32::consts::PI);
+}
+
+#[test]
+fn dashes_follow_the_pattern() {
+    // Dashes at 0..2, 3..5, 6..8 and 9..10
+    assert_close(
+        drawn_area(line(Stroke::new(2.0).with_dashes(vec![2.0, 1.0], 0.0))),
+        14.0,
+    );
+    // Dashes at 0..1, 2..4, 5..7 and 8..10
+    assert_close(
+        drawn_area(line(Stroke::new(2.0).with_dashes(vec![2.0, 1.0], 1.0))),
+        14.0,
+    );
+    // An odd pattern alternates its meaning, dashes at 0..1, 2..3, ...
+    assert_close(
+        drawn_area(line(Stroke::new(2.0).with_dashes(vec![1.0], 0.0))),
+        10.0,
+    );
+}
+
+#[test]
+fn short_dashes_on_long_lines_finish() {
+    let long_line = |stroke: Stroke| {
+        let end = Vector2::new(1e8, 0.0);
+        Shape::Line(Line::new(Vector2::new(0.0, 0.0), end).with_stroke(stroke))
+    };
+
+    // Far too many dashes, drawn solid instead
+    let solid = drawn_area(long_line(Stroke::new(2.0)));
+    let dashed = drawn_area(long_line(Stroke::new(2.0).with_dashes(vec![1.0, 1.0], 0.0)));
+    assert_eq!(dashed, solid);
+
+    // Few enough dashes, but the gaps are too short to move a position of 1e8 in f32
+    let mut queue = RenderQueue::default();
+    queue.add(long_line(
+        Stroke::new(2.0).with_dashes(vec![1e4 - 0.5, 0.5], 0.0),
+    ));
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+    assert_eq!(backend.triangles().count(), 10_000 * 2);
+}
+
+#[test]
+fn all_joins_face_the_camera() {
+    let zigzag = vec![
+        Vector2::new(0.0, 0.0),
+        Vector2::new(10.0, 0.0),
+        Vector2::new(2.0, 3.0),
+        Vector2::new(8.0, 8.0),
+        Vector2::new(0.0, 8.0),
+    ];
+
+    for join in [LineJoin::Miter, LineJoin::Bevel, LineJoin::Round] {
+        for closed in [false, true] {
+            let mut polyline =
+                Polyline::new(zigzag.clone()).with_stroke(Stroke::new(1.5).with_join(join));
+            polyline.closed = closed;
+
+            assert!(drawn_area(Shape::Polyline(polyline)) > 0.0);
+        }
+    }
+}
+
+#[test]
+fn sharp_miters_become_bevels() {
+    let spike = vec![
+        Vector2::new(0.0, 0.0),
+        Vector2::new(10.0, 0.0),
+        Vector2::new(0.0, 0.5),
+    ];
+    let area = |join| {
+        drawn_area(Shape::Polyline(
+            Polyline::new(spike.clone()).with_stroke(Stroke::new(1.0).with_join(join)),
+        ))
+    };
+
+    assert_close(area(LineJoin::Miter), area(LineJoin::Bevel));
+    assert!(
+        area(LineJoin::Miter)
+            < drawn_area(Shape::Polyline(
+                Polyline::new(spike.clone()).with_stroke(
+                    Stroke::new(1.0)
+                        .with_join(LineJoin::Miter)
+                        .with_miter_limit(100.0)
+                )
+            ))
+    );
+}
+
+#[test]
+fn joins_cover_each_point_once() {
+    let corner = vec![
+        Vector2::new(0.0, 0.0),
+        Vector2::new(10.0, 0.0),
+        Vector2::new(10.0, 10.0),
+    ];
+    let translucent = Stroke::new(2.0).with_color(Color::rgba(1.0, 1.0, 1.0, 0.5));
+    let area = |join| {
+        drawn_area(Shape::Polyline(
+            Polyline::new(corner.clone()).with_stroke(translucent.clone().with_join(join)),
+        ))
+    };
+
+    // Two 10 by 2 segments sharing a unit square inside the corner, plus the join outside
+    assert_close(area(LineJoin::Miter), 40.0);
+    assert_close(area(LineJoin::Bevel), 39.5);
+
+    // 12 by 12 without the 8 by 8 inside
+    let outline = Quad::square(Vector2::new(0.0, 0.0), 10.0).outline(translucent);
+    assert_close(drawn_area(Shape::Polyline(outline)), 80.0);
+}
+
+#[test]
+fn quad_outline_is_closed() {
+    let outline = Quad::square(Vector2::new(0.0, 0.0), 10.0).outline(Stroke::new(1.0));
+
+    assert!(outline.closed);
+    assert_eq!(outline.points.len(), 4);
+}
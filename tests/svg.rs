@@ -0,0 +1,344 @@
+mod common;
+
+use std::f32::consts::PI;
+
+use common::recording::RecordingBackend;
+use stellar_engine::{
+    math::Vector2,
+    rendering::{
+        BlendMode, Color, DrawParams, RenderBackend, RenderQueue,
+        shapes::{FillRule, LineCap, LineJoin, Path, Quad, Shape, Triangle},
+        svg::{self, SvgError},
+    },
+};
+
+fn document(content: &str) -> String {
+    format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">{content}</svg>"#)
+}
+
+/// Imports a document which must contain exactly one shape.
+fn import_one(content: &str) -> Path {
+    let mut shapes = svg::import(&document(content)).unwrap();
+    assert_eq!(shapes.len(), 1, "expected one shape in {content}");
+    match shapes.pop() {
+        Some(Shape::Path(path)) => path,
+        shape => panic!("expected a path, got {shape:?}"),
+    }
+}
+
+/// Total area of the triangles of the path, zoomed in so curves are close to exact.
+fn drawn_area(path: Path) -> f32 {
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Path(path));
+    let mut backend = RecordingBackend {
+        zoom: 10.0,
+        ..Default::default()
+    };
+    let Ok(()) = backend.draw_queue(&mut queue);
+
+    backend
+        .triangles()
+        .map(|[a, b, c]| ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])) * 0.5)
+        .sum()
+}
+
+fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "{actual} is not within {tolerance} of {expected}"
+    );
+}
+
+#[test]
+fn imports_rects_with_the_y_axis_pointing_up() {
+    let path = import_one(r##"<rect x="10" y="20" width="30" height="40" fill="#ff0000"/>"##);
+
+    assert_eq!(path.fill, Some(Color::RED));
+    assert_eq!(path.stroke, None);
+    let contours = path.flatten(1.0);
+    assert_eq!(
+        contours,
+        vec![(
+            vec![
+                Vector2::new(10.0, -20.0),
+                Vector2::new(40.0, -20.0),
+                Vector2::new(40.0, -60.0),
+                Vector2::new(10.0, -60.0),
+            ],
+            true
+        )]
+    );
+    assert_close(drawn_area(path), 1200.0, 1e-3);
+}
+
+#[test]
+fn rounds_rect_corners() {
+    let path = import_one(r#"<rect width="20" height="10" rx="2"/>"#);
+    assert_close(drawn_area(path), 200.0 - (4.0 - PI) * 4.0, 0.25);
+}
+
+#[test]
+fn imports_circles_and_ellipses() {
+    let circle = import_one(r#"<circle cx="10" cy="10" r="5"/>"#);
+    for point in &circle.flatten(1.0)[0].0 {
+        assert_close((*point - Vector2::new(10.0, -10.0)).length(), 5.0, 1e-3);
+    }
+    assert_close(drawn_area(circle), PI * 25.0, 1.0);
+
+    let ellipse = import_one(r#"<ellipse rx="4" ry="2"/>"#);
+    assert_close(drawn_area(ellipse), PI * 8.0, 1.0);
+}
+
+#[test]
+fn imports_lines_polylines_and_polygons() {
+    let line = import_one(r#"<line x1="0" y1="0" x2="10" y2="0" stroke="black"/>"#);
+    assert_eq!(line.stroke.map(|stroke| stroke.thickness), Some(1.0));
+
+    let polyline = import_one(r#"<polyline points="0,0 10,0 10,10" fill="none" stroke="red"/>"#);
+    assert_eq!(polyline.fill, None);
+    let contours = polyline.flatten(1.0);
+    assert!(!contours[0].1);
+    assert_eq!(contours[0].0.len(), 3);
+
+    let polygon = import_one(r#"<polygon points="0 0 10 0 10 10"/>"#);
+    assert!(polygon.flatten(1.0)[0].1);
+    assert_close(drawn_area(polygon), 50.0, 1e-3);
+}
+
+#[test]
+fn keeps_the_direction_of_arcs_when_flipping() {
+    // Sweeping from the left to the right end goes over the top on screen
+    let path = import_one(r#"<path d="M0 0 A5 5 0 0 1 10 0" fill="none" stroke="black"/>"#);
+    let contours = path.flatten(10.0);
+    assert!(contours[0].0.iter().all(|point| point.y >= -1e-3));
+    assert!(contours[0].0.iter().any(|point| point.y > 4.9));
+}
+
+#[test]
+fn applies_group_transforms() {
+    let path = import_one(
+        r#"<g transform="translate(5 5)"><g transform="scale(2)">
+            <circle r="1" stroke="blue" stroke-width="0.5"/>
+        </g></g>"#,
+    );
+
+    for point in &path.flatten(1.0)[0].0 {
+        assert_close((*point - Vector2::new(5.0, -5.0)).length(), 2.0, 1e-3);
+    }
+    assert_eq!(path.stroke.map(|stroke| stroke.thickness), Some(1.0));
+}
+
+#[test]
+fn rotates_around_a_point() {
+    let path = import_one(r#"<rect x="10" width="10" height="10" transform="rotate(90 10 0)"/>"#);
+    let (points, _) = &path.flatten(1.0)[0];
+    // A quarter turn clockwise on screen moves the rect from the right of the pivot to its left
+    for (point, expected) in
+        points
+            .iter()
+            .zip([(10.0, 0.0), (10.0, -10.0), (0.0, -10.0), (0.0, 0.0)])
+    {
+        assert_close(point.x, expected.0, 1e-4);
+        assert_close(point.y, expected.1, 1e-4);
+    }
+}
+
+#[test]
+fn inherits_and_overrides_styles() {
+    let path = import_one(
+        r#"<g fill="blue" stroke="lime" style="stroke-width:3; stroke-linecap:round">
+            <path d="M0 0 L10 0 L10 10 Z" fill="red" stroke-linejoin="bevel"
+                stroke-dasharray="2, 1" style="fill:yellow; fill-rule:evenodd"/>
+        </g>"#,
+    );
+
+    assert_eq!(path.fill, Some(Color::YELLOW));
+    assert_eq!(path.fill_rule, FillRule::EvenOdd);
+    let stroke = path.stroke.unwrap();
+    assert_eq!(stroke.color, Color::GREEN);
+    assert_eq!(stroke.thickness, 3.0);
+    assert_eq!(stroke.cap, LineCap::Round);
+    assert_eq!(stroke.join, LineJoin::Bevel);
+    assert_eq!(stroke.dash_pattern, vec![2.0, 1.0]);
+}
+
+#[test]
+fn parses_colors() {
+    let fill =
+        |value: &str| import_one(&format!(r#"<rect width="1" height="1" fill="{value}"/>"#)).fill;
+
+    assert_eq!(fill("#f00"), Some(Color::RED));
+    assert_eq!(fill("rgb(0, 0, 255)"), Some(Color::BLUE));
+    assert_eq!(fill("rgb(100%,100%,0%)"), Some(Color::YELLOW));
+    assert_eq!(
+        fill("orange").map(Color::to_rgba8),
+        Some([255, 165, 0, 255])
+    );
+    assert_eq!(fill("url(#gradient) white"), Some(Color::WHITE));
+    assert_eq!(
+        import_one(r#"<rect width="1" height="1" color="cyan" fill="currentColor"/>"#).fill,
+        Some(Color::CYAN)
+    );
+}
+
+#[test]
+fn multiplies_opacities() {
+    let path = import_one(
+        r#"<g opacity="0.5"><rect width="1" height="1" fill-opacity="0.5" opacity="0.5"/></g>"#,
+    );
+    assert_eq!(path.fill.map(|color| color.a), Some(0.125));
+}
+
+#[test]
+fn ignores_unsupported_presentation_values() {
+    let path = import_one(
+        r##"<g fill="#ff0000" stroke="#0000ff" stroke-width="3" stroke-linecap="round">
+            <rect width="1" height="1" fill="sparkly" stroke="rebeccapurple" stroke-width="1em"
+                stroke-linecap="inherit " style="fill-rule: wobbly; stroke-opacity: lots"/>
+        </g>"##,
+    );
+
+    assert_eq!(path.fill, Some(Color::RED));
+    assert_eq!(path.fill_rule, FillRule::NonZero);
+    let stroke = path.stroke.unwrap();
+    assert_eq!(stroke.color, Color::BLUE);
+    assert_eq!(stroke.thickness, 3.0);
+    assert_eq!(stroke.cap, LineCap::Round);
+}
+
+#[test]
+fn reads_opacity_percentages() {
+    let path = import_one(r#"<rect width="1" height="1" opacity="50%" fill-opacity="50%"/>"#);
+    assert_eq!(path.fill.map(|color| color.a), Some(0.25));
+
+    let path = import_one(r#"<rect width="1" height="1" opacity="150%"/>"#);
+    assert_eq!(path.fill.map(|color| color.a), Some(1.0));
+}
+
+#[test]
+fn skips_hidden_and_unsupported_elements() {
+    let shapes = svg::import(&document(
+        r#"<defs><rect width="1" height="1"/></defs>
+        <text>Hello</text>
+        <g display="none"><rect width="1" height="1"/></g>
+        <rect width="1" height="1" style="display:none"/>
+        <rect width="0" height="1"/>
+        <rect width="1" height="1" fill="none"/>
+        <circle r="1"/>"#,
+    ))
+    .unwrap();
+    assert_eq!(shapes.len(), 1);
+}
+
+#[test]
+fn adds_shapes_to_a_queue() {
+    let mut queue = RenderQueue::default();
+    let handles = queue
+        .add_svg(&document(r#"<rect width="1" height="1"/><circle r="1"/>"#))
+        .unwrap();
+    assert_eq!(handles.len(), 2);
+    assert_eq!(queue.len(), 2);
+}
+
+#[test]
+fn reports_invalid_documents() {
+    assert!(matches!(svg::import("<svg"), Err(SvgError::Xml(_))));
+    assert!(matches!(
+        svg::import("<html></html>"),
+        Err(SvgError::NotSvg)
+    ));
+    assert!(matches!(
+        svg::import(&document(r#"<path d="M 0"/>"#)),
+        Err(SvgError::InvalidPath(_))
+    ));
+    match svg::import(&document(
+        r#"<rect width="1" height="1" transform="spin(3)"/>"#,
+    )) {
+        Err(SvgError::InvalidValue { name, value }) => {
+            assert_eq!((name.as_str(), value.as_str()), ("transform", "spin(3)"));
+        }
+        result => panic!("expected an invalid value, got {result:?}"),
+    }
+    assert!(matches!(
+        svg::import(&document(r#"<rect width="1em" height="1"/>"#)),
+        Err(SvgError::InvalidValue { .. })
+    ));
+}
+
+#[test]
+fn exports_triangles_in_world_units() {
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Quad(
+        Quad::square(Vector2::new(1.0, 2.0), 2.0).with_color(Color::RED),
+    ));
+    queue.add(Shape::Triangle(
+        Triangle::new([
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(0.0, 1.0),
+        ])
+        .with_color(Color::rgba(0.0, 0.0, 1.0, 0.5)),
+    ));
+
+    assert_eq!(
+        queue.to_svg(10.0),
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="30" viewBox="0 -3 2 3">
+  <path fill="#ff0000" d="M0 -3L0 -1L2 -1ZM0 -3L2 -1L2 -3Z"/>
+  <path fill="#0000ff" fill-opacity="0.5019608" d="M0 0L1 0L0 -1Z"/>
+</svg>
+"##
+    );
+}
+
+#[test]
+fn exports_blend_modes_as_groups() {
+    let mut queue = RenderQueue::default();
+    let square = |color| Shape::Quad(Quad::square(Vector2::new(0.0, 0.0), 2.0).with_color(color));
+    queue.add(square(Color::WHITE));
+    queue.add_with(
+        square(Color::GRAY),
+        DrawParams::default().with_blend_mode(BlendMode::Multiply),
+    );
+    let hidden = queue.add(square(Color::BLACK));
+    queue.set_visible(hidden, false);
+
+    let document = queue.to_svg(1.0);
+    assert_eq!(document.matches("<path").count(), 2);
+    assert!(
+        document.contains("  <g style=\"mix-blend-mode:multiply\">\n    <path fill=\"#808080\"")
+    );
+    assert!(!document.contains("#000000"));
+}
+
+#[test]
+fn exported_documents_import_to_the_same_area() {
+    let mut queue = RenderQueue::default();
+    queue
+        .add_svg(&document(
+            r#"<circle cx="5" cy="5" r="4" fill="red"/>
+        <path d="M0 0 Q10 10 20 0 Z" fill="blue"/>"#,
+        ))
+        .unwrap();
+    let expected = triangle_areas(&mut queue);
+
+    let mut imported = RenderQueue::default();
+    imported.add_svg(&queue.to_svg(1.0)).unwrap();
+    assert_close(triangle_areas(&mut imported), expected, 1e-2);
+}
+
+fn triangle_areas(queue: &mut RenderQueue) -> f32 {
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(queue);
+    backend
+        .triangles()
+        .map(|[a, b, c]| ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])) * 0.5)
+        .sum()
+}
+
+#[test]
+fn exports_empty_queues() {
+    assert_eq!(
+        RenderQueue::default().to_svg(1.0),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"0\" height=\"0\" viewBox=\"0 0 0 0\">\n</svg>\n"
+    );
+}
@@ -0,0 +1,182 @@
+mod common;
+
+use std::path::PathBuf;
+
+use common::recording::RecordingBackend;
+use stellar_engine::{
+    math::Vector2,
+    rendering::{
+        Color, Font, FontError, GlyphAtlas, RenderBackend, RenderQueue,
+        shapes::{Shape, Text, TextAlign},
+    },
+};
+
+fn font() -> Font {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fonts/Cantarell-Regular.ttf");
+    Font::load(path).unwrap()
+}
+
+fn ascii(size: f32) -> GlyphAtlas {
+    font().glyph_atlas(size, ' '..='~').unwrap()
+}
+
+/// Vertices and indices `text` is drawn with.
+fn draw(text: Text) -> RecordingBackend {
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Text(text));
+    let mut backend = RecordingBackend::default();
+    let Ok(()) = backend.draw_queue(&mut queue);
+    backend
+}
+
+/// Smallest and largest x of the vertices drawn.
+fn x_extent(backend: &RecordingBackend) -> (f32, f32) {
+    backend
+        .vertices
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(min, max), vertex| {
+            (min.min(vertex.position[0]), max.max(vertex.position[0]))
+        })
+}
+
+#[test]
+fn rasterizes_glyphs_into_an_atlas() {
+    let atlas = ascii(32.0);
+    assert_eq!(atlas.size(), 32.0);
+    assert!((atlas.ascent() - atlas.descent() - 32.0).abs() < 1e-3);
+    assert!(atlas.line_height() >= 32.0);
+
+    let space = atlas.glyph(' ').unwrap();
+    assert_eq!(space.region, None);
+    assert!(space.advance > 0.0);
+
+    // The top of an H is above the baseline and its bottom sits on it
+    let h = atlas.glyph('H').unwrap();
+    let region = h.region.unwrap();
+    assert!(h.offset.y < 0.0);
+    assert!((h.offset.y + region.height as f32).abs() <= 1.0);
+    assert!(region.width as f32 <= h.advance + 1.0);
+
+    // The glyphs are white with their coverage in alpha
+    let texture = atlas.texture();
+    let alphas: Vec<u8> = (region.y..region.y + region.height)
+        .flat_map(|y| (region.x..region.x + region.width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let start = ((y * texture.width() + x) * 4) as usize;
+            assert_eq!(&texture.pixels()[start..start + 3], [255; 3]);
+            texture.pixels()[start + 3]
+        })
+        .collect();
+    assert!(alphas.contains(&255) && alphas.contains(&0));
+
+    assert!(atlas.glyph('é').is_none());
+    assert!(!font().has_glyph('\u{10FFFF}'));
+}
+
+#[test]
+fn reports_invalid_fonts() {
+    assert!(matches!(
+        Font::from_bytes(b"not a font".to_vec()),
+        Err(FontError::InvalidFont)
+    ));
+    assert!(matches!(
+        Font::load("tests/fonts/missing.ttf"),
+        Err(FontError::Io(_))
+    ));
+}
+
+#[test]
+fn measures_lines_by_their_advances() {
+    let atlas = ascii(32.0);
+    let text = Text::new("AV", atlas.clone(), Vector2::new(0.0, 0.0));
+    let advances = atlas.glyph('A').unwrap().advance + atlas.glyph('V').unwrap().advance;
+    let expected = advances + atlas.kerning('A', 'V');
+    assert!((text.drawn_size().x - expected).abs() < 1e-3);
+    // Pairs with a character outside of the atlas are never kerned
+    assert_eq!(atlas.kerning('A', '\u{e9}'), 0.0);
+}
+
+#[test]
+fn draws_one_quad_per_visible_glyph() {
+    let atlas = ascii(16.0);
+    let backend = draw(Text::new(
+        "Hi there!\nok",
+        atlas.clone(),
+        Vector2::new(0.0, 0.0),
+    ));
+    assert_eq!(backend.vertices.len(), 10 * 4);
+    assert_eq!(backend.indices.len(), 10 * 6);
+    assert_eq!(backend.batches.len(), 1);
+    assert_eq!(backend.batches[0].texture.as_ref(), Some(atlas.texture()));
+
+    // Every glyph is front-facing and below the top of the text
+    for [a, b, c] in backend.triangles() {
+        let area = (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]);
+        assert!(area > 0.0);
+        assert!(a[1] <= 0.0 && b[1] <= 0.0 && c[1] <= 0.0);
+    }
+}
+
+#[test]
+fn aligns_lines_to_the_position() {
+    let atlas = ascii(16.0);
+    let text = |align| Text::new("Score", atlas.clone(), Vector2::new(50.0, 0.0)).with_align(align);
+    let width = text(TextAlign::Left).drawn_size().x;
+
+    let (left, _) = x_extent(&draw(text(TextAlign::Left)));
+    let (_, right) = x_extent(&draw(text(TextAlign::Right)));
+    let (center_left, center_right) = x_extent(&draw(text(TextAlign::Center)));
+    assert!((left - 50.0).abs() < 2.0);
+    assert!((right - 50.0).abs() < 2.0);
+    assert!((center_left - (50.0 - width / 2.0)).abs() < 2.0);
+    assert!(((center_left + center_right) / 2.0 - 50.0).abs() < 2.0);
+}
+
+#[test]
+fn wraps_lines_at_the_maximum_width() {
+    let atlas = ascii(16.0);
+    let text = Text::new(
+        "the quick brown fox jumps over the lazy dog",
+        atlas.clone(),
+        Vector2::new(0.0, 0.0),
+    );
+    let unwrapped = text.drawn_size();
+    let wrapped = text.clone().with_max_width(unwrapped.x / 2.0);
+
+    let lines = wrapped.lines();
+    assert!(lines.len() >= 2, "{lines:?}");
+    assert_eq!(lines.join(" "), text.text);
+    assert!(wrapped.drawn_size().x <= unwrapped.x / 2.0);
+    assert!(
+        (wrapped.drawn_size().y - unwrapped.y - (lines.len() - 1) as f32 * atlas.line_height())
+            .abs()
+            < 1e-3
+    );
+
+    // Words that do not fit on a line of their own are broken up
+    let long = Text::new("aaaaaaaaaa bb", atlas.clone(), Vector2::new(0.0, 0.0));
+    let width = Text::new("aaaa", atlas, Vector2::new(0.0, 0.0))
+        .drawn_size()
+        .x;
+    let lines = long.with_max_width(width + 0.5).lines();
+    assert_eq!(lines[..2], ["aaaa", "aaaa"]);
+    assert_eq!(lines[2..].join(" "), "aa bb");
+}
+
+#[test]
+fn scales_with_the_size() {
+    let atlas = ascii(16.0);
+    let text = Text::new("Level 3\nLives 2", atlas, Vector2::new(0.0, 0.0));
+    let size = text.drawn_size();
+    let double = text.clone().with_size(32.0).drawn_size();
+    assert!((double.x - size.x * 2.0).abs() < 1e-3);
+    assert!((double.y - size.y * 2.0).abs() < 1e-3);
+
+    let colored = draw(text.with_color(Color::rgb(1.0, 0.0, 0.0)));
+    assert!(
+        colored
+            .vertices
+            .iter()
+            .all(|vertex| vertex.color == [1.0, 0.0, 0.0, 1.0])
+    );
+}
@@ -0,0 +1,221 @@
+mod common;
+
+use std::{
+    f32::consts::{FRAC_PI_2, PI},
+    path::PathBuf,
+};
+
+use common::{approx::assert_vector_close, recording::draw};
+use stellar_engine::{
+    math::Vector2,
+    rendering::{
+        Font, RenderQueue, Texture,
+        shapes::{
+            Circle, Ellipse, Path, Quad, Shape, Sprite, Text, TextAlign, Transform2D, Triangle,
+        },
+    },
+};
+
+#[test]
+fn transform_rotates_and_scales_around_pivot() {
+    let transform = Transform2D::default()
+        .with_pivot(Vector2::new(1.0, 1.0))
+        .with_rotation(FRAC_PI_2)
+        .with_scale(Vector2::new(2.0, 3.0))
+        .with_position(Vector2::new(10.0, 0.0));
+    // An explicit pivot ignores the center of the shape
+    let affine = transform.to_affine(Vector2::new(50.0, 50.0));
+
+    assert_vector_close(
+        affine * Vector2::new(1.0, 1.0),
+        Vector2::new(11.0, 1.0),
+        1e-4,
+    );
+    // One unit right of the pivot is scaled to two, then turned to point up
+    assert_vector_close(
+        affine * Vector2::new(2.0, 1.0),
+        Vector2::new(11.0, 3.0),
+        1e-4,
+    );
+    assert_vector_close(
+        affine * Vector2::new(1.0, 2.0),
+        Vector2::new(8.0, 1.0),
+        1e-4,
+    );
+
+    let around_center = Transform2D::default().with_rotation(FRAC_PI_2);
+    assert_vector_close(
+        around_center.to_affine(Vector2::new(1.0, 1.0)) * Vector2::new(2.0, 1.0),
+        Vector2::new(1.0, 2.0),
+        1e-4,
+    );
+
+    assert!(Transform2D::default().is_identity());
+    assert!(
+        Transform2D::default()
+            .with_pivot(Vector2::new(5.0, 5.0))
+            .is_identity()
+    );
+    assert!(!transform.is_identity());
+}
+
+#[test]
+fn rotated_rect_spins_around_its_center() {
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Quad(
+        Quad::rect(Vector2::new(10.0, 0.0), Vector2::new(4.0, 2.0)).rotated(FRAC_PI_2),
+    ));
+
+    let backend = draw(&mut queue);
+    let (min, max) = backend.bounds();
+    assert_vector_close(min, Vector2::new(9.0, -2.0), 1e-4);
+    assert_vector_close(max, Vector2::new(11.0, 2.0), 1e-4);
+    backend.assert_counter_clockwise();
+}
+
+#[test]
+fn points_stay_unchanged_by_the_transform() {
+    let quad = Quad::square(Vector2::new(3.0, 4.0), 2.0).rotated(PI);
+    assert_eq!(
+        quad.points,
+        Quad::square(Vector2::new(3.0, 4.0), 2.0).points
+    );
+    assert_eq!(quad.transform.pivot, None);
+    assert_eq!(quad.transform.rotation, PI);
+
+    let outline = quad.outline(Default::default());
+    assert_eq!(outline.transform, quad.transform);
+}
+
+#[test]
+fn changing_the_transform_moves_a_queued_shape() {
+    let mut queue = RenderQueue::default();
+    let handle = queue.add(Shape::Quad(Quad::square(Vector2::new(0.0, 0.0), 2.0)));
+    let (_, max) = draw(&mut queue).bounds();
+    assert_vector_close(max, Vector2::new(1.0, 1.0), 1e-4);
+
+    let transform = queue.get_mut(handle).unwrap().transform_mut();
+    transform.position = Vector2::new(5.0, 0.0);
+    transform.scale = Vector2::new(2.0, 2.0);
+
+    let (min, max) = draw(&mut queue).bounds();
+    assert_vector_close(min, Vector2::new(3.0, -2.0), 1e-4);
+    assert_vector_close(max, Vector2::new(7.0, 2.0), 1e-4);
+}
+
+#[test]
+fn mirrored_shapes_stay_visible() {
+    let mut queue = RenderQueue::default();
+    let triangle = Triangle::new([
+        Vector2::new(0.0, 0.0),
+        Vector2::new(3.0, 0.0),
+        Vector2::new(0.0, 3.0),
+    ]);
+    queue.add(Shape::Triangle(triangle.with_transform(
+        Transform2D::default().with_scale(Vector2::new(-1.0, 1.0)),
+    )));
+
+    let backend = draw(&mut queue);
+    let (min, max) = backend.bounds();
+    assert_vector_close(min, Vector2::new(-1.0, 0.0), 1e-4);
+    assert_vector_close(max, Vector2::new(2.0, 3.0), 1e-4);
+    backend.assert_counter_clockwise();
+}
+
+#[test]
+fn scaled_curves_get_more_segments() {
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Circle(Circle::new(Vector2::new(0.0, 0.0), 2.0)));
+    let plain = draw(&mut queue).triangles().count();
+
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Circle(
+        Circle::new(Vector2::new(0.0, 0.0), 2.0)
+            .with_transform(Transform2D::default().with_scale(Vector2::new(20.0, 20.0))),
+    ));
+    let scaled = draw(&mut queue).triangles().count();
+
+    assert!(scaled > plain, "{scaled} triangles, {plain} without scale");
+}
+
+#[test]
+fn rotations_add_up() {
+    let quad = Quad::rect(Vector2::new(0.0, 0.0), Vector2::new(4.0, 2.0))
+        .rotated(0.1)
+        .rotated(0.1);
+    assert!((quad.transform.rotation - 0.2).abs() < 1e-6);
+
+    let ellipse = Ellipse::new(Vector2::new(0.0, 0.0), Vector2::new(2.0, 1.0))
+        .rotated(FRAC_PI_2)
+        .rotated(FRAC_PI_2);
+    assert!((ellipse.rotation - PI).abs() < 1e-6);
+    assert_eq!(ellipse.transform, Transform2D::default());
+}
+
+#[test]
+fn pivot_follows_the_moved_shape() {
+    let mut queue = RenderQueue::default();
+    let handle = queue.add(Shape::Sprite(
+        Sprite::new(
+            Texture::from_rgba8(1, 1, vec![255; 4]).unwrap(),
+            Vector2::new(0.0, 0.0),
+        )
+        .with_size(Vector2::new(4.0, 2.0)),
+    ));
+    draw(&mut queue);
+
+    let Some(Shape::Sprite(sprite)) = queue.get_mut(handle) else {
+        unreachable!()
+    };
+    sprite.center = Vector2::new(20.0, 10.0);
+    sprite.transform.rotation = FRAC_PI_2;
+
+    let (min, max) = draw(&mut queue).bounds();
+    assert_vector_close(min, Vector2::new(19.0, 8.0), 1e-4);
+    assert_vector_close(max, Vector2::new(21.0, 12.0), 1e-4);
+}
+
+#[test]
+fn paths_turn_around_their_center() {
+    let path = Path::new()
+        .move_to(Vector2::new(498.0, 299.0))
+        .line_to(Vector2::new(502.0, 299.0))
+        .line_to(Vector2::new(502.0, 301.0))
+        .line_to(Vector2::new(498.0, 301.0))
+        .close();
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Path(
+        path.with_transform(Transform2D::default().with_rotation(FRAC_PI_2)),
+    ));
+
+    let (min, max) = draw(&mut queue).bounds();
+    assert_vector_close(min, Vector2::new(499.0, 298.0), 1e-4);
+    assert_vector_close(max, Vector2::new(501.0, 302.0), 1e-4);
+}
+
+#[test]
+fn text_turns_around_the_middle_of_its_lines() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fonts/Cantarell-Regular.ttf");
+    let atlas = Font::load(path)
+        .unwrap()
+        .glyph_atlas(32.0, ' '..='~')
+        .unwrap();
+    let text =
+        Text::new("Hello\nworld", atlas, Vector2::new(100.0, 50.0)).with_align(TextAlign::Center);
+    let size = text.drawn_size();
+    let center = Vector2::new(100.0, 50.0 - size.y * 0.5);
+
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Text(text.clone()));
+    let (min, max) = draw(&mut queue).bounds();
+
+    let mut queue = RenderQueue::default();
+    queue.add(Shape::Text(
+        text.with_transform(Transform2D::default().with_rotation(PI)),
+    ));
+    let (turned_min, turned_max) = draw(&mut queue).bounds();
+
+    // Half a turn mirrors the glyphs through the center
+    assert_vector_close(turned_min, center * 2.0 - max, 1e-4);
+    assert_vector_close(turned_max, center * 2.0 - min, 1e-4);
+}
@@ -0,0 +1,168 @@
+use stellar_engine::math::{Vector2, Vector3, Vector4};
+
+#[test]
+fn vector3_operators() {
+    let mut a = Vector3::new(1, 2, 3);
+    let b = Vector3::new(4, 5, 6);
+    assert_eq!(a + b, Vector3::new(5, 7, 9));
+    assert_eq!(b - a, Vector3::new(3, 3, 3));
+    assert_eq!(a * 2, Vector3::new(2, 4, 6));
+    assert_eq!(b / 2, Vector3::new(2, 2, 3));
+    assert_eq!(b % 4, Vector3::new(0, 1, 2));
+    assert_eq!(-a, Vector3::new(-1, -2, -3));
+
+    a += b;
+    a -= Vector3::new(1, 1, 1);
+    a *= 3;
+    assert_eq!(a, Vector3::new(12, 18, 24));
+    assert_eq!(Vector3::new(2.0, 3.0, 6.0).length(), 7.0);
+}
+
+#[test]
+fn vector4_operators() {
+    let mut a = Vector4::new(1.0, 2.0, 3.0, 4.0);
+    let b = Vector4::new(0.5, 0.5, 0.5, 0.5);
+    assert_eq!(a + b, Vector4::new(1.5, 2.5, 3.5, 4.5));
+    assert_eq!(a - b, Vector4::new(0.5, 1.5, 2.5, 3.5));
+    assert_eq!(-b * 2.0, Vector4::new(-1.0, -1.0, -1.0, -1.0));
+
+    a /= 2.0;
+    a += b;
+    assert_eq!(a, Vector4::new(1.0, 1.5, 2.0, 2.5));
+    assert_eq!(Vector4::new(1, 1, 1, 1).length_squared(), 4);
+    assert_eq!(Vector4::new(2.0, 2.0, 2.0, 2.0).length(), 4.0);
+}
+
+#[test]
+fn converts_between_sizes() {
+    let point = Vector2::new(1, 2).extend(3);
+    assert_eq!(point, Vector3::new(1, 2, 3));
+    let homogeneous = point.extend(1);
+    assert_eq!(homogeneous, Vector4::new(1, 2, 3, 1));
+    assert_eq!(homogeneous.truncate(), point);
+    assert_eq!(point.truncate(), Vector2::new(1, 2));
+
+    assert_eq!(Vector2::from([1, 2]), Vector2::from((1, 2)));
+    assert_eq!(Vector3::from([1, 2, 3]), Vector3::from((1, 2, 3)));
+    assert_eq!(Vector4::from([1, 2, 3, 4]), Vector4::from((1, 2, 3, 4)));
+    assert_eq!(<[f32; 2]>::from(Vector2::new(1.0, 2.0)), [1.0, 2.0]);
+    assert_eq!(<[u8; 3]>::from(Vector3::new(1, 2, 3)), [1, 2, 3]);
+    assert_eq!(<[i64; 4]>::from(Vector4::new(1, 2, 3, 4)), [1, 2, 3, 4]);
+}
+
+#[test]
+fn swizzles() {
+    let v2 = Vector2::new(1, 2);
+    assert_eq!(v2.xy(), v2);
+    assert_eq!(v2.yx(), Vector2::new(2, 1));
+
+    let v3 = Vector3::new(1, 2, 3);
+    assert_eq!(v3.xy(), Vector2::new(1, 2));
+    assert_eq!(v3.zx(), Vector2::new(3, 1));
+    assert_eq!(v3.xyz(), v3);
+    assert_eq!(v3.zyx(), Vector3::new(3, 2, 1));
+    assert_eq!(v3.yzx(), Vector3::new(2, 3, 1));
+
+    let v4 = Vector4::new(1, 2, 3, 4);
+    assert_eq!(v4.xy(), Vector2::new(1, 2));
+    assert_eq!(v4.wz(), Vector2::new(4, 3));
+    assert_eq!(v4.xyz(), v4.truncate());
+    assert_eq!(v4.wyx(), Vector3::new(4, 2, 1));
+}
+
+fn assert_close(actual: Vector2<f32>, expected: Vector2<f32>) {
+    assert!(
+        actual.approx_eq(expected, 1e-5),
+        "{actual:?} is not {expected:?}"
+    );
+}
+
+#[test]
+fn vector2_products() {
+    let a = Vector2::new(3, 4);
+    let b = Vector2::new(-2, 1);
+    assert_eq!(a.dot(b), -2);
+    assert_eq!(a.perp_dot(b), 11);
+    assert_eq!(b.perp_dot(a), -11);
+    assert_eq!(a.perpendicular(), Vector2::new(-4, 3));
+    assert_eq!(a.perpendicular().dot(a), 0);
+    assert_eq!(a.distance_squared(b), 34);
+    assert_eq!(Vector2::new(0.0, 0.0).distance(Vector2::new(3.0, 4.0)), 5.0);
+}
+
+#[test]
+fn vector2_normalization() {
+    let v = Vector2::new(3.0f32, 4.0);
+    assert_close(v.normalize(), Vector2::new(0.6, 0.8));
+    assert_eq!(v.try_normalize(), Some(v.normalize()));
+    assert_eq!(Vector2::new(0.0f32, 0.0).try_normalize(), None);
+    assert_eq!(Vector2::new(f32::INFINITY, 0.0).try_normalize(), None);
+    assert!(Vector2::new(0.0f32, 0.0).normalize().x.is_nan());
+
+    assert_close(v.clamp_length(0.0, 2.5), Vector2::new(1.5, 2.0));
+    assert_close(v.clamp_length(10.0, 20.0), Vector2::new(6.0, 8.0));
+    assert_close(v.clamp_length(1.0, 10.0), v);
+    assert_eq!(
+        Vector2::new(0.0f32, 0.0).clamp_length(1.0, 2.0),
+        Vector2::new(0.0, 0.0)
+    );
+}
+
+#[test]
+fn vector2_angles() {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    assert_close(Vector2::from_angle(FRAC_PI_2), Vector2::new(0.0, 1.0));
+    assert!((Vector2::new(-1.0f32, 1.0).angle() - 3.0 * PI / 4.0).abs() < 1e-6);
+
+    let x = Vector2::new(2.0f32, 0.0);
+    assert!((x.angle_between(Vector2::new(0.0, 5.0)) - FRAC_PI_2).abs() < 1e-6);
+    assert!((x.angle_between(Vector2::new(0.0, -5.0)) + FRAC_PI_2).abs() < 1e-6);
+    assert_close(x.rotate(FRAC_PI_2), Vector2::new(0.0, 2.0));
+    assert_close(x.rotate(PI), Vector2::new(-2.0, 0.0));
+}
+
+#[test]
+fn vector2_projection_and_interpolation() {
+    let v = Vector2::new(2.0f32, 3.0);
+    assert_close(
+        v.project_onto(Vector2::new(4.0, 0.0)),
+        Vector2::new(2.0, 0.0),
+    );
+    assert_close(
+        v.project_onto(Vector2::new(1.0, 1.0)),
+        Vector2::new(2.5, 2.5),
+    );
+
+    // A ball falling onto the floor bounces up
+    assert_eq!(
+        Vector2::new(1, -2).reflect(Vector2::new(0, 1)),
+        Vector2::new(1, 2)
+    );
+    assert_close(
+        Vector2::new(1.0f32, 0.0).reflect(Vector2::new(-1.0, 0.0)),
+        Vector2::new(-1.0, 0.0),
+    );
+
+    let end = Vector2::new(10.0, -5.0);
+    assert_eq!(v.lerp(end, 0.0), v);
+    assert_eq!(v.lerp(end, 1.0), end);
+    assert_close(v.lerp(end, 0.25), Vector2::new(4.0, 1.0));
+}
+
+#[test]
+fn vector2_components() {
+    let a = Vector2::new(1, -5);
+    let b = Vector2::new(-2, 3);
+    assert_eq!(a.min(b), Vector2::new(-2, -5));
+    assert_eq!(a.max(b), Vector2::new(1, 3));
+    assert_eq!(a.abs(), Vector2::new(1, 5));
+    assert_eq!(
+        Vector2::new(0.5f32, 2.0).max(Vector2::new(1.0, 1.0)),
+        Vector2::new(1.0, 2.0)
+    );
+
+    let v = Vector2::new(1.0f32, 2.0);
+    assert!(v.approx_eq(Vector2::new(1.0005, 1.9995), 1e-3));
+    assert!(!v.approx_eq(Vector2::new(1.01, 2.0), 1e-3));
+}